@@ -1,4 +1,5 @@
 use fstapi::{Error, Handle, Hier, Reader, Result, Scope, ScopeType, Writer};
+use fstapi::{attr_type, misc_type};
 use regex::Regex;
 use std::collections::HashMap;
 
@@ -61,20 +62,22 @@ pub fn build(
             writer.set_scope(s.ty, &s.name, &s.component)?;
           }
         }
-        // Write the current variable to the output.
-        let handle = writer.create_var(
-          v.ty(),
-          v.direction(),
-          v.length(),
-          name,
-          handles.get(&v.handle()).copied(),
-        )?;
+        // Write the current variable to the output,
+        // along with its supplemental type information.
+        let alias = handles.get(&v.handle()).copied();
+        let handle = match v.supplemental() {
+          Some(s) => writer.create_var2(v.ty(), v.direction(), v.length(), name, alias, s)?,
+          None => writer.create_var(v.ty(), v.direction(), v.length(), name, alias)?,
+        };
         // Update mappings between input handles and output handles.
         handles.insert(v.handle(), handle);
       }
 
       // Write attributes only when `strip_attrs` is `false`.
-      Hier::AttrBegin(a) if !strip_attrs => {
+      // Supplemental type information is written along with variables.
+      Hier::AttrBegin(a)
+        if !strip_attrs && (a.ty() != attr_type::MISC || a.subtype() != misc_type::SUPVAR) =>
+      {
         writer.set_attr_begin(a.ty(), a.subtype(), a.name()?, a.arg())?
       }
       Hier::AttrEnd if !strip_attrs => writer.set_attr_end(),
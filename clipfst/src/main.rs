@@ -154,7 +154,8 @@ fn try_main() -> Result<()> {
   }
 
   // Write value change data.
-  VcdWriter::new(writer, start, end, handles).write(&mut reader)
+  let dump_activity: Vec<_> = reader.dump_activity_changes().collect();
+  VcdWriter::new(writer, start, end, handles, dump_activity).write(&mut reader)
 }
 
 fn get_start_end(reader: &Reader, start: Option<u64>, end: Option<u64>) -> (u64, u64) {
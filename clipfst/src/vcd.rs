@@ -1,6 +1,6 @@
 use crate::try_or_exit;
 use fstapi::{Handle, Reader, Result, Writer};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::mem;
 
 /// Value change data writer.
@@ -11,10 +11,31 @@ pub struct VcdWriter {
   handles: HashMap<Handle, Handle>,
   last_time: u64,
   last_values: HashMap<Handle, Box<[u8]>>,
+  dump_activity: VecDeque<(u64, bool)>,
 }
 
 impl VcdWriter {
-  pub fn new(writer: Writer, start: u64, end: u64, handles: HashMap<Handle, Handle>) -> Self {
+  pub fn new(
+    writer: Writer,
+    start: u64,
+    end: u64,
+    handles: HashMap<Handle, Handle>,
+    dump_activity: impl IntoIterator<Item = (u64, bool)>,
+  ) -> Self {
+    // Keep dump activity changes in the time range,
+    // changes before the start time are merged into the start time.
+    let mut active = true;
+    let mut changes = VecDeque::new();
+    for (time, a) in dump_activity {
+      if time < start {
+        active = a;
+      } else if time <= end {
+        changes.push_back((time, a));
+      }
+    }
+    if !active {
+      changes.push_front((start, false));
+    }
     Self {
       writer,
       start_time: start,
@@ -22,6 +43,7 @@ impl VcdWriter {
       handles,
       last_time: start,
       last_values: HashMap::new(),
+      dump_activity: changes,
     }
   }
 
@@ -65,21 +87,33 @@ impl VcdWriter {
   }
 
   fn write_time_change(&mut self, time: u64) {
+    // Write dump activity changes up to the given time.
+    while let Some(&(t, active)) = self.dump_activity.front()
+      && t <= time
+    {
+      self.dump_activity.pop_front();
+      self.emit_time_change(t);
+      self.writer.emit_dump_active(active);
+    }
+    self.emit_time_change(time);
+  }
+
+  fn emit_time_change(&mut self, time: u64) {
     if time != self.last_time {
       let ret = self.writer.emit_time_change(time - self.start_time);
-      try_or_exit!(ret, _, "Failed to write time change!");
+      try_or_exit!(ret, e, "Failed to write time change: {e}!");
       self.last_time = time;
     }
   }
 
   fn write_value_change(&mut self, handle: Handle, value: &[u8]) {
     let ret = self.writer.emit_value_change(self.handles[&handle], value);
-    try_or_exit!(ret, _, "Failed to write value change!");
+    try_or_exit!(ret, e, "Failed to write value change: {e}!");
   }
 
   fn write_var_len_value_change(&mut self, handle: Handle, value: &[u8]) {
     let handle = self.handles[&handle];
     let ret = self.writer.emit_var_len_value_change(handle, value);
-    try_or_exit!(ret, _, "Failed to write value change!");
+    try_or_exit!(ret, e, "Failed to write value change: {e}!");
   }
 }
@@ -0,0 +1,48 @@
+//! Helpers shared by integration tests.
+
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs, process};
+
+/// A temporary waveform file, which is removed when dropped.
+pub struct TempFile(PathBuf);
+
+impl TempFile {
+  /// Creates a new temporary file path with the given name.
+  pub fn new(name: &str) -> Self {
+    let file_name = format!("clipfst-test-{}-{name}.fst", process::id());
+    Self(env::temp_dir().join(file_name))
+  }
+
+  /// Returns the path of the file.
+  pub fn path(&self) -> &Path {
+    &self.0
+  }
+}
+
+impl Drop for TempFile {
+  fn drop(&mut self) {
+    let _ = fs::remove_file(&self.0);
+    // The hierarchy is written to a separate file if it is not compressed.
+    let mut hier = self.0.clone().into_os_string();
+    hier.push(".hier");
+    let _ = fs::remove_file(hier);
+  }
+}
+
+/// Runs `clipfst` on the given input and output files
+/// with the given arguments.
+///
+/// Panics if `clipfst` fails.
+pub fn clipfst(input: &TempFile, output: &TempFile, args: &[&str]) {
+  let out = Command::new(env!("CARGO_BIN_EXE_clipfst"))
+    .arg(input.path())
+    .arg(output.path())
+    .args(args)
+    .output()
+    .unwrap();
+  let stderr = String::from_utf8_lossy(&out.stderr);
+  assert!(out.status.success(), "clipfst failed: {stderr}");
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::*;
+use fstapi::{Reader, Writer, scope_type, var_dir, var_type};
+
+/// Dump activity changes of the waveform written by [`write_dump_activity`].
+const CHANGES: [(u64, bool); 4] = [(20, false), (50, true), (70, false), (90, true)];
+
+/// Writes a waveform with dump activity changes in [`CHANGES`].
+fn write_dump_activity(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  writer.set_upscope();
+  let mut changes = CHANGES.iter().peekable();
+  for time in (0..=100).step_by(10) {
+    writer.emit_time_change(time).unwrap();
+    while let Some((_, active)) = changes.next_if(|(t, _)| *t == time) {
+      writer.emit_dump_active(*active);
+    }
+    let value = if time % 20 == 0 { b"0" } else { b"1" };
+    writer.emit_value_change(clk, value).unwrap();
+  }
+}
+
+/// Clips the waveform with the given arguments,
+/// returns dump activity changes of the output.
+fn clip(name: &str, args: &[&str]) -> Vec<(u64, bool)> {
+  let input = TempFile::new(&format!("{name}-input"));
+  let output = TempFile::new(&format!("{name}-output"));
+  write_dump_activity(&input);
+  clipfst(&input, &output, args);
+  let reader = Reader::open(output.path()).unwrap();
+  reader.dump_activity_changes().collect()
+}
+
+#[test]
+fn keep_all() {
+  assert_eq!(clip("dump-activity-keep-all", &[]), CHANGES);
+}
+
+#[test]
+fn clip_while_active() {
+  // Times are relative to the start of the clip.
+  assert_eq!(
+    clip("dump-activity-while-active", &["-s", "10", "-e", "60"]),
+    [(10, false), (40, true)]
+  );
+}
+
+#[test]
+fn clip_while_inactive() {
+  // The inactive state before the start is kept.
+  assert_eq!(
+    clip("dump-activity-while-inactive", &["-s", "30", "-e", "80"]),
+    [(0, false), (20, true), (40, false)]
+  );
+  assert_eq!(
+    clip("dump-activity-at-change", &["-s", "70", "-e", "100"]),
+    [(0, false), (20, true)]
+  );
+}
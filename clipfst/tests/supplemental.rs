@@ -0,0 +1,105 @@
+mod common;
+
+use common::*;
+use fstapi::{Reader, Supplemental, Writer};
+use fstapi::{attr_type, misc_type, var_type};
+use fstapi::{scope_type, supplemental_data_type as sdt, supplemental_var_type as svt, var_dir};
+
+/// Returns supplemental types of the variables `top.data` and `top.count`.
+fn supplementals() -> [Supplemental; 2] {
+  [
+    Supplemental::new("unsigned", svt::VHDL_SIGNAL, sdt::VHDL_UNSIGNED),
+    Supplemental::new("", svt::VHDL_VARIABLE, sdt::VHDL_NATURAL),
+  ]
+}
+
+/// Writes a waveform with VHDL variables and a comment attribute.
+fn write_vhdl(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  let [data_s, count_s] = supplementals();
+  writer
+    .set_scope(scope_type::VHDL_ARCHITECTURE, "top", "rtl")
+    .unwrap();
+  writer
+    .set_attr_begin(attr_type::MISC, misc_type::COMMENT, "vhdl", 0)
+    .unwrap();
+  writer.set_attr_end();
+  let data = writer
+    .create_var2(var_type::VCD_REG, var_dir::INPUT, 4, "data", None, &data_s)
+    .unwrap();
+  let count = writer
+    .create_var2(
+      var_type::VCD_INTEGER,
+      var_dir::IMPLICIT,
+      32,
+      "count",
+      None,
+      &count_s,
+    )
+    .unwrap();
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  writer.set_upscope();
+  for time in 0..10 {
+    writer.emit_time_change(time * 10).unwrap();
+    writer
+      .emit_value_change(data, format!("{:04b}", time).as_bytes())
+      .unwrap();
+    writer
+      .emit_value_change(count, format!("{:032b}", time).as_bytes())
+      .unwrap();
+    writer
+      .emit_value_change(clk, (time % 2).to_string().as_bytes())
+      .unwrap();
+  }
+  writer.emit_time_change(100).unwrap();
+}
+
+/// Returns names and supplemental types of all variables in the given file,
+/// and the number of attributes.
+fn read_vars(file: &TempFile) -> (Vec<(String, Option<Supplemental>)>, usize) {
+  let mut reader = Reader::open(file.path()).unwrap();
+  let vars = reader
+    .vars()
+    .map(|v| {
+      let (name, var) = v.unwrap();
+      (name, var.supplemental().cloned())
+    })
+    .collect();
+  let attrs = reader
+    .hiers()
+    .filter(|h| matches!(h, fstapi::Hier::AttrBegin(_)))
+    .count();
+  (vars, attrs)
+}
+
+#[test]
+fn keep_supplemental() {
+  let input = TempFile::new("keep-supplemental-input");
+  let output = TempFile::new("keep-supplemental-output");
+  write_vhdl(&input);
+  let [data_s, count_s] = supplementals();
+  let expected = vec![
+    ("top.data".to_string(), Some(data_s.clone())),
+    ("top.count".to_string(), Some(count_s.clone())),
+    ("top.clk".to_string(), None),
+  ];
+
+  clipfst(&input, &output, &["-s", "20", "-e", "50"]);
+  // Two supplemental attributes and one comment.
+  assert_eq!(read_vars(&output), (expected.clone(), 3));
+
+  // Supplemental types are kept even if attributes are stripped.
+  clipfst(&input, &output, &["--strip-attrs"]);
+  assert_eq!(read_vars(&output), (expected, 2));
+
+  // Or only some signals are kept,
+  // then there are one supplemental attribute and one comment.
+  clipfst(&input, &output, &["--signals", "count|clk"]);
+  let expected = vec![
+    ("top.count".to_string(), Some(count_s)),
+    ("top.clk".to_string(), None),
+  ];
+  assert_eq!(read_vars(&output), (expected, 2));
+}
@@ -1,6 +1,9 @@
+use crate::enums::EnumMap;
+use crate::matcher::ChangeMatcher;
 use fstapi::{Handle, Reader, Result};
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
+use std::mem;
 
 /// Map for stroing handles and their corresponding variable names.
 pub type VarMap = HashMap<Handle, String>;
@@ -15,14 +18,19 @@ pub enum VarInfo {
 }
 
 impl VarInfo {
-  pub fn new(reader: &mut Reader, re: Option<Regex>) -> Result<Self> {
-    if let Some(re) = re {
+  /// Collects variables matching the given regex,
+  /// and referencing enumeration tables if `enums` is given.
+  pub fn new(reader: &mut Reader, re: Option<Regex>, enums: Option<&EnumMap>) -> Result<Self> {
+    if re.is_some() || enums.is_some() {
       // Collect matching variables.
       let mut vars = HashMap::new();
       for var in reader.vars() {
         let (name, var) = var?;
         let handle = var.handle();
-        if re.is_match(&name) && (!var.is_alias() || !vars.contains_key(&handle)) {
+        if re.as_ref().is_none_or(|re| re.is_match(&name))
+          && enums.is_none_or(|e| e.contains(handle))
+          && (!var.is_alias() || !vars.contains_key(&handle))
+        {
           vars.insert(handle, name);
         }
       }
@@ -44,6 +52,11 @@ pub trait VarChecker<T> {
   fn new(vars: T) -> Self;
   fn num_vars(&self) -> usize;
   fn check(&mut self, handle: Handle) -> Option<&str>;
+
+  /// Returns `true` if no more variables can pass the check.
+  fn is_done(&self) -> bool {
+    false
+  }
 }
 
 /// Checks if the given handle is in the variable map.
@@ -138,6 +151,7 @@ impl OnceVisitor for DenseVisitor {
 pub struct OnceChecker<V, C> {
   visitor: V,
   checker: C,
+  num_checked: usize,
 }
 
 impl<T, V, C> VarChecker<T> for OnceChecker<V, C>
@@ -150,12 +164,15 @@ where
     Self {
       visitor: V::new(checker.num_vars()),
       checker,
+      num_checked: 0,
     }
   }
 
   fn check(&mut self, handle: Handle) -> Option<&str> {
     if self.visitor.visit(handle) {
-      self.checker.check(handle)
+      let name = self.checker.check(handle);
+      self.num_checked += name.is_some() as usize;
+      name
     } else {
       None
     }
@@ -164,6 +181,10 @@ where
   fn num_vars(&self) -> usize {
     self.checker.num_vars()
   }
+
+  fn is_done(&self) -> bool {
+    self.num_checked == self.num_vars()
+  }
 }
 
 /// Checks if the given handle is in the variable map for only once.
@@ -171,3 +192,148 @@ pub type SparseOnceChecker = OnceChecker<SparseVisitor, SparseChecker>;
 
 /// Checks if the given handle is in the variable array for only once.
 pub type DenseOnceChecker = OnceChecker<DenseVisitor, DenseChecker>;
+
+/// Trait for detecting matching value changes by the given matcher,
+/// keeping states of variables if required.
+pub trait ChangeDetector {
+  /// Detects the given value change, returns time and value of the match.
+  fn detect<'a, M>(
+    &'a mut self,
+    matcher: &M,
+    time: u64,
+    handle: Handle,
+    value: &'a [u8],
+  ) -> Option<(u64, &'a [u8])>
+  where
+    M: ChangeMatcher;
+
+  /// Finishes detecting at the end of the waveform,
+  /// returns time, handle and value of remaining matches.
+  fn finish(self) -> Vec<(u64, Handle, Vec<u8>)>
+  where
+    Self: Sized,
+  {
+    Vec::new()
+  }
+}
+
+/// Detects value changes without keeping any state.
+pub struct StatelessDetector;
+
+impl ChangeDetector for StatelessDetector {
+  fn detect<'a, M>(
+    &'a mut self,
+    matcher: &M,
+    time: u64,
+    handle: Handle,
+    value: &'a [u8],
+  ) -> Option<(u64, &'a [u8])>
+  where
+    M: ChangeMatcher,
+  {
+    matcher
+      .is_change_match(handle, None, value)
+      .then_some((time, value))
+  }
+}
+
+/// Detects value changes by previous values of variables.
+#[derive(Default)]
+pub struct PrevValueDetector {
+  values: Vec<Option<Vec<u8>>>,
+  prev: Vec<u8>,
+}
+
+impl ChangeDetector for PrevValueDetector {
+  fn detect<'a, M>(
+    &'a mut self,
+    matcher: &M,
+    time: u64,
+    handle: Handle,
+    value: &'a [u8],
+  ) -> Option<(u64, &'a [u8])>
+  where
+    M: ChangeMatcher,
+  {
+    let index = u32::from(handle) as usize;
+    if self.values.len() <= index {
+      self.values.resize(index + 1, None);
+    }
+    let prev = match &mut self.values[index] {
+      Some(v) => {
+        mem::swap(v, &mut self.prev);
+        v.clear();
+        v.extend_from_slice(value);
+        Some(self.prev.as_slice())
+      }
+      None => {
+        self.values[index] = Some(value.into());
+        None
+      }
+    };
+    matcher
+      .is_change_match(handle, prev, value)
+      .then_some((time, value))
+  }
+}
+
+/// Detects matching values held for at least the given time.
+///
+/// Matches are detected when the held values change,
+/// or at the end of the waveform.
+pub struct HeldValueDetector {
+  min_time: u64,
+  end_time: u64,
+  /// Time since when the matching value is held, and the value.
+  held: HashMap<Handle, (u64, Vec<u8>)>,
+  last: Vec<u8>,
+}
+
+impl HeldValueDetector {
+  pub fn new(min_time: u64, end_time: u64) -> Self {
+    Self {
+      min_time,
+      end_time,
+      held: HashMap::new(),
+      last: Vec::new(),
+    }
+  }
+}
+
+impl ChangeDetector for HeldValueDetector {
+  fn detect<'a, M>(
+    &'a mut self,
+    matcher: &M,
+    time: u64,
+    handle: Handle,
+    value: &'a [u8],
+  ) -> Option<(u64, &'a [u8])>
+  where
+    M: ChangeMatcher,
+  {
+    // Writing the same value again does not end the period.
+    if self.held.get(&handle).is_some_and(|(_, v)| v == value) {
+      return None;
+    }
+    let ended = self.held.remove(&handle);
+    if matcher.is_change_match(handle, None, value) {
+      self.held.insert(handle, (time, value.into()));
+    }
+    let (since, v) = ended?;
+    (time - since >= self.min_time).then(|| {
+      self.last = v;
+      (since, self.last.as_slice())
+    })
+  }
+
+  fn finish(self) -> Vec<(u64, Handle, Vec<u8>)> {
+    let mut matches: Vec<_> = self
+      .held
+      .into_iter()
+      .filter(|(_, (since, _))| self.end_time.saturating_sub(*since) >= self.min_time)
+      .map(|(handle, (since, value))| (since, handle, value))
+      .collect();
+    matches.sort_by_key(|(since, handle, _)| (*since, *handle));
+    matches
+  }
+}
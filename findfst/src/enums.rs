@@ -0,0 +1,51 @@
+use fstapi::{EnumTable, Handle, Hierarchy, Reader, Result};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Map for storing handles and their corresponding enumeration tables.
+#[derive(Clone, Default)]
+pub struct EnumMap {
+  tables: HashMap<Handle, Rc<EnumTable>>,
+}
+
+impl EnumMap {
+  /// Collects enumeration tables of all variables that reference them.
+  pub fn new(reader: &mut Reader) -> Result<Self> {
+    let hier = Hierarchy::new(reader)?;
+    let mut shared = HashMap::new();
+    let mut tables = HashMap::new();
+    for (_, var) in hier.vars() {
+      let Some(enum_handle) = var.enum_table() else {
+        continue;
+      };
+      let Some(table) = hier.enum_table(enum_handle) else {
+        continue;
+      };
+      let table = shared
+        .entry(enum_handle)
+        .or_insert_with(|| Rc::new(table.clone()));
+      tables.insert(var.handle(), table.clone());
+    }
+    Ok(Self { tables })
+  }
+
+  /// Returns the enumeration table of the given handle.
+  pub fn get(&self, handle: Handle) -> Option<&EnumTable> {
+    self.tables.get(&handle).map(|t| t.as_ref())
+  }
+
+  /// Returns `true` if the given handle references an enumeration table.
+  pub fn contains(&self, handle: Handle) -> bool {
+    self.tables.contains_key(&handle)
+  }
+
+  /// Returns `true` if any enumeration table contains the given literal.
+  pub fn contains_literal(&self, literal: &str) -> bool {
+    self.tables.values().any(|t| t.value(literal).is_some())
+  }
+
+  /// Returns an iterator over all handles and their enumeration tables.
+  pub fn iter(&self) -> impl Iterator<Item = (Handle, &EnumTable)> {
+    self.tables.iter().map(|(h, t)| (*h, t.as_ref()))
+  }
+}
@@ -0,0 +1,525 @@
+use fstapi::{Handle, VarType, var_type};
+use std::fmt;
+
+/// Errors that can occurr when constructing [`Expr`].
+pub enum Error {
+  Syntax(String),
+  InvalidLiteral(String),
+  UnknownSignal(String),
+  AmbiguousSignal(String),
+  UnsupportedSignal(String),
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::Syntax(e) => write!(f, "Invalid expression: {e}!"),
+      Self::InvalidLiteral(l) => write!(f, "Invalid literal in expression: {l}!"),
+      Self::UnknownSignal(s) => write!(f, "Unknown signal in expression: {s}!"),
+      Self::AmbiguousSignal(s) => write!(f, "Ambiguous signal in expression: {s}!"),
+      Self::UnsupportedSignal(s) => write!(f, "Unsupported signal in expression: {s}!"),
+    }
+  }
+}
+
+/// Result type of expression parsing.
+type Result<T> = std::result::Result<T, Error>;
+
+/// Token of expression.
+#[derive(Clone, PartialEq)]
+enum Token {
+  Ident(String),
+  Num(u128, Option<u32>),
+  Op(&'static str),
+  LParen,
+  RParen,
+  LBracket,
+  RBracket,
+  Colon,
+}
+
+/// Operators, longer operators must come first.
+const OPS: [&str; 13] = [
+  "||", "&&", "==", "!=", "<=", ">=", "|", "^", "&", "<", ">", "!", "~",
+];
+
+/// Splits the given expression into tokens.
+fn tokenize(s: &str) -> Result<Vec<Token>> {
+  let mut tokens = Vec::new();
+  let mut rest = s;
+  while let Some(c) = rest.chars().next() {
+    if c.is_whitespace() {
+      rest = &rest[c.len_utf8()..];
+      continue;
+    }
+    let token = match c {
+      '(' => Token::LParen,
+      ')' => Token::RParen,
+      '[' => Token::LBracket,
+      ']' => Token::RBracket,
+      ':' => Token::Colon,
+      '`' => {
+        let Some(end) = rest[1..].find('`') else {
+          return Err(Error::Syntax(format!("unclosed quoted name `{rest}`")));
+        };
+        let name = &rest[1..end + 1];
+        rest = &rest[end + 2..];
+        tokens.push(Token::Ident(name.into()));
+        continue;
+      }
+      c if c.is_ascii_digit() || c == '\'' => {
+        let len = rest
+          .find(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '\'')
+          .unwrap_or(rest.len());
+        tokens.push(parse_literal(&rest[..len])?);
+        rest = &rest[len..];
+        continue;
+      }
+      c if c.is_ascii_alphabetic() || c == '_' || c == '$' => {
+        let len = rest
+          .find(|c: char| !c.is_ascii_alphanumeric() && !matches!(c, '_' | '$' | '.'))
+          .unwrap_or(rest.len());
+        tokens.push(Token::Ident(rest[..len].into()));
+        rest = &rest[len..];
+        continue;
+      }
+      _ => match OPS.iter().find(|op| rest.starts_with(*op)) {
+        Some(op) => {
+          tokens.push(Token::Op(op));
+          rest = &rest[op.len()..];
+          continue;
+        }
+        None => return Err(Error::Syntax(format!("unexpected character `{c}`"))),
+      },
+    };
+    tokens.push(token);
+    rest = &rest[1..];
+  }
+  Ok(tokens)
+}
+
+/// Parses the given literal, like `42`, `0x8000_0000`, `0b1010`, `0o17`
+/// or `8'hff`.
+fn parse_literal(s: &str) -> Result<Token> {
+  let invalid = || Error::InvalidLiteral(s.into());
+  let (width, radix, digits) = if let Some((width, rest)) = s.split_once('\'') {
+    let width = match width {
+      "" => None,
+      w => Some(
+        w.parse::<u32>()
+          .ok()
+          .filter(|w| (1..=128).contains(w))
+          .ok_or_else(invalid)?,
+      ),
+    };
+    let rest = rest.strip_prefix(['s', 'S']).unwrap_or(rest);
+    let mut chars = rest.chars();
+    let radix = match chars.next().map(|c| c.to_ascii_lowercase()) {
+      Some('b') => 2,
+      Some('o') => 8,
+      Some('d') => 10,
+      Some('h') => 16,
+      _ => return Err(invalid()),
+    };
+    (width, radix, chars.as_str())
+  } else if let Some(d) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+    (None, 16, d)
+  } else if let Some(d) = s.strip_prefix("0b").or_else(|| s.strip_prefix("0B")) {
+    (None, 2, d)
+  } else if let Some(d) = s.strip_prefix("0o").or_else(|| s.strip_prefix("0O")) {
+    (None, 8, d)
+  } else {
+    (None, 10, s)
+  };
+  let digits = digits.replace('_', "");
+  if digits.is_empty() {
+    return Err(invalid());
+  }
+  let value = u128::from_str_radix(&digits, radix).map_err(|_| invalid())?;
+  if width.is_some_and(|w| w < 128 && value >> w != 0) {
+    return Err(invalid());
+  }
+  Ok(Token::Num(value, width))
+}
+
+/// Binary operator.
+#[derive(Clone, Copy)]
+enum BinOp {
+  Or,
+  And,
+  BitOr,
+  BitXor,
+  BitAnd,
+  Eq,
+  Ne,
+  Lt,
+  Le,
+  Gt,
+  Ge,
+}
+
+impl BinOp {
+  /// Returns the binary operator and its precedence of the given operator.
+  fn new(op: &str) -> Option<(Self, u8)> {
+    Some(match op {
+      "||" => (Self::Or, 1),
+      "&&" => (Self::And, 2),
+      "|" => (Self::BitOr, 3),
+      "^" => (Self::BitXor, 4),
+      "&" => (Self::BitAnd, 5),
+      "==" => (Self::Eq, 6),
+      "!=" => (Self::Ne, 6),
+      "<" => (Self::Lt, 7),
+      "<=" => (Self::Le, 7),
+      ">" => (Self::Gt, 7),
+      ">=" => (Self::Ge, 7),
+      _ => return None,
+    })
+  }
+}
+
+/// Node of expression tree.
+enum Node {
+  /// Signal of the given index.
+  Signal(usize),
+  /// Bits `[msb:lsb]` of signal of the given index.
+  SignalSlice(usize, u32, u32),
+  /// Bits `[msb:lsb]` of the given expression.
+  Slice(Box<Node>, u32, u32),
+  Const(Bits),
+  Not(Box<Node>),
+  BitNot(Box<Node>),
+  Binary(BinOp, Box<Node>, Box<Node>),
+}
+
+/// Known value of an expression.
+#[derive(Clone, Copy)]
+struct Bits {
+  value: u128,
+  width: u32,
+}
+
+impl Bits {
+  fn new(value: u128, width: u32) -> Self {
+    Self {
+      value: value & mask(width),
+      width,
+    }
+  }
+
+  fn from_bool(b: bool) -> Self {
+    Self::new(b as u128, 1)
+  }
+
+  /// Parses the given bits, like `0101`, returns [`None`] if
+  /// there are unknown bits.
+  fn parse(bits: &[u8]) -> Option<Self> {
+    let value = bits.iter().try_fold(0u128, |v, b| match b {
+      b'0' => Some(v << 1),
+      b'1' => Some((v << 1) | 1),
+      _ => None,
+    })?;
+    Some(Self::new(value, bits.len() as u32))
+  }
+
+  fn is_true(self) -> bool {
+    self.value != 0
+  }
+}
+
+/// Returns the mask of the given width.
+fn mask(width: u32) -> u128 {
+  if width >= 128 {
+    u128::MAX
+  } else {
+    (1 << width) - 1
+  }
+}
+
+/// Parser of expression.
+struct Parser {
+  tokens: Vec<Token>,
+  pos: usize,
+  signals: Vec<Signal>,
+}
+
+impl Parser {
+  fn peek(&self) -> Option<&Token> {
+    self.tokens.get(self.pos)
+  }
+
+  fn next(&mut self) -> Result<Token> {
+    let token = self.peek().cloned();
+    self.pos += 1;
+    token.ok_or_else(|| Error::Syntax("unexpected end of expression".into()))
+  }
+
+  fn expect(&mut self, token: Token, what: &str) -> Result<()> {
+    if self.next()? == token {
+      Ok(())
+    } else {
+      Err(Error::Syntax(format!("expected {what}")))
+    }
+  }
+
+  /// Parses binary expressions with precedence at least `min_prec`.
+  fn parse_binary(&mut self, min_prec: u8) -> Result<Node> {
+    let mut lhs = self.parse_unary()?;
+    while let Some(Token::Op(op)) = self.peek()
+      && let Some((op, prec)) = BinOp::new(op)
+      && prec >= min_prec
+    {
+      self.pos += 1;
+      let rhs = self.parse_binary(prec + 1)?;
+      lhs = Node::Binary(op, Box::new(lhs), Box::new(rhs));
+    }
+    Ok(lhs)
+  }
+
+  fn parse_unary(&mut self) -> Result<Node> {
+    match self.peek() {
+      Some(Token::Op("!")) => {
+        self.pos += 1;
+        Ok(Node::Not(Box::new(self.parse_unary()?)))
+      }
+      Some(Token::Op("~")) => {
+        self.pos += 1;
+        Ok(Node::BitNot(Box::new(self.parse_unary()?)))
+      }
+      _ => self.parse_postfix(),
+    }
+  }
+
+  fn parse_postfix(&mut self) -> Result<Node> {
+    let mut node = self.parse_primary()?;
+    while self.peek() == Some(&Token::LBracket) {
+      self.pos += 1;
+      let msb = self.parse_index()?;
+      let lsb = if self.peek() == Some(&Token::Colon) {
+        self.pos += 1;
+        self.parse_index()?
+      } else {
+        msb
+      };
+      self.expect(Token::RBracket, "`]`")?;
+      if msb < lsb || msb - lsb >= 128 {
+        return Err(Error::Syntax(format!("invalid slice [{msb}:{lsb}]")));
+      }
+      node = match node {
+        Node::Signal(i) => {
+          let signal = &mut self.signals[i];
+          signal.max_index = signal.max_index.max(msb);
+          Node::SignalSlice(i, msb, lsb)
+        }
+        node => Node::Slice(Box::new(node), msb, lsb),
+      };
+    }
+    if let Node::Signal(i) = node {
+      self.signals[i].whole = true;
+    }
+    Ok(node)
+  }
+
+  fn parse_index(&mut self) -> Result<u32> {
+    match self.next()? {
+      Token::Num(n, _) => n
+        .try_into()
+        .map_err(|_| Error::Syntax(format!("index {n} is too large"))),
+      _ => Err(Error::Syntax("expected index".into())),
+    }
+  }
+
+  fn parse_primary(&mut self) -> Result<Node> {
+    match self.next()? {
+      Token::Ident(name) => {
+        let index = match self.signals.iter().position(|s| s.name == name) {
+          Some(i) => i,
+          None => {
+            self.signals.push(Signal::new(name));
+            self.signals.len() - 1
+          }
+        };
+        Ok(Node::Signal(index))
+      }
+      Token::Num(value, width) => {
+        let width = width.unwrap_or((128 - value.leading_zeros()).max(1));
+        Ok(Node::Const(Bits::new(value, width)))
+      }
+      Token::LParen => {
+        let node = self.parse_binary(1)?;
+        self.expect(Token::RParen, "`)`")?;
+        Ok(node)
+      }
+      _ => Err(Error::Syntax("expected signal, literal or `(`".into())),
+    }
+  }
+}
+
+/// Signal referenced by an expression.
+pub struct Signal {
+  /// Name in the expression.
+  name: String,
+  /// Full name, handle and length of the resolved variable.
+  var: Option<(String, Handle, u32)>,
+  /// `true` if the signal is referenced without slicing.
+  whole: bool,
+  /// The maximum bit index used in slicing.
+  max_index: u32,
+}
+
+impl Signal {
+  fn new(name: String) -> Self {
+    Self {
+      name,
+      var: None,
+      whole: false,
+      max_index: 0,
+    }
+  }
+
+  /// Returns the full name of the signal.
+  pub fn full_name(&self) -> &str {
+    self.var.as_ref().map_or(&self.name, |(n, _, _)| n)
+  }
+
+  /// Returns the handle of the signal.
+  pub fn handle(&self) -> Handle {
+    self.var.as_ref().unwrap().1
+  }
+}
+
+/// Boolean expression over multiple signals.
+pub struct Expr {
+  root: Node,
+  signals: Vec<Signal>,
+}
+
+impl Expr {
+  /// Parses the given expression.
+  pub fn new(s: &str) -> Result<Self> {
+    let mut parser = Parser {
+      tokens: tokenize(s)?,
+      pos: 0,
+      signals: Vec::new(),
+    };
+    let root = parser.parse_binary(1)?;
+    if parser.peek().is_some() {
+      return Err(Error::Syntax("unexpected token after expression".into()));
+    }
+    Ok(Self {
+      root,
+      signals: parser.signals,
+    })
+  }
+
+  /// Resolves referenced signals by the given variables,
+  /// in `(full name, handle, length, type)` form.
+  ///
+  /// A signal can be referenced by its full name, or by a unique suffix
+  /// of its full name, like `cpu.valid` for `top.cpu.valid`.
+  pub fn resolve<I>(&mut self, vars: I) -> Result<()>
+  where
+    I: IntoIterator<Item = (String, Handle, u32, VarType)>,
+  {
+    // Exact match and suffix matches of each signal.
+    let mut matches: Vec<(Option<usize>, Vec<usize>)> =
+      vec![(None, Vec::new()); self.signals.len()];
+    let vars: Vec<_> = vars.into_iter().collect();
+    for (i, (name, handle, _, _)) in vars.iter().enumerate() {
+      for (signal, (exact, suffixes)) in self.signals.iter().zip(&mut matches) {
+        if *name == signal.name {
+          exact.get_or_insert(i);
+        } else if let Some(prefix) = name.strip_suffix(&signal.name)
+          && prefix.ends_with('.')
+          && suffixes.iter().all(|&j| vars[j].1 != *handle)
+        {
+          suffixes.push(i);
+        }
+      }
+    }
+    for (signal, (exact, suffixes)) in self.signals.iter_mut().zip(matches) {
+      let index = match (exact, suffixes.as_slice()) {
+        (Some(i), _) => i,
+        (None, [i]) => *i,
+        (None, []) => return Err(Error::UnknownSignal(signal.name.clone())),
+        (None, _) => return Err(Error::AmbiguousSignal(signal.name.clone())),
+      };
+      let (name, handle, length, ty) = &vars[index];
+      let is_bits = !matches!(
+        *ty,
+        var_type::VCD_REAL
+          | var_type::VCD_REAL_PARAMETER
+          | var_type::VCD_REALTIME
+          | var_type::SV_SHORTREAL
+          | var_type::GEN_STRING
+      );
+      if !is_bits || (signal.whole && *length > 128) || signal.max_index >= *length {
+        return Err(Error::UnsupportedSignal(name.clone()));
+      }
+      signal.var = Some((name.clone(), *handle, *length));
+    }
+    Ok(())
+  }
+
+  /// Returns the referenced signals.
+  pub fn signals(&self) -> &[Signal] {
+    &self.signals
+  }
+
+  /// Evaluates the expression with the given values of referenced signals,
+  /// empty values are treated as unknown.
+  ///
+  /// Returns [`None`] if the result is unknown.
+  pub fn eval(&self, values: &[Vec<u8>]) -> Option<bool> {
+    eval(&self.root, values).map(Bits::is_true)
+  }
+}
+
+/// Evaluates the given node.
+fn eval(node: &Node, values: &[Vec<u8>]) -> Option<Bits> {
+  match node {
+    Node::Signal(i) => Bits::parse(&values[*i]),
+    Node::SignalSlice(i, msb, lsb) => {
+      let value = &values[*i];
+      let end = value.len().checked_sub(*lsb as usize)?;
+      let start = value.len().checked_sub(*msb as usize + 1)?;
+      Bits::parse(&value[start..end])
+    }
+    Node::Slice(node, msb, lsb) => {
+      let bits = eval(node, values)?;
+      let value = bits.value.checked_shr(*lsb).unwrap_or(0);
+      Some(Bits::new(value, msb - lsb + 1))
+    }
+    Node::Const(bits) => Some(*bits),
+    Node::Not(node) => eval(node, values).map(|b| Bits::from_bool(!b.is_true())),
+    Node::BitNot(node) => eval(node, values).map(|b| Bits::new(!b.value, b.width)),
+    Node::Binary(BinOp::And, lhs, rhs) => match (eval(lhs, values), eval(rhs, values)) {
+      (Some(l), _) if !l.is_true() => Some(Bits::from_bool(false)),
+      (_, Some(r)) if !r.is_true() => Some(Bits::from_bool(false)),
+      (Some(_), Some(_)) => Some(Bits::from_bool(true)),
+      _ => None,
+    },
+    Node::Binary(BinOp::Or, lhs, rhs) => match (eval(lhs, values), eval(rhs, values)) {
+      (Some(l), _) if l.is_true() => Some(Bits::from_bool(true)),
+      (_, Some(r)) if r.is_true() => Some(Bits::from_bool(true)),
+      (Some(_), Some(_)) => Some(Bits::from_bool(false)),
+      _ => None,
+    },
+    Node::Binary(op, lhs, rhs) => {
+      let (l, r) = (eval(lhs, values)?, eval(rhs, values)?);
+      let width = l.width.max(r.width);
+      Some(match op {
+        BinOp::BitOr => Bits::new(l.value | r.value, width),
+        BinOp::BitXor => Bits::new(l.value ^ r.value, width),
+        BinOp::BitAnd => Bits::new(l.value & r.value, width),
+        BinOp::Eq => Bits::from_bool(l.value == r.value),
+        BinOp::Ne => Bits::from_bool(l.value != r.value),
+        BinOp::Lt => Bits::from_bool(l.value < r.value),
+        BinOp::Le => Bits::from_bool(l.value <= r.value),
+        BinOp::Gt => Bits::from_bool(l.value > r.value),
+        BinOp::Ge => Bits::from_bool(l.value >= r.value),
+        BinOp::And | BinOp::Or => unreachable!(),
+      })
+    }
+  }
+}
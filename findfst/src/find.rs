@@ -1,16 +1,27 @@
+use crate::checker::{ChangeDetector, HeldValueDetector, PrevValueDetector, StatelessDetector};
 use crate::checker::{DenseChecker, DenseOnceChecker, SparseChecker, SparseOnceChecker};
 use crate::checker::{VarChecker, VarInfo};
-use crate::matcher::{ExactMatcher, RegexHexMatcher, RegexMatcher, ValueMatcher};
-use crate::printer::{FullPrinter, NamePrinter, Printer};
-use fstapi::{Handle, Reader, Result};
+use crate::enums::EnumMap;
+use crate::expr::Expr;
+use crate::matcher::{ChangeMatcher, EdgeMatcher, TransitionMatcher};
+use crate::matcher::{EnumMatcher, EnumRegexMatcher, ExactMatcher, UnknownMatcher};
+use crate::matcher::{RegexHexMatcher, RegexMatcher, ValueMatcher};
+use crate::printer::{CountPrinter, CsvPrinter, EnumPrinter, ExprPrinter, Format, FullPrinter};
+use crate::printer::{JsonPrinter, NamePrinter, Printer, TimeScale};
+use fstapi::{Handle, Logic, Reader, ReaderPool, Result};
 use regex::{Error as RegexError, bytes::Regex};
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
+use std::iter;
+use std::mem;
+use std::ops::ControlFlow;
 
 /// Errors that can occurr when constructing [`MatchInfo`].
 pub enum Error {
   Regex(RegexError),
   InvalidHex(String),
   InvalidBin(String),
+  InvalidMask(String),
 }
 
 impl fmt::Display for Error {
@@ -19,6 +30,7 @@ impl fmt::Display for Error {
       Self::Regex(e) => write!(f, "Invalid value regex: {e}"),
       Self::InvalidHex(v) => write!(f, "Invalid hexadecimal value: {v}!"),
       Self::InvalidBin(v) => write!(f, "Invalid binary value: {v}!"),
+      Self::InvalidMask(m) => write!(f, "Invalid mask: {m}!"),
     }
   }
 }
@@ -26,155 +38,612 @@ impl fmt::Display for Error {
 /// Information for matching values.
 pub enum MatchInfo {
   Regex(Regex, bool),
-  Exact(Box<[u8]>),
+  /// Four-state pattern, and `true` if bits beyond the pattern are masked.
+  Exact(Box<[u8]>, bool),
+  EnumRegex(Regex),
+  Enum(String),
+  /// Values with X bits, or Z bits, or both.
+  Unknown(bool, bool),
 }
 
 impl MatchInfo {
-  pub fn new(value: String, hex: bool, regex: bool) -> std::result::Result<Self, Error> {
-    if regex {
+  /// Creates match information from the given value.
+  ///
+  /// Binary and hexadecimal values are parsed as four-state patterns,
+  /// in which `?` matches any bit and `_` is ignored. Only bits set in the
+  /// given mask are compared if `mask` is given.
+  pub fn new(
+    value: String,
+    hex: bool,
+    regex: bool,
+    enum_literal: bool,
+    mask: Option<&str>,
+  ) -> std::result::Result<Self, Error> {
+    if enum_literal {
+      if regex {
+        Ok(Self::EnumRegex(Regex::new(&value).map_err(Error::Regex)?))
+      } else {
+        Ok(Self::Enum(value))
+      }
+    } else if regex {
       let re = Regex::new(&value).map_err(Error::Regex)?;
       Ok(Self::Regex(re, hex))
-    } else if hex {
-      let mut s = Vec::new();
-      for c in value.chars() {
-        let digit = match c.to_digit(16) {
-          Some(d) => d,
-          _ => return Err(Error::InvalidHex(value)),
-        };
-        s.push(if (digit & 8) != 0 { b'1' } else { b'0' });
-        s.push(if (digit & 4) != 0 { b'1' } else { b'0' });
-        s.push(if (digit & 2) != 0 { b'1' } else { b'0' });
-        s.push(if (digit & 1) != 0 { b'1' } else { b'0' });
-      }
-      Ok(Self::Exact(s.into()))
-    } else if value.contains(|c: char| !c.is_digit(2)) {
-      Err(Error::InvalidBin(value))
     } else {
-      Ok(Self::Exact(value.into_bytes().into()))
+      let Some(mut pattern) = parse_pattern(&value, hex, false) else {
+        return Err(if hex {
+          Error::InvalidHex(value)
+        } else {
+          Error::InvalidBin(value)
+        });
+      };
+      let Some(mask) = mask else {
+        return Ok(Self::Exact(pattern.into(), false));
+      };
+      let Some(mut mask) = parse_pattern(mask, hex, true) else {
+        return Err(Error::InvalidMask(mask.into()));
+      };
+      // Align the pattern and the mask from the least significant bit.
+      let len = pattern.len().max(mask.len());
+      pattern.splice(0..0, iter::repeat_n(b'0', len - pattern.len()));
+      mask.splice(0..0, iter::repeat_n(b'0', len - mask.len()));
+      for (p, m) in pattern.iter_mut().zip(mask) {
+        if m == b'0' {
+          *p = b'?';
+        }
+      }
+      Ok(Self::Exact(pattern.into(), true))
+    }
+  }
+
+  /// Converts the match information into a value matcher.
+  fn into_matcher(self, enums: &EnumMap) -> Box<dyn ValueMatcher> {
+    match self {
+      Self::Regex(re, false) => Box::new(RegexMatcher::new(re)),
+      Self::Regex(re, true) => Box::new(RegexHexMatcher::new(re)),
+      Self::Exact(e, false) => Box::new(ExactMatcher::new(e)),
+      Self::Exact(e, true) => Box::new(ExactMatcher::masked(e)),
+      Self::EnumRegex(re) => Box::new(EnumRegexMatcher::new(re, enums.clone())),
+      Self::Enum(l) => Box::new(EnumMatcher::new(&l, enums)),
+      Self::Unknown(x, z) => Box::new(UnknownMatcher::new(x, z)),
+    }
+  }
+}
+
+/// Parses the given binary or hexadecimal pattern into bits,
+/// returns [`None`] if the pattern is invalid.
+///
+/// Hexadecimal patterns can have an optional `0x` prefix. Only `0` and `1`
+/// bits are allowed in masks, otherwise bits can also be `?` or any
+/// four-state logic value, like `x` and `z`.
+fn parse_pattern(value: &str, hex: bool, mask: bool) -> Option<Vec<u8>> {
+  let (digits, radix, width) = match hex {
+    true => (value.strip_prefix("0x").unwrap_or(value), 16, 4),
+    false => (value, 2, 1),
+  };
+  let mut bits = Vec::new();
+  for c in digits.bytes().filter(|c| *c != b'_') {
+    let c = c.to_ascii_lowercase();
+    match (c as char).to_digit(radix) {
+      Some(d) => bits.extend((0..width).rev().map(|i| b'0' + ((d >> i) & 1) as u8)),
+      None if mask => return None,
+      None if c == b'?' || Logic::from_char(c).is_some() => {
+        bits.extend(iter::repeat_n(c, width as usize))
+      }
+      None => return None,
+    }
+  }
+  (!bits.is_empty()).then_some(bits)
+}
+
+/// Source of value changes of the FST waveform.
+pub enum Source<'a> {
+  /// Decodes value change blocks sequentially by the reader.
+  Reader(&'a mut Reader),
+  /// Decodes value change blocks in parallel by the reader pool,
+  /// with masks set for the given signals, or all signals if [`None`].
+  Pool(&'a ReaderPool, Option<Vec<Handle>>),
+}
+
+/// Value changes of the FST waveform, optionally limited to a time range,
+/// or sampled at edges of a clock.
+pub struct Blocks<'a> {
+  source: Source<'a>,
+  time_range: Option<(u64, u64)>,
+  clock: Option<(Handle, bool)>,
+}
+
+impl<'a> Blocks<'a> {
+  pub fn new(source: Source<'a>) -> Self {
+    Self {
+      source,
+      time_range: None,
+      clock: None,
+    }
+  }
+
+  /// Limits value changes to the given time range, value changes after
+  /// the range are ignored.
+  pub fn time_range(mut self, start: u64, end: u64) -> Self {
+    self.time_range = Some((start, end));
+    self
+  }
+
+  /// Samples values at rising edges of the given clock,
+  /// or falling edges if `negedge` is `true`.
+  ///
+  /// At each edge, values before the edge are reported as value changes
+  /// at the time of the edge, if they differ from values sampled at the
+  /// previous edge.
+  pub fn clock(mut self, clock: Handle, negedge: bool) -> Self {
+    self.clock = Some((clock, negedge));
+    self
+  }
+
+  /// Returns the start time of the time range, or `0` if there is no limit.
+  fn start_time(&self) -> u64 {
+    self.time_range.map_or(0, |(start, _)| start)
+  }
+
+  /// Runs the given callback on each value change in time order,
+  /// stops iterating when the callback returns [`ControlFlow::Break`].
+  ///
+  /// Value changes before the start time may be delivered as well, for
+  /// keeping states of signals, matches before the start time should not
+  /// be reported by the callback.
+  fn try_for_each<F>(self, mut callback: F) -> Result<()>
+  where
+    F: FnMut(u64, Handle, &[u8], bool) -> ControlFlow<()>,
+  {
+    let Self {
+      source,
+      time_range,
+      clock,
+    } = self;
+    let setup = |reader: &mut Reader| {
+      if let Some((start, end)) = time_range {
+        reader.set_time_range_limit(start, end);
+      }
+      if let Some((clock, _)) = clock {
+        reader.set_mask(clock);
+      }
+    };
+    let end = time_range.map_or(u64::MAX, |(_, end)| end);
+    let mut filtered = |time, handle, value: &[u8], var_len| {
+      if time > end {
+        ControlFlow::Break(())
+      } else {
+        callback(time, handle, value, var_len)
+      }
+    };
+    let mut sampler = clock.map(|(clock, negedge)| Sampler::new(clock, negedge));
+    let sampled = |time, handle, value: &[u8], var_len| match &mut sampler {
+      Some(sampler) => sampler.update(time, handle, value, &mut filtered),
+      None => filtered(time, handle, value, var_len),
+    };
+    let ret = match source {
+      Source::Reader(reader) => {
+        setup(reader);
+        reader.try_for_each_block(sampled)
+      }
+      Source::Pool(pool, handles) => pool.try_for_each_block(
+        |reader| {
+          set_masks(reader, handles.as_deref());
+          setup(reader);
+        },
+        sampled,
+      ),
+    }?;
+    if ret.is_continue()
+      && let Some(sampler) = &mut sampler
+    {
+      let _ = sampler.flush(&mut filtered);
+    }
+    Ok(())
+  }
+}
+
+/// Samples values at edges of a clock.
+struct Sampler {
+  clock: Handle,
+  negedge: bool,
+  /// The least significant bit of the clock.
+  clock_bit: Option<u8>,
+  /// The current time step, and `true` if there is an edge at the step.
+  step: Option<(u64, bool)>,
+  /// Value changes at the current time step.
+  pending: Vec<(Handle, Vec<u8>)>,
+  /// Values before the current time step.
+  values: HashMap<Handle, Vec<u8>>,
+  /// Values sampled at the last edge.
+  sampled: HashMap<Handle, Vec<u8>>,
+  /// Signals changed since the last edge.
+  changed: BTreeSet<Handle>,
+}
+
+impl Sampler {
+  fn new(clock: Handle, negedge: bool) -> Self {
+    Self {
+      clock,
+      negedge,
+      clock_bit: None,
+      step: None,
+      pending: Vec::new(),
+      values: HashMap::new(),
+      sampled: HashMap::new(),
+      changed: BTreeSet::new(),
+    }
+  }
+
+  /// Updates the sampler by the given value change,
+  /// runs the callback on sampled value changes.
+  fn update<F>(&mut self, time: u64, handle: Handle, value: &[u8], callback: F) -> ControlFlow<()>
+  where
+    F: FnMut(u64, Handle, &[u8], bool) -> ControlFlow<()>,
+  {
+    if self.step.is_some_and(|(t, _)| t != time) {
+      self.flush(callback)?;
+    }
+    let (_, edge) = self.step.get_or_insert((time, false));
+    if handle == self.clock {
+      let bit = value.last().copied();
+      let (from, to) = if self.negedge {
+        (b'1', b'0')
+      } else {
+        (b'0', b'1')
+      };
+      *edge |= self.clock_bit == Some(from) && bit == Some(to);
+      self.clock_bit = bit;
+    }
+    self.pending.push((handle, value.into()));
+    ControlFlow::Continue(())
+  }
+
+  /// Finishes the current time step, runs the callback on value changes
+  /// sampled at the step if there is an edge.
+  fn flush<F>(&mut self, mut callback: F) -> ControlFlow<()>
+  where
+    F: FnMut(u64, Handle, &[u8], bool) -> ControlFlow<()>,
+  {
+    let Some((time, edge)) = self.step.take() else {
+      return ControlFlow::Continue(());
+    };
+    if edge {
+      for handle in mem::take(&mut self.changed) {
+        let value = &self.values[&handle];
+        if self.sampled.get(&handle) != Some(value) {
+          self.sampled.insert(handle, value.clone());
+          callback(time, handle, value, false)?;
+        }
+      }
+    }
+    for (handle, value) in self.pending.drain(..) {
+      self.values.insert(handle, value);
+      self.changed.insert(handle);
+    }
+    ControlFlow::Continue(())
+  }
+}
+
+/// Sets masks for the given signals, or all signals if [`None`].
+pub fn set_masks(reader: &mut Reader, handles: Option<&[Handle]>) {
+  match handles {
+    Some(handles) => {
+      reader.clear_mask_all();
+      for handle in handles {
+        reader.set_mask(*handle);
+      }
     }
+    None => reader.set_mask_all(),
   }
 }
 
-/// Finds the matching value in the given FST waveform.
+/// Information for finding value changes.
+pub enum FindInfo {
+  /// Changes to the matching value.
+  Value(MatchInfo),
+  /// Rising edges if `true`, otherwise falling edges.
+  Edge(bool),
+  /// Changes from the first matching value to the second matching value.
+  Transition(Option<MatchInfo>, Option<MatchInfo>),
+  /// Matching values held for at least the given time,
+  /// with the end time of the waveform.
+  Held(MatchInfo, u64, u64),
+}
+
+impl FindInfo {
+  /// Returns an iterator over all match information.
+  pub fn match_infos(&self) -> impl Iterator<Item = &MatchInfo> {
+    let (first, second) = match self {
+      Self::Value(m) | Self::Held(m, _, _) => (Some(m), None),
+      Self::Edge(_) => (None, None),
+      Self::Transition(from, to) => (from.as_ref(), to.as_ref()),
+    };
+    first.into_iter().chain(second)
+  }
+}
+
+/// Finds the matching value changes in the given FST waveform.
+///
+/// Enumeration literals are matched instead of values if `enums` is given.
 pub fn find_value(
-  reader: &mut Reader,
-  value_match: MatchInfo,
+  blocks: Blocks,
+  find_info: FindInfo,
   vars: VarInfo,
+  enums: Option<EnumMap>,
   all_matches: bool,
-  names_only: bool,
+  printer: PrinterInfo,
 ) -> Result<()> {
-  match value_match {
-    MatchInfo::Regex(re, false) => {
-      find_value_m(reader, RegexMatcher::new(re), vars, all_matches, names_only)
+  let enums = enums.unwrap_or_default();
+  match find_info {
+    FindInfo::Value(m) => find_value_d(
+      blocks,
+      m,
+      StatelessDetector,
+      vars,
+      enums,
+      all_matches,
+      printer,
+    ),
+    FindInfo::Held(m, min_time, end_time) => {
+      let detector = HeldValueDetector::new(min_time, end_time);
+      find_value_d(blocks, m, detector, vars, enums, all_matches, printer)
     }
+    FindInfo::Edge(rising) => find_value_m(
+      blocks,
+      EdgeMatcher::new(rising),
+      PrevValueDetector::default(),
+      vars,
+      all_matches,
+      printer,
+    ),
+    FindInfo::Transition(from, to) => find_value_m(
+      blocks,
+      TransitionMatcher::new(
+        from.map(|m| m.into_matcher(&enums)),
+        to.map(|m| m.into_matcher(&enums)),
+      ),
+      PrevValueDetector::default(),
+      vars,
+      all_matches,
+      printer,
+    ),
+  }
+}
+
+/// Stage #2, with change detector applied, determines value matcher.
+fn find_value_d<D>(
+  blocks: Blocks,
+  value_match: MatchInfo,
+  detector: D,
+  vars: VarInfo,
+  enums: EnumMap,
+  all_matches: bool,
+  printer: PrinterInfo,
+) -> Result<()>
+where
+  D: ChangeDetector,
+{
+  match value_match {
+    MatchInfo::Regex(re, false) => find_value_m(
+      blocks,
+      RegexMatcher::new(re),
+      detector,
+      vars,
+      all_matches,
+      printer,
+    ),
     MatchInfo::Regex(re, true) => find_value_m(
-      reader,
+      blocks,
       RegexHexMatcher::new(re),
+      detector,
       vars,
       all_matches,
-      names_only,
+      printer,
+    ),
+    MatchInfo::Exact(e, masked) => find_value_m(
+      blocks,
+      match masked {
+        true => ExactMatcher::masked(e),
+        false => ExactMatcher::new(e),
+      },
+      detector,
+      vars,
+      all_matches,
+      printer,
+    ),
+    MatchInfo::EnumRegex(re) => find_value_m(
+      blocks,
+      EnumRegexMatcher::new(re, enums),
+      detector,
+      vars,
+      all_matches,
+      printer,
+    ),
+    MatchInfo::Enum(l) => find_value_m(
+      blocks,
+      EnumMatcher::new(&l, &enums),
+      detector,
+      vars,
+      all_matches,
+      printer,
+    ),
+    MatchInfo::Unknown(x, z) => find_value_m(
+      blocks,
+      UnknownMatcher::new(x, z),
+      detector,
+      vars,
+      all_matches,
+      printer,
     ),
-    MatchInfo::Exact(e) => {
-      find_value_m(reader, ExactMatcher::new(e), vars, all_matches, names_only)
-    }
   }
 }
 
-/// Stage #2, with value matcher applied, determines variable checker.
-fn find_value_m<M>(
-  reader: &mut Reader,
+/// Information for printing results.
+pub enum PrinterInfo {
+  Name,
+  Full,
+  /// Prints enumeration literals instead of values.
+  Enum(EnumMap),
+  Json(TimeScale),
+  Csv(TimeScale),
+  /// Prints only the number of results of each variable in the given format.
+  Count(Format),
+}
+
+/// Stage #3, with value matcher applied, determines variable checker.
+fn find_value_m<M, D>(
+  blocks: Blocks,
   value_matcher: M,
+  detector: D,
   vars: VarInfo,
   all_matches: bool,
-  names_only: bool,
+  printer: PrinterInfo,
 ) -> Result<()>
 where
-  M: ValueMatcher,
+  M: ChangeMatcher,
+  D: ChangeDetector,
 {
+  let (m, d) = (value_matcher, detector);
   match (vars, all_matches) {
-    (VarInfo::Map(vars), true) => {
-      find_value_mc(reader, value_matcher, SparseChecker::new(vars), names_only)
-    }
-    (VarInfo::Map(vars), false) => find_value_mc(
-      reader,
-      value_matcher,
-      SparseOnceChecker::new(vars),
-      names_only,
-    ),
-    (VarInfo::Array(vars), true) => {
-      find_value_mc(reader, value_matcher, DenseChecker::new(vars), names_only)
-    }
-    (VarInfo::Array(vars), false) => find_value_mc(
-      reader,
-      value_matcher,
-      DenseOnceChecker::new(vars),
-      names_only,
-    ),
+    (VarInfo::Map(vars), true) => find_value_mc(blocks, m, d, SparseChecker::new(vars), printer),
+    (VarInfo::Map(vars), false) => {
+      find_value_mc(blocks, m, d, SparseOnceChecker::new(vars), printer)
+    }
+    (VarInfo::Array(vars), true) => find_value_mc(blocks, m, d, DenseChecker::new(vars), printer),
+    (VarInfo::Array(vars), false) => {
+      find_value_mc(blocks, m, d, DenseOnceChecker::new(vars), printer)
+    }
   }
 }
 
-/// Stage #3, with value matcher and variable checker applied, determines printer.
-fn find_value_mc<M, T, C>(
-  reader: &mut Reader,
+/// Stage #4, with value matcher and variable checker applied, determines printer.
+fn find_value_mc<M, D, T, C>(
+  blocks: Blocks,
   value_matcher: M,
+  detector: D,
   var_checker: C,
-  names_only: bool,
+  printer: PrinterInfo,
 ) -> Result<()>
 where
-  M: ValueMatcher,
+  M: ChangeMatcher,
+  D: ChangeDetector,
   C: VarChecker<T>,
 {
-  if names_only {
-    find_value_mcp(reader, value_matcher, var_checker, NamePrinter)
-  } else {
-    find_value_mcp(reader, value_matcher, var_checker, FullPrinter)
+  let (m, d, c) = (value_matcher, detector, var_checker);
+  match printer {
+    PrinterInfo::Name => find_value_mcp(blocks, m, d, c, NamePrinter),
+    PrinterInfo::Full => find_value_mcp(blocks, m, d, c, FullPrinter),
+    PrinterInfo::Enum(enums) => find_value_mcp(blocks, m, d, c, EnumPrinter::new(enums)),
+    PrinterInfo::Json(scale) => find_value_mcp(blocks, m, d, c, JsonPrinter::new(scale)),
+    PrinterInfo::Csv(scale) => find_value_mcp(blocks, m, d, c, CsvPrinter::new(scale)),
+    PrinterInfo::Count(format) => find_value_mcp(blocks, m, d, c, CountPrinter::new(format)),
   }
 }
 
-/// Final stage, all generics are applied, creates callbacks and
-/// finds for matching values.
-fn find_value_mcp<M, T, C, P>(
-  reader: &mut Reader,
+/// Final stage, all generics are applied, runs change detector,
+/// variable checker and printer on value changes.
+fn find_value_mcp<M, D, T, C, P>(
+  blocks: Blocks,
   value_matcher: M,
+  mut detector: D,
   mut var_checker: C,
-  printer: P,
+  mut printer: P,
 ) -> Result<()>
 where
-  M: ValueMatcher,
+  M: ChangeMatcher,
+  D: ChangeDetector,
   C: VarChecker<T>,
   P: Printer,
 {
-  reader.for_each_block(|time, handle, value, _| {
-    find_value_callback(
-      &value_matcher,
-      &mut var_checker,
-      &printer,
-      time,
-      handle,
-      value,
-    )
-  })
-}
-
-/// Callback of FST block iterator.
-/// Runs value matcher, variable checker and printer.
-fn find_value_callback<M, T, C, P>(
-  value_matcher: &M,
-  var_checker: &mut C,
-  printer: &P,
-  time: u64,
-  handle: Handle,
-  value: &[u8],
-) where
-  M: ValueMatcher,
-  C: VarChecker<T>,
-  P: Printer,
-{
-  // Check if value matches.
-  if value_matcher.is_match(value) {
-    // Check the current variable and print.
+  let start = blocks.start_time();
+  printer.begin();
+  blocks.try_for_each(|time, handle, value, _| {
+    // Check if value change matches, matches before the start time
+    // only update the detector.
+    if let Some((time, value)) = detector.detect(&value_matcher, time, handle, value)
+      && time >= start
+    {
+      // Check the current variable and print.
+      if let Some(name) = var_checker.check(handle) {
+        printer.print(time, handle, name, value);
+      }
+      // Stop if all variables have been checked.
+      if var_checker.is_done() {
+        return ControlFlow::Break(());
+      }
+    }
+    ControlFlow::Continue(())
+  })?;
+  // Check matches detected at the end of the waveform.
+  for (time, handle, value) in detector.finish() {
+    if var_checker.is_done() {
+      break;
+    }
+    if time < start {
+      continue;
+    }
     if let Some(name) = var_checker.check(handle) {
-      printer.print(time, name, value);
+      printer.print(time, handle, name, &value);
+    }
+  }
+  printer.end();
+  Ok(())
+}
+
+/// Finds times at which the given expression is true in the given FST waveform.
+///
+/// The expression is evaluated at each time step where any of its referenced
+/// signals changes, after all value changes at that time step are applied.
+/// Only the number of matching times is printed if `count` is `true`.
+pub fn find_expr(
+  blocks: Blocks,
+  expr: Expr,
+  all_matches: bool,
+  format: Format,
+  scale: TimeScale,
+  count: bool,
+) -> Result<()> {
+  let mut printer = ExprPrinter::new(format, scale, expr.signals(), count);
+  // Indices of signals of each handle, a handle may be referenced by
+  // different names.
+  let mut indices: HashMap<Handle, Vec<usize>> = HashMap::new();
+  for (i, signal) in expr.signals().iter().enumerate() {
+    indices.entry(signal.handle()).or_default().push(i);
+  }
+  let mut values = vec![Vec::new(); expr.signals().len()];
+  let start = blocks.start_time();
+  printer.begin();
+  // Time step with value changes that has not been evaluated yet.
+  let mut pending = None;
+  let mut done = false;
+  let mut check = |time, values: &[Vec<u8>]| {
+    if time >= start && expr.eval(values) == Some(true) {
+      printer.print(time, values);
+      done = !all_matches;
+    }
+    done
+  };
+  blocks.try_for_each(|time, handle, value, _| {
+    if let Some(t) = pending
+      && t != time
+    {
+      pending = None;
+      if check(t, &values) {
+        return ControlFlow::Break(());
+      }
+    }
+    if let Some(indices) = indices.get(&handle) {
+      for &i in indices {
+        values[i].clear();
+        values[i].extend_from_slice(value);
+      }
+      pending = Some(time);
     }
+    ControlFlow::Continue(())
+  })?;
+  if let Some(t) = pending {
+    check(t, &values);
   }
+  printer.end();
+  Ok(())
 }
@@ -1,12 +1,17 @@
 mod checker;
+mod enums;
+mod expr;
 mod find;
 mod matcher;
 mod printer;
 
 use checker::VarInfo;
 use clap::Parser;
-use find::{MatchInfo, find_value};
-use fstapi::{Reader, Result};
+use enums::EnumMap;
+use expr::Expr;
+use find::{Blocks, FindInfo, MatchInfo, PrinterInfo, Source, find_expr, find_value, set_masks};
+use fstapi::{Handle, Reader, ReaderPool, Result};
+use printer::{Format, TimeScale};
 use std::process;
 
 #[derive(Parser)]
@@ -28,12 +33,91 @@ struct Cli {
   file: String,
 
   /// The value to find, in binary format by default.
-  value: String,
+  ///
+  /// Binary and hexadecimal values are four-state patterns, like `1???_0x1z`
+  /// or `8?`, in which `?` matches any bit and `_` is ignored.
+  #[arg(required_unless_present_any = [
+    "expr", "rising", "falling", "changes_from", "to", "has_x", "has_z",
+  ])]
+  value: Option<String>,
 
-  /// Use lowercase hexadecimal format value instead of binary format.
+  /// Compare only bits set in the given mask, in the same format as the value.
+  #[arg(
+    long,
+    conflicts_with_all = ["regex", "enum_literal", "rising", "falling", "has_x", "has_z"]
+  )]
+  mask: Option<String>,
+
+  /// Find values with X bits instead of the value.
+  #[arg(
+    long,
+    conflicts_with_all = [
+      "value", "hex", "regex", "enum_literal", "rising", "falling", "changes_from", "to",
+    ]
+  )]
+  has_x: bool,
+
+  /// Find values with Z bits instead of the value.
+  #[arg(
+    long,
+    conflicts_with_all = [
+      "value", "hex", "regex", "enum_literal", "rising", "falling", "changes_from", "to",
+    ]
+  )]
+  has_z: bool,
+
+  /// Find rising edges (`0` to `1`) of the least significant bit instead of
+  /// the value.
+  #[arg(long, conflicts_with_all = ["value", "falling", "hex", "regex", "held_for"])]
+  rising: bool,
+
+  /// Find falling edges (`1` to `0`) of the least significant bit instead of
+  /// the value.
+  #[arg(long, conflicts_with_all = ["value", "hex", "regex", "held_for"])]
+  falling: bool,
+
+  /// Find changes from the given value instead of the value,
+  /// to the value given by `--to`, or to any other value by default.
+  #[arg(long, value_name = "VALUE", conflicts_with_all = ["value", "rising", "falling", "held_for"])]
+  changes_from: Option<String>,
+
+  /// Find changes to the given value instead of the value,
+  /// from the value given by `--changes-from`, or from any other value by default.
+  #[arg(long, value_name = "VALUE", conflicts_with_all = ["value", "rising", "falling", "held_for"])]
+  to: Option<String>,
+
+  /// Find the value only if it is held for at least the given time.
+  #[arg(long, value_name = "TIME")]
+  held_for: Option<u64>,
+
+  /// Find times at which the given boolean expression is true, like
+  /// `valid && ready && addr[31:28] == 'h8`, instead of the value.
+  ///
+  /// The expression is evaluated at each time step where any referenced signal
+  /// changes. Signals are referenced by full names or unique suffixes of full
+  /// names, names with special characters can be quoted by backticks.
+  /// Supported operators are `!`, `~`, `[msb:lsb]`, `<`, `<=`, `>`, `>=`, `==`,
+  /// `!=`, `&`, `^`, `|`, `&&` and `||`, literals can be decimal, `0x`, `0o`,
+  /// `0b` or Verilog-style like `8'hff`.
+  #[arg(
+    long,
+    conflicts_with_all = [
+      "value", "hex", "enum_literal", "regex", "signals", "names_only",
+      "rising", "falling", "changes_from", "to", "held_for", "mask", "has_x", "has_z",
+    ]
+  )]
+  expr: Option<String>,
+
+  /// Use hexadecimal format value instead of binary format,
+  /// with an optional `0x` prefix.
   #[arg(short = 'x', long)]
   hex: bool,
 
+  /// Find enumeration literal instead of value, and print literals of
+  /// enumeration signals instead of values.
+  #[arg(short, long = "enum", conflicts_with = "hex")]
+  enum_literal: bool,
+
   /// Find all matching values in a signal, not just the first match.
   #[arg(short, long)]
   all_matches: bool,
@@ -47,8 +131,42 @@ struct Cli {
   signals: Option<String>,
 
   /// Print only signal names to stdout.
-  #[arg(short, long)]
+  #[arg(short, long, conflicts_with_all = ["format", "count"])]
   names_only: bool,
+
+  /// Output format, JSON Lines and CSV contain time, scaled time with unit,
+  /// full name, handle, raw value and hexadecimal value of each match.
+  #[arg(short, long, value_enum, default_value_t = Format::Text)]
+  format: Format,
+
+  /// Print only the number of matches of each signal,
+  /// implies `--all-matches`.
+  #[arg(short, long)]
+  count: bool,
+
+  /// Find only value changes at or after the given time.
+  #[arg(long, value_name = "TIME")]
+  start: Option<u64>,
+
+  /// Find only value changes at or before the given time.
+  #[arg(long, value_name = "TIME")]
+  end: Option<u64>,
+
+  /// Sample signals at rising edges of the given clock signal, like
+  /// `top.clk`, and find only value changes between sampled values.
+  ///
+  /// Values are sampled right before each edge, so glitches between
+  /// edges are ignored.
+  #[arg(long, value_name = "SIGNAL")]
+  clock: Option<String>,
+
+  /// Sample signals at falling edges of the clock instead.
+  #[arg(long, requires = "clock")]
+  negedge: bool,
+
+  /// Number of threads for decoding the waveform in parallel.
+  #[arg(short, long, default_value_t = 1)]
+  jobs: usize,
 }
 
 macro_rules! try_or_exit {
@@ -69,35 +187,185 @@ fn main() {
 
 fn try_main() -> Result<()> {
   // Parse command line arguments.
-  let cli = Cli::parse();
+  let mut cli = Cli::parse();
+
+  if cli.expr.is_some() {
+    return try_expr(cli);
+  }
 
   // Validate command line arguments.
-  let match_info = try_or_exit!(MatchInfo::new(cli.value, cli.hex, cli.regex), e, "{e}");
+  let match_info = |value| {
+    try_or_exit!(
+      MatchInfo::new(
+        value,
+        cli.hex,
+        cli.regex,
+        cli.enum_literal,
+        cli.mask.as_deref()
+      ),
+      e,
+      "{e}"
+    )
+  };
+  let find_info = if cli.rising || cli.falling {
+    FindInfo::Edge(cli.rising)
+  } else if cli.changes_from.is_some() || cli.to.is_some() {
+    FindInfo::Transition(
+      cli.changes_from.take().map(match_info),
+      cli.to.take().map(match_info),
+    )
+  } else if cli.has_x || cli.has_z {
+    FindInfo::Value(MatchInfo::Unknown(cli.has_x, cli.has_z))
+  } else {
+    FindInfo::Value(match_info(cli.value.take().unwrap()))
+  };
   let signal_re = cli
     .signals
+    .take()
     .map(|s| try_or_exit!(regex::Regex::new(&s), e, "Invalid signal regex: {e}"));
 
   // Open the given FST file.
-  let mut reader = Reader::open(cli.file)?;
+  let pool = ReaderPool::open(&cli.file)?.threads(cli.jobs);
+  let mut reader = pool.get()?;
+  let clock = cli.clock_handle(&mut reader)?;
+  let time_range = cli.time_range(&reader);
+  let scale = TimeScale::new(reader.timescale());
+  let find_info = match (find_info, cli.held_for) {
+    (FindInfo::Value(m), Some(time)) => {
+      let end_time = time_range.map_or(reader.end_time(), |(_, end)| end);
+      FindInfo::Held(m, time, end_time)
+    }
+    (find_info, _) => find_info,
+  };
 
-  // Get variable information and update signal mask.
-  let vars = VarInfo::new(&mut reader, signal_re)?;
-  match &vars {
-    VarInfo::Map(m) => {
-      reader.clear_mask_all();
-      for handle in m.keys() {
-        reader.set_mask(*handle);
+  // Get enumeration tables.
+  let enums = if cli.enum_literal {
+    let enums = EnumMap::new(&mut reader)?;
+    for m in find_info.match_infos() {
+      if let MatchInfo::Enum(literal) = m
+        && !enums.contains_literal(literal)
+      {
+        eprintln!("Unknown enumeration literal: {literal}!");
+        process::exit(1)
       }
     }
-    VarInfo::Array(_) => reader.set_mask_all(),
-  }
+    Some(enums)
+  } else {
+    None
+  };
+
+  // Get variable information and update signal mask.
+  let vars = VarInfo::new(&mut reader, signal_re, enums.as_ref())?;
+  let handles = match &vars {
+    VarInfo::Map(m) => Some(m.keys().copied().collect::<Vec<_>>()),
+    VarInfo::Array(_) => None,
+  };
+  let source = if cli.jobs > 1 {
+    drop(reader);
+    Source::Pool(&pool, handles)
+  } else {
+    set_masks(&mut reader, handles.as_deref());
+    Source::Reader(&mut reader)
+  };
+  let blocks = cli.blocks(source, time_range, clock);
 
   // Iterate over blocks and find value.
+  let printer = match (&enums, cli.format) {
+    _ if cli.count => PrinterInfo::Count(cli.format),
+    _ if cli.names_only => PrinterInfo::Name,
+    (_, Format::Json) => PrinterInfo::Json(scale),
+    (_, Format::Csv) => PrinterInfo::Csv(scale),
+    (Some(enums), Format::Text) => PrinterInfo::Enum(enums.clone()),
+    (None, Format::Text) => PrinterInfo::Full,
+  };
   find_value(
-    &mut reader,
-    match_info,
+    blocks,
+    find_info,
     vars,
-    cli.all_matches,
-    cli.names_only,
+    enums,
+    cli.all_matches || cli.count,
+    printer,
+  )
+}
+
+fn try_expr(cli: Cli) -> Result<()> {
+  // Validate the expression.
+  let mut expr = try_or_exit!(Expr::new(cli.expr.as_ref().unwrap()), e, "{e}");
+
+  // Open the given FST file.
+  let pool = ReaderPool::open(&cli.file)?.threads(cli.jobs);
+  let mut reader = pool.get()?;
+  let clock = cli.clock_handle(&mut reader)?;
+  let time_range = cli.time_range(&reader);
+  let scale = TimeScale::new(reader.timescale());
+
+  // Resolve referenced signals and update signal mask.
+  let vars = reader
+    .vars()
+    .map(|v| v.map(|(name, v)| (name, v.handle(), v.length(), v.ty())))
+    .collect::<Result<Vec<_>>>()?;
+  try_or_exit!(expr.resolve(vars), e, "{e}");
+  let handles: Vec<_> = expr.signals().iter().map(|s| s.handle()).collect();
+  let source = if cli.jobs > 1 {
+    drop(reader);
+    Source::Pool(&pool, Some(handles))
+  } else {
+    set_masks(&mut reader, Some(&handles));
+    Source::Reader(&mut reader)
+  };
+  let blocks = cli.blocks(source, time_range, clock);
+
+  // Iterate over blocks and evaluate the expression.
+  find_expr(
+    blocks,
+    expr,
+    cli.all_matches || cli.count,
+    cli.format,
+    scale,
+    cli.count,
   )
 }
+
+impl Cli {
+  /// Returns the handle of the clock signal, exits if the signal is not found.
+  fn clock_handle(&self, reader: &mut Reader) -> Result<Option<Handle>> {
+    let Some(clock) = &self.clock else {
+      return Ok(None);
+    };
+    for var in reader.vars() {
+      let (name, var) = var?;
+      if name == *clock {
+        return Ok(Some(var.handle()));
+      }
+    }
+    eprintln!("Unknown clock signal: {clock}!");
+    process::exit(1)
+  }
+
+  /// Returns the time range given by `--start` and `--end`.
+  fn time_range(&self, reader: &Reader) -> Option<(u64, u64)> {
+    (self.start.is_some() || self.end.is_some()).then(|| {
+      let start = self.start.unwrap_or(reader.start_time());
+      let end = self.end.unwrap_or(reader.end_time());
+      (start, end)
+    })
+  }
+
+  /// Returns value changes from the given source,
+  /// with the time range and the clock applied.
+  fn blocks<'a>(
+    &self,
+    source: Source<'a>,
+    time_range: Option<(u64, u64)>,
+    clock: Option<Handle>,
+  ) -> Blocks<'a> {
+    let mut blocks = Blocks::new(source);
+    if let Some((start, end)) = time_range {
+      blocks = blocks.time_range(start, end);
+    }
+    if let Some(clock) = clock {
+      blocks = blocks.clock(clock, self.negedge);
+    }
+    blocks
+  }
+}
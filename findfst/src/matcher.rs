@@ -1,10 +1,13 @@
+use crate::enums::EnumMap;
+use fstapi::{Handle, LogicVec};
 use regex::bytes::Regex;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::iter;
 
 /// Trait for matching values in different configurations.
 pub trait ValueMatcher {
-  fn is_match(&self, value: &[u8]) -> bool;
+  fn is_match(&self, handle: Handle, value: &[u8]) -> bool;
 }
 
 /// Use regex to match binary values.
@@ -19,7 +22,7 @@ impl RegexMatcher {
 }
 
 impl ValueMatcher for RegexMatcher {
-  fn is_match(&self, value: &[u8]) -> bool {
+  fn is_match(&self, _: Handle, value: &[u8]) -> bool {
     self.re.is_match(value)
   }
 }
@@ -36,51 +39,191 @@ impl RegexHexMatcher {
 }
 
 impl ValueMatcher for RegexHexMatcher {
-  fn is_match(&self, value: &[u8]) -> bool {
-    let hex = value
-      .rchunks(4)
-      .rev()
-      .map(|ds| {
-        let digit = ds
-          .iter()
-          .fold(0, |ans, d| (ans << 1) | ((*d != b'0') as u32));
-        char::from_digit(digit, 16).unwrap() as u8
-      })
-      .collect::<Vec<_>>();
-    self.re.is_match(&hex)
-  }
-}
-
-/// Use byte array to match any value.
+  fn is_match(&self, _: Handle, value: &[u8]) -> bool {
+    LogicVec::new(value).is_ok_and(|v| self.re.is_match(v.to_hex_string().as_bytes()))
+  }
+}
+
+/// Use byte array to match any value, in four-state comparison.
+///
+/// Values are compared from the least significant bit, `?` in the byte array
+/// matches any bit. Bits beyond the byte array are compared with `0`, or
+/// ignored if the matcher is masked.
 pub struct ExactMatcher {
   exact: Box<[u8]>,
+  fill: u8,
 }
 
 impl ExactMatcher {
   pub fn new(exact: Box<[u8]>) -> Self {
-    Self { exact }
+    Self { exact, fill: b'0' }
   }
+
+  pub fn masked(exact: Box<[u8]>) -> Self {
+    Self { exact, fill: b'?' }
+  }
+}
+
+/// Returns `true` if the given bit matches the given pattern bit.
+fn bit_match(bit: &u8, pattern: &u8) -> bool {
+  bit == pattern || *pattern == b'?'
 }
 
 impl ValueMatcher for ExactMatcher {
-  fn is_match(&self, value: &[u8]) -> bool {
+  fn is_match(&self, _: Handle, value: &[u8]) -> bool {
     match value.len().cmp(&self.exact.len()) {
       Ordering::Greater => value
         .iter()
         .rev()
-        .zip(self.exact.iter().rev().chain(iter::repeat(&b'0')))
-        .all(|(l, r)| l == r),
+        .zip(self.exact.iter().rev().chain(iter::repeat(&self.fill)))
+        .all(|(l, r)| bit_match(l, r)),
       Ordering::Less => value
         .iter()
         .rev()
         .chain(iter::repeat(&b'0'))
         .zip(self.exact.iter().rev())
-        .all(|(l, r)| l == r),
+        .all(|(l, r)| bit_match(l, r)),
       Ordering::Equal => value
         .iter()
         .rev()
         .zip(self.exact.iter().rev())
-        .all(|(l, r)| l == r),
+        .all(|(l, r)| bit_match(l, r)),
+    }
+  }
+}
+
+/// Matches values with X bits, or Z bits, or both.
+pub struct UnknownMatcher {
+  x: bool,
+  z: bool,
+}
+
+impl UnknownMatcher {
+  pub fn new(x: bool, z: bool) -> Self {
+    Self { x, z }
+  }
+}
+
+impl ValueMatcher for UnknownMatcher {
+  fn is_match(&self, _: Handle, value: &[u8]) -> bool {
+    value
+      .iter()
+      .any(|b| (self.x && b.eq_ignore_ascii_case(&b'x')) || (self.z && b.eq_ignore_ascii_case(&b'z')))
+      // Values of non-bit-vector variables may also contain `x` or `z`.
+      && LogicVec::new(value).is_ok()
+  }
+}
+
+/// Use enumeration literal to match values of enumeration variables.
+pub struct EnumMatcher {
+  values: HashMap<Handle, ExactMatcher>,
+}
+
+impl EnumMatcher {
+  pub fn new(literal: &str, enums: &EnumMap) -> Self {
+    Self {
+      values: enums
+        .iter()
+        .filter_map(|(h, t)| {
+          t.value(literal)
+            .map(|v| (h, ExactMatcher::new(v.as_bytes().into())))
+        })
+        .collect(),
+    }
+  }
+}
+
+impl ValueMatcher for EnumMatcher {
+  fn is_match(&self, handle: Handle, value: &[u8]) -> bool {
+    self
+      .values
+      .get(&handle)
+      .is_some_and(|m| m.is_match(handle, value))
+  }
+}
+
+/// Use regex to match enumeration literals.
+pub struct EnumRegexMatcher {
+  re: Regex,
+  enums: EnumMap,
+}
+
+impl EnumRegexMatcher {
+  pub fn new(re: Regex, enums: EnumMap) -> Self {
+    Self { re, enums }
+  }
+}
+
+impl ValueMatcher for EnumRegexMatcher {
+  fn is_match(&self, handle: Handle, value: &[u8]) -> bool {
+    self
+      .enums
+      .get(handle)
+      .and_then(|t| t.literal(value))
+      .is_some_and(|l| self.re.is_match(l.as_bytes()))
+  }
+}
+
+/// Trait for matching value changes, with previous values of signals.
+pub trait ChangeMatcher {
+  /// Returns `true` if the change from `prev` to `value` matches,
+  /// `prev` is [`None`] for initial values.
+  fn is_change_match(&self, handle: Handle, prev: Option<&[u8]>, value: &[u8]) -> bool;
+}
+
+impl<M: ValueMatcher> ChangeMatcher for M {
+  fn is_change_match(&self, handle: Handle, _: Option<&[u8]>, value: &[u8]) -> bool {
+    self.is_match(handle, value)
+  }
+}
+
+/// Matches rising or falling edges of the least significant bit.
+pub struct EdgeMatcher {
+  rising: bool,
+}
+
+impl EdgeMatcher {
+  pub fn new(rising: bool) -> Self {
+    Self { rising }
+  }
+}
+
+impl ChangeMatcher for EdgeMatcher {
+  fn is_change_match(&self, _: Handle, prev: Option<&[u8]>, value: &[u8]) -> bool {
+    let (from, to) = if self.rising {
+      (b'0', b'1')
+    } else {
+      (b'1', b'0')
+    };
+    prev.and_then(|p| p.last()) == Some(&from) && value.last() == Some(&to)
+  }
+}
+
+/// Matches changes from a value to another value.
+///
+/// Changes leaving the first value are matched if there is no second value,
+/// and changes entering the second value are matched if there is no first value.
+pub struct TransitionMatcher {
+  from: Option<Box<dyn ValueMatcher>>,
+  to: Option<Box<dyn ValueMatcher>>,
+}
+
+impl TransitionMatcher {
+  pub fn new(from: Option<Box<dyn ValueMatcher>>, to: Option<Box<dyn ValueMatcher>>) -> Self {
+    Self { from, to }
+  }
+}
+
+impl ChangeMatcher for TransitionMatcher {
+  fn is_change_match(&self, handle: Handle, prev: Option<&[u8]>, value: &[u8]) -> bool {
+    let Some(prev) = prev else {
+      return false;
+    };
+    match (&self.from, &self.to) {
+      (Some(from), Some(to)) => from.is_match(handle, prev) && to.is_match(handle, value),
+      (Some(from), None) => from.is_match(handle, prev) && !from.is_match(handle, value),
+      (None, Some(to)) => !to.is_match(handle, prev) && to.is_match(handle, value),
+      (None, None) => false,
     }
   }
 }
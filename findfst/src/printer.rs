@@ -1,13 +1,26 @@
+use crate::enums::EnumMap;
+use crate::expr::Signal;
+use clap::ValueEnum;
+use fstapi::{Handle, LogicVec};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+
 /// Trait for printing the result.
 pub trait Printer {
-  fn print(&self, time: u64, name: &str, value: &[u8]);
+  /// Prints the header before all results.
+  fn begin(&mut self) {}
+
+  fn print(&mut self, time: u64, handle: Handle, name: &str, value: &[u8]);
+
+  /// Prints the footer after all results.
+  fn end(&mut self) {}
 }
 
 /// Prints all informations.
 pub struct FullPrinter;
 
 impl Printer for FullPrinter {
-  fn print(&self, time: u64, name: &str, value: &[u8]) {
+  fn print(&mut self, time: u64, _: Handle, name: &str, value: &[u8]) {
     print!("#{time} {name} ");
     for v in value {
       print!("{}", *v as char);
@@ -20,7 +33,301 @@ impl Printer for FullPrinter {
 pub struct NamePrinter;
 
 impl Printer for NamePrinter {
-  fn print(&self, _: u64, name: &str, _: &[u8]) {
+  fn print(&mut self, _: u64, _: Handle, name: &str, _: &[u8]) {
     println!("{name}")
   }
 }
+
+/// Prints all informations, with enumeration literals instead of values.
+pub struct EnumPrinter {
+  enums: EnumMap,
+}
+
+impl EnumPrinter {
+  pub fn new(enums: EnumMap) -> Self {
+    Self { enums }
+  }
+}
+
+impl Printer for EnumPrinter {
+  fn print(&mut self, time: u64, handle: Handle, name: &str, value: &[u8]) {
+    match self.enums.get(handle).and_then(|t| t.literal(value)) {
+      Some(literal) => println!("#{time} {name} {literal}"),
+      None => FullPrinter.print(time, handle, name, value),
+    }
+  }
+}
+
+/// Output format of results.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+  /// Plain text, like `#time name value`.
+  Text,
+  /// JSON Lines, one JSON object per line.
+  Json,
+  /// CSV with a header row.
+  Csv,
+}
+
+/// Timescale for converting times to scaled times with units.
+#[derive(Clone, Copy)]
+pub struct TimeScale {
+  factor: u64,
+  unit: &'static str,
+}
+
+impl TimeScale {
+  /// Creates a timescale from the given exponent of the waveform,
+  /// like `-9` for `1ns`.
+  pub fn new(exponent: i32) -> Self {
+    const UNITS: [&str; 8] = ["zs", "as", "fs", "ps", "ns", "us", "ms", "s"];
+    match exponent + 21 {
+      e @ 0..=23 => {
+        let index = (e as usize / 3).min(UNITS.len() - 1);
+        Self {
+          factor: 10u64.pow(e as u32 - index as u32 * 3),
+          unit: UNITS[index],
+        }
+      }
+      _ => Self {
+        factor: 1,
+        unit: "",
+      },
+    }
+  }
+
+  /// Returns the scaled time of the given time.
+  fn scale(self, time: u64) -> u128 {
+    time as u128 * self.factor as u128
+  }
+}
+
+/// Returns the value as a string.
+fn raw_string(value: &[u8]) -> String {
+  String::from_utf8_lossy(value).into()
+}
+
+/// Returns the value in hexadecimal,
+/// or [`None`] if the value is not a bit vector.
+fn hex_string(value: &[u8]) -> Option<String> {
+  LogicVec::new(value).ok().map(|v| v.to_hex_string())
+}
+
+/// Prints the given fields as a CSV record.
+fn print_csv_record<I, S>(fields: I)
+where
+  I: IntoIterator<Item = S>,
+  S: AsRef<str>,
+{
+  let fields: Vec<_> = fields
+    .into_iter()
+    .map(|f| {
+      let f = f.as_ref();
+      if f.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", f.replace('"', "\"\""))
+      } else {
+        f.into()
+      }
+    })
+    .collect();
+  println!("{}", fields.join(","));
+}
+
+/// Prints results as JSON Lines.
+pub struct JsonPrinter {
+  scale: TimeScale,
+}
+
+impl JsonPrinter {
+  pub fn new(scale: TimeScale) -> Self {
+    Self { scale }
+  }
+}
+
+impl Printer for JsonPrinter {
+  fn print(&mut self, time: u64, handle: Handle, name: &str, value: &[u8]) {
+    let record = json!({
+      "time": time,
+      "scaled_time": self.scale.scale(time),
+      "time_unit": self.scale.unit,
+      "name": name,
+      "handle": u32::from(handle),
+      "value": raw_string(value),
+      "hex": hex_string(value),
+    });
+    println!("{record}");
+  }
+}
+
+/// Prints results as CSV.
+pub struct CsvPrinter {
+  scale: TimeScale,
+}
+
+impl CsvPrinter {
+  pub fn new(scale: TimeScale) -> Self {
+    Self { scale }
+  }
+}
+
+impl Printer for CsvPrinter {
+  fn begin(&mut self) {
+    print_csv_record([
+      "time",
+      "scaled_time",
+      "time_unit",
+      "name",
+      "handle",
+      "value",
+      "hex",
+    ]);
+  }
+
+  fn print(&mut self, time: u64, handle: Handle, name: &str, value: &[u8]) {
+    print_csv_record([
+      time.to_string(),
+      self.scale.scale(time).to_string(),
+      self.scale.unit.into(),
+      name.into(),
+      u32::from(handle).to_string(),
+      raw_string(value),
+      hex_string(value).unwrap_or_default(),
+    ]);
+  }
+}
+
+/// Counts results of each variable, and prints the counts at the end.
+pub struct CountPrinter {
+  format: Format,
+  /// Handles, names and counts, in order of the first result.
+  counts: Vec<(Handle, String, u64)>,
+  indices: HashMap<Handle, usize>,
+}
+
+impl CountPrinter {
+  pub fn new(format: Format) -> Self {
+    Self {
+      format,
+      counts: Vec::new(),
+      indices: HashMap::new(),
+    }
+  }
+}
+
+impl Printer for CountPrinter {
+  fn print(&mut self, _: u64, handle: Handle, name: &str, _: &[u8]) {
+    let index = *self.indices.entry(handle).or_insert_with(|| {
+      self.counts.push((handle, name.into(), 0));
+      self.counts.len() - 1
+    });
+    self.counts[index].2 += 1;
+  }
+
+  fn end(&mut self) {
+    if self.format == Format::Csv {
+      print_csv_record(["name", "handle", "count"]);
+    }
+    for (handle, name, count) in &self.counts {
+      match self.format {
+        Format::Text => println!("{name} {count}"),
+        Format::Json => println!(
+          "{}",
+          json!({"name": name, "handle": u32::from(*handle), "count": count})
+        ),
+        Format::Csv => print_csv_record([
+          name.clone(),
+          u32::from(*handle).to_string(),
+          count.to_string(),
+        ]),
+      }
+    }
+  }
+}
+
+/// Prints times at which an expression is true, and values of all signals
+/// referenced by the expression.
+pub struct ExprPrinter<'a> {
+  format: Format,
+  scale: TimeScale,
+  signals: &'a [Signal],
+  /// Number of results, if only the number is printed.
+  count: Option<u64>,
+}
+
+impl<'a> ExprPrinter<'a> {
+  pub fn new(format: Format, scale: TimeScale, signals: &'a [Signal], count: bool) -> Self {
+    Self {
+      format,
+      scale,
+      signals,
+      count: count.then_some(0),
+    }
+  }
+
+  /// Prints the header before all results.
+  pub fn begin(&self) {
+    if self.format == Format::Csv {
+      if self.count.is_some() {
+        println!("count");
+      } else {
+        let names = self.signals.iter().map(|s| s.full_name());
+        print_csv_record(
+          ["time", "scaled_time", "time_unit"]
+            .into_iter()
+            .chain(names),
+        );
+      }
+    }
+  }
+
+  /// Prints the given time and values of signals.
+  pub fn print(&mut self, time: u64, values: &[Vec<u8>]) {
+    if let Some(count) = &mut self.count {
+      *count += 1;
+      return;
+    }
+    match self.format {
+      Format::Text => {
+        print!("#{time}");
+        for (signal, value) in self.signals.iter().zip(values) {
+          print!(" {}={}", signal.full_name(), raw_string(value));
+        }
+        println!();
+      }
+      Format::Json => {
+        let values: Map<_, _> = self
+          .signals
+          .iter()
+          .zip(values)
+          .map(|(s, v)| (s.full_name().into(), Value::String(raw_string(v))))
+          .collect();
+        let record = json!({
+          "time": time,
+          "scaled_time": self.scale.scale(time),
+          "time_unit": self.scale.unit,
+          "values": values,
+        });
+        println!("{record}");
+      }
+      Format::Csv => print_csv_record(
+        [
+          time.to_string(),
+          self.scale.scale(time).to_string(),
+          self.scale.unit.into(),
+        ]
+        .into_iter()
+        .chain(values.iter().map(|v| raw_string(v))),
+      ),
+    }
+  }
+
+  /// Prints the footer after all results.
+  pub fn end(&self) {
+    if let Some(count) = self.count {
+      match self.format {
+        Format::Text | Format::Csv => println!("{count}"),
+        Format::Json => println!("{}", json!({ "count": count })),
+      }
+    }
+  }
+}
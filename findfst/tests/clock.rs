@@ -0,0 +1,93 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Writer, scope_type, var_dir, var_type};
+
+/// Writes a waveform with a clock `top.clk` toggling every 10 time units,
+/// and a signal `top.sig` with a glitch between rising edges 30 and 50.
+fn write_clock(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  let sig = writer
+    .create_var(var_type::VCD_WIRE, var_dir::OUTPUT, 1, "sig", None)
+    .unwrap();
+  writer.set_upscope();
+  let sig_changes: [(u64, &[u8]); 5] = [(0, b"0"), (33, b"1"), (36, b"0"), (42, b"1"), (62, b"0")];
+  let mut sig_changes = sig_changes.iter().peekable();
+  for time in 0..=80 {
+    let clk_change = time % 10 == 0;
+    let sig_change = sig_changes.next_if(|(t, _)| *t == time);
+    if !clk_change && sig_change.is_none() {
+      continue;
+    }
+    writer.emit_time_change(time).unwrap();
+    if clk_change {
+      let value = if time % 20 == 0 { b"0" } else { b"1" };
+      writer.emit_value_change(clk, value).unwrap();
+    }
+    if let Some((_, value)) = sig_change {
+      writer.emit_value_change(sig, value).unwrap();
+    }
+  }
+  writer.emit_time_change(90).unwrap();
+}
+
+#[test]
+fn sample_on_rising_edges() {
+  let file = TempFile::new("clock-sample-on-rising-edges");
+  write_clock(&file);
+  assert_eq!(
+    findfst(&file, &["1", "-a", "-s", "sig"]),
+    ["#33 top.sig 1", "#42 top.sig 1"]
+  );
+  // The glitch is ignored, the first sampled value is a change.
+  let args = ["-a", "-s", "sig", "--clock", "top.clk"];
+  assert_eq!(
+    findfst(&file, &[&["1"], &args[..]].concat()),
+    ["#50 top.sig 1"]
+  );
+  assert_eq!(
+    findfst(&file, &[&["0"], &args[..]].concat()),
+    ["#10 top.sig 0", "#70 top.sig 0"]
+  );
+  assert_eq!(
+    findfst(&file, &[&["--rising"], &args[..]].concat()),
+    ["#50 top.sig 1"]
+  );
+  assert_eq!(
+    findfst(&file, &["--expr", "sig", "-a", "--clock", "top.clk"]),
+    ["#50 top.sig=1"]
+  );
+}
+
+#[test]
+fn sample_on_falling_edges() {
+  let file = TempFile::new("clock-sample-on-falling-edges");
+  write_clock(&file);
+  let args = ["-a", "-s", "sig", "--clock", "top.clk", "--negedge"];
+  assert_eq!(
+    findfst(&file, &[&["1"], &args[..]].concat()),
+    ["#60 top.sig 1"]
+  );
+  assert_eq!(
+    findfst(&file, &[&["--falling"], &args[..]].concat()),
+    ["#80 top.sig 0"]
+  );
+}
+
+#[test]
+fn unknown_clock() {
+  let file = TempFile::new("clock-unknown-clock");
+  write_clock(&file);
+  let output = run_findfst(&file, &["1", "--clock", "nosuch"]);
+  assert!(!output.status.success());
+  let stderr = String::from_utf8(output.stderr).unwrap();
+  assert_eq!(stderr.trim(), "Unknown clock signal: nosuch!");
+  // `--negedge` requires `--clock`.
+  assert!(!run_findfst(&file, &["1", "--negedge"]).status.success());
+}
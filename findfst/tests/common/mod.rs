@@ -0,0 +1,54 @@
+//! Helpers shared by integration tests.
+
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::{env, fs, process};
+
+/// A temporary waveform file, which is removed when dropped.
+pub struct TempFile(PathBuf);
+
+impl TempFile {
+  /// Creates a new temporary file path with the given name.
+  pub fn new(name: &str) -> Self {
+    let file_name = format!("findfst-test-{}-{name}.fst", process::id());
+    Self(env::temp_dir().join(file_name))
+  }
+
+  /// Returns the path of the file.
+  pub fn path(&self) -> &Path {
+    &self.0
+  }
+}
+
+impl Drop for TempFile {
+  fn drop(&mut self) {
+    let _ = fs::remove_file(&self.0);
+    // The hierarchy is written to a separate file if it is not compressed.
+    let mut hier = self.0.clone().into_os_string();
+    hier.push(".hier");
+    let _ = fs::remove_file(hier);
+  }
+}
+
+/// Runs `findfst` on the given file with the given arguments.
+pub fn run_findfst(file: &TempFile, args: &[&str]) -> Output {
+  Command::new(env!("CARGO_BIN_EXE_findfst"))
+    .arg(file.path())
+    .args(args)
+    .output()
+    .unwrap()
+}
+
+/// Runs `findfst` on the given file with the given arguments,
+/// returns lines of the standard output.
+///
+/// Panics if `findfst` fails.
+pub fn findfst(file: &TempFile, args: &[&str]) -> Vec<String> {
+  let output = run_findfst(file, args);
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(output.status.success(), "findfst failed: {stderr}");
+  let stdout = String::from_utf8(output.stdout).unwrap();
+  stdout.lines().map(String::from).collect()
+}
@@ -0,0 +1,130 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Writer, scope_type, var_dir, var_type};
+
+/// Writes a waveform with an interrupt line `top.irq`
+/// and a 3-bit state `top.state`.
+fn write_irq(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let irq = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "irq", None)
+    .unwrap();
+  let state = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 3, "state", None)
+    .unwrap();
+  writer.set_upscope();
+  let changes: [(u64, _, &[u8]); 13] = [
+    (0, irq, b"0"),
+    (0, state, b"000"),
+    (5, state, b"011"),
+    (10, irq, b"1"),
+    (15, state, b"111"),
+    (20, irq, b"0"),
+    (25, state, b"011"),
+    (30, irq, b"x"),
+    (35, state, b"001"),
+    (40, irq, b"1"),
+    (45, state, b"011"),
+    (50, irq, b"0"),
+    (60, state, b"111"),
+  ];
+  for (time, handle, value) in changes {
+    writer.emit_time_change(time).unwrap();
+    writer.emit_value_change(handle, value).unwrap();
+  }
+  writer.emit_time_change(80).unwrap();
+}
+
+#[test]
+fn rising_edges() {
+  let file = TempFile::new("edges-rising-edges");
+  write_irq(&file);
+  // Only the least significant bit is checked, `x` to `1` is not an edge.
+  assert_eq!(
+    findfst(&file, &["--rising", "-a"]),
+    ["#5 top.state 011", "#10 top.irq 1"]
+  );
+  assert_eq!(
+    findfst(&file, &["--rising", "-s", "irq"]),
+    ["#10 top.irq 1"]
+  );
+}
+
+#[test]
+fn falling_edges() {
+  let file = TempFile::new("edges-falling-edges");
+  write_irq(&file);
+  assert_eq!(
+    findfst(&file, &["--falling", "-a"]),
+    ["#20 top.irq 0", "#50 top.irq 0"]
+  );
+  assert_eq!(findfst(&file, &["--falling"]), ["#20 top.irq 0"]);
+}
+
+#[test]
+fn transitions() {
+  let file = TempFile::new("edges-transitions");
+  write_irq(&file);
+  assert_eq!(
+    findfst(&file, &["--changes-from", "011", "--to", "111", "-a"]),
+    ["#15 top.state 111", "#60 top.state 111"]
+  );
+  // Changes to any other value.
+  assert_eq!(
+    findfst(&file, &["--changes-from", "011", "-a"]),
+    [
+      "#15 top.state 111",
+      "#35 top.state 001",
+      "#60 top.state 111"
+    ]
+  );
+  // Changes from any other value, the initial value is not a change.
+  assert_eq!(
+    findfst(&file, &["--to", "0", "-a", "-s", "irq"]),
+    ["#20 top.irq 0", "#50 top.irq 0"]
+  );
+  assert_eq!(
+    findfst(&file, &["--to", "3", "-x", "-a"]),
+    ["#5 top.state 011", "#25 top.state 011", "#45 top.state 011"]
+  );
+}
+
+#[test]
+fn held_values() {
+  let file = TempFile::new("edges-held-values");
+  write_irq(&file);
+  assert_eq!(
+    findfst(&file, &["011", "--held-for", "10", "-a"]),
+    ["#5 top.state 011", "#25 top.state 011", "#45 top.state 011"]
+  );
+  assert_eq!(
+    findfst(&file, &["011", "--held-for", "11", "-a"]),
+    ["#45 top.state 011"]
+  );
+  // The last value is held until the end of the waveform.
+  assert_eq!(
+    findfst(&file, &["111", "--held-for", "20"]),
+    ["#60 top.state 111"]
+  );
+  assert!(findfst(&file, &["111", "--held-for", "21"]).is_empty());
+}
+
+#[test]
+fn conflicting_options() {
+  let file = TempFile::new("edges-conflicting-options");
+  write_irq(&file);
+  for args in [
+    &["--rising", "--falling"][..],
+    &["--rising", "-x"],
+    &["--rising", "--held-for", "5"],
+    &["--changes-from", "0", "--held-for", "5"],
+    &["1", "--to", "0"],
+  ] {
+    let output = run_findfst(&file, args);
+    assert!(!output.status.success(), "{args:?}");
+  }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Writer, scope_type, var_dir, var_type};
+
+/// Writes a waveform with an enumeration signal `top.state`,
+/// and a plain signal `top.plain` declared right after the table definition.
+fn write_enums(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let table = writer
+    .create_enum_table("state", 2, &[("IDLE", "0"), ("RUN", "1"), ("DONE", "10")])
+    .unwrap();
+  let plain = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 2, "plain", None)
+    .unwrap();
+  writer.emit_enum_table_ref(table);
+  let state = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 2, "state", None)
+    .unwrap();
+  writer.set_upscope();
+  for (time, value) in [(0, b"00"), (10, b"01"), (20, b"10"), (30, b"01")] {
+    writer.emit_time_change(time).unwrap();
+    writer.emit_value_change(plain, value).unwrap();
+    writer.emit_value_change(state, value).unwrap();
+  }
+  writer.emit_time_change(40).unwrap();
+}
+
+#[test]
+fn find_enum_literal() {
+  let file = TempFile::new("find-enum-literal");
+  write_enums(&file);
+  assert_eq!(
+    findfst(&file, &["RUN", "--enum", "--all-matches"]),
+    ["#10 top.state RUN", "#30 top.state RUN"]
+  );
+  assert_eq!(findfst(&file, &["DONE", "-e"]), ["#20 top.state DONE"]);
+}
+
+#[test]
+fn unknown_enum_literal() {
+  let file = TempFile::new("unknown-enum-literal");
+  write_enums(&file);
+  let output = run_findfst(&file, &["STOP", "--enum"]);
+  assert!(!output.status.success());
+  let stderr = String::from_utf8(output.stderr).unwrap();
+  assert_eq!(stderr.trim(), "Unknown enumeration literal: STOP!");
+}
+
+#[test]
+fn plain_signal_after_table_definition() {
+  let file = TempFile::new("plain-signal-after-table-definition");
+  write_enums(&file);
+  // Without `--enum`, both signals are matched by value.
+  let mut lines = findfst(&file, &["10"]);
+  lines.sort();
+  assert_eq!(lines, ["#20 top.plain 10", "#20 top.state 10"]);
+  // The plain signal does not reference the table.
+  assert_eq!(
+    findfst(&file, &["RUN", "-e", "-a", "-s", "plain"]),
+    Vec::<String>::new()
+  );
+}
@@ -0,0 +1,192 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Writer, scope_type, var_dir, var_type};
+
+/// Writes a waveform with a bus handshake in scope `top`,
+/// a signal `top.a-b` with special characters,
+/// and a signal `top.sub.valid` sharing the suffix with `top.valid`.
+fn write_bus(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let valid = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "valid", None)
+    .unwrap();
+  let ready = writer
+    .create_var(var_type::VCD_WIRE, var_dir::OUTPUT, 1, "ready", None)
+    .unwrap();
+  let addr = writer
+    .create_var(var_type::VCD_REG, var_dir::INPUT, 32, "addr", None)
+    .unwrap();
+  let data = writer
+    .create_var(var_type::VCD_REG, var_dir::INPUT, 8, "data", None)
+    .unwrap();
+  let special = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "a-b", None)
+    .unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "sub", "").unwrap();
+  let sub_valid = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "valid", None)
+    .unwrap();
+  writer.set_upscope();
+  writer.set_upscope();
+  let addr_value = |v: u32| format!("{v:032b}");
+  let changes = [
+    (0, valid, "0".into()),
+    (0, ready, "0".into()),
+    (0, addr, addr_value(0)),
+    (0, data, "00000000".into()),
+    (0, special, "0".into()),
+    (0, sub_valid, "0".into()),
+    (10, valid, "1".into()),
+    (10, addr, addr_value(0x8000_0000)),
+    (20, ready, "1".into()),
+    (30, addr, addr_value(0x8000_0004)),
+    (30, special, "1".into()),
+    (40, valid, "0".into()),
+    (50, valid, "1".into()),
+    (50, data, "11111111".into()),
+    (60, ready, "0".into()),
+    (60, data, "xxxx0001".into()),
+  ];
+  let mut last_time = None;
+  for (time, handle, value) in changes {
+    if last_time != Some(time) {
+      writer.emit_time_change(time).unwrap();
+      last_time = Some(time);
+    }
+    writer.emit_value_change(handle, value.as_bytes()).unwrap();
+  }
+  writer.emit_time_change(70).unwrap();
+}
+
+/// Runs `findfst --expr` with the given expression and `--all-matches`,
+/// returns times of matches.
+fn expr_times(file: &TempFile, expr: &str) -> Vec<u64> {
+  findfst(file, &["--expr", expr, "-a"])
+    .iter()
+    .map(|l| l[1..l.find(' ').unwrap()].parse().unwrap())
+    .collect()
+}
+
+/// Runs `findfst --expr` with the given expression,
+/// returns the error message.
+fn expr_error(file: &TempFile, expr: &str) -> String {
+  let output = run_findfst(file, &["--expr", expr]);
+  assert!(!output.status.success(), "{expr}");
+  String::from_utf8(output.stderr).unwrap().trim().into()
+}
+
+#[test]
+fn handshake() {
+  let file = TempFile::new("expr-handshake");
+  write_bus(&file);
+  // Values of referenced signals are printed in order of appearance.
+  assert_eq!(
+    findfst(&file, &["--expr", "top.valid && top.ready", "-a"]),
+    ["#20 top.valid=1 top.ready=1", "#50 top.valid=1 top.ready=1"]
+  );
+  assert_eq!(
+    findfst(
+      &file,
+      &["--expr", "top.valid && ready && addr == 0x8000_0000"]
+    ),
+    ["#20 top.valid=1 top.ready=1 top.addr=10000000000000000000000000000000"]
+  );
+  // Only the first match is printed by default.
+  assert_eq!(
+    findfst(&file, &["--expr", "ready ^ top.valid"]),
+    ["#10 top.ready=0 top.valid=1"]
+  );
+}
+
+#[test]
+fn operators() {
+  let file = TempFile::new("expr-operators");
+  write_bus(&file);
+  assert_eq!(expr_times(&file, "!top.valid"), [0, 40]);
+  assert_eq!(expr_times(&file, "ready ^ top.valid"), [10, 40, 60]);
+  // Expressions are only evaluated when referenced signals change.
+  assert_eq!(
+    expr_times(&file, "top.valid || ready"),
+    [10, 20, 40, 50, 60]
+  );
+  assert_eq!(expr_times(&file, "~data == 0"), [50]);
+  assert_eq!(expr_times(&file, "(addr & 0xf) != 0"), [30]);
+  assert_eq!(expr_times(&file, "data > 100 || addr[2]"), [30, 50, 60]);
+  assert_eq!(expr_times(&file, "data <= 0o377 && data < 1"), [0]);
+  assert_eq!(expr_times(&file, "addr >= 0x8000_0004"), [30]);
+}
+
+#[test]
+fn slices_and_literals() {
+  let file = TempFile::new("expr-slices-and-literals");
+  write_bus(&file);
+  assert_eq!(
+    expr_times(&file, "top.valid && ready && addr[31:28] == 'h8"),
+    [20, 30, 50]
+  );
+  assert_eq!(expr_times(&file, "data == 8'hff"), [50]);
+  assert_eq!(expr_times(&file, "data == 8'b1111_1111"), [50]);
+  assert_eq!(expr_times(&file, "addr == 32'd0"), [0]);
+  assert_eq!(expr_times(&file, "data[7:4] == 0b0"), [0]);
+}
+
+#[test]
+fn unknown_bits() {
+  let file = TempFile::new("expr-unknown-bits");
+  write_bus(&file);
+  // Comparisons with unknown bits are false.
+  assert_eq!(expr_times(&file, "data == 1"), Vec::<u64>::new());
+  assert_eq!(expr_times(&file, "data[3:0] == 1"), [60]);
+}
+
+#[test]
+fn signal_names() {
+  let file = TempFile::new("expr-signal-names");
+  write_bus(&file);
+  assert_eq!(findfst(&file, &["--expr", "`a-b`"]), ["#30 top.a-b=1"]);
+  assert_eq!(expr_times(&file, "sub.valid"), Vec::<u64>::new());
+  assert_eq!(
+    expr_error(&file, "valid"),
+    "Ambiguous signal in expression: valid!"
+  );
+  assert_eq!(
+    expr_error(&file, "nosuch"),
+    "Unknown signal in expression: nosuch!"
+  );
+  // Bit index out of range.
+  assert_eq!(
+    expr_error(&file, "data[8]"),
+    "Unsupported signal in expression: top.data!"
+  );
+}
+
+#[test]
+fn syntax_errors() {
+  let file = TempFile::new("expr-syntax-errors");
+  write_bus(&file);
+  assert_eq!(
+    expr_error(&file, "data =="),
+    "Invalid expression: unexpected end of expression!"
+  );
+  for expr in ["(valid", "addr[3:", "ready &&& valid"] {
+    let error = expr_error(&file, expr);
+    assert!(error.starts_with("Invalid expression: "), "{expr}: {error}");
+  }
+}
+
+#[test]
+fn in_parallel() {
+  let file = TempFile::new("expr-in-parallel");
+  write_bus(&file);
+  for expr in ["top.valid && top.ready", "data[3:0] == 1", "!top.valid"] {
+    assert_eq!(
+      findfst(&file, &["--expr", expr, "-a", "-j", "4"]),
+      findfst(&file, &["--expr", expr, "-a"]),
+      "{expr}"
+    );
+  }
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Writer, scope_type, var_dir, var_type};
+use serde_json::{Value, json};
+
+/// Writes a waveform with timescale `100ps`, a 4-bit bus `top.bus`,
+/// a 1-bit signal `top.a,b` and a string `top.msg`.
+fn write_formats(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true).unwrap().timescale(-10);
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let bus = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 4, "bus", None)
+    .unwrap();
+  let special = writer
+    .create_var(var_type::VCD_WIRE, var_dir::OUTPUT, 1, "a,b", None)
+    .unwrap();
+  let msg = writer
+    .create_var(var_type::GEN_STRING, var_dir::OUTPUT, 0, "msg", None)
+    .unwrap();
+  writer.set_upscope();
+  for (time, value) in [(0, b"0000"), (5, b"1010"), (10, b"0001"), (15, b"1010")] {
+    writer.emit_time_change(time).unwrap();
+    writer.emit_value_change(bus, value).unwrap();
+    let value = if time % 10 == 0 { b"0" } else { b"1" };
+    writer.emit_value_change(special, value).unwrap();
+    let value = if time == 5 { "1010" } else { "idle" };
+    writer
+      .emit_var_len_value_change(msg, value.as_bytes())
+      .unwrap();
+  }
+  writer.emit_time_change(20).unwrap();
+}
+
+/// Parses the given JSON Lines.
+fn parse_lines(lines: &[String]) -> Vec<Value> {
+  lines
+    .iter()
+    .map(|l| serde_json::from_str(l).unwrap())
+    .collect()
+}
+
+#[test]
+fn json_lines() {
+  let file = TempFile::new("formats-json-lines");
+  write_formats(&file);
+  let lines = findfst(&file, &["1010", "-a", "-f", "json"]);
+  assert_eq!(
+    parse_lines(&lines),
+    [
+      json!({
+        "time": 5, "scaled_time": 500, "time_unit": "ps",
+        "name": "top.bus", "handle": 1, "value": "1010", "hex": "a",
+      }),
+      json!({
+        "time": 5, "scaled_time": 500, "time_unit": "ps",
+        "name": "top.msg", "handle": 3, "value": "1010", "hex": "a",
+      }),
+      json!({
+        "time": 15, "scaled_time": 1500, "time_unit": "ps",
+        "name": "top.bus", "handle": 1, "value": "1010", "hex": "a",
+      }),
+    ]
+  );
+  // Values that are not bit vectors have no hexadecimal value.
+  let lines = findfst(&file, &["idle", "-r", "-s", "msg", "-f", "json"]);
+  assert_eq!(parse_lines(&lines)[0]["hex"], Value::Null);
+}
+
+#[test]
+fn csv() {
+  let file = TempFile::new("formats-csv");
+  write_formats(&file);
+  assert_eq!(
+    findfst(&file, &["1", "-a", "-s", "a,b", "-f", "csv"]),
+    [
+      "time,scaled_time,time_unit,name,handle,value,hex",
+      "5,500,ps,\"top.a,b\",2,1,1",
+      "15,1500,ps,\"top.a,b\",2,1,1",
+    ]
+  );
+  // Only the header is printed if nothing is found.
+  assert_eq!(
+    findfst(&file, &["1111", "-f", "csv"]),
+    ["time,scaled_time,time_unit,name,handle,value,hex"]
+  );
+}
+
+#[test]
+fn counts() {
+  let file = TempFile::new("formats-counts");
+  write_formats(&file);
+  // Signals are printed in order of the first match, `-c` implies `-a`.
+  assert_eq!(findfst(&file, &["1010", "-c"]), ["top.bus 2", "top.msg 1"]);
+  assert_eq!(
+    parse_lines(&findfst(&file, &["1010", "-c", "-f", "json"])),
+    [
+      json!({ "name": "top.bus", "handle": 1, "count": 2 }),
+      json!({ "name": "top.msg", "handle": 3, "count": 1 }),
+    ]
+  );
+  assert_eq!(
+    findfst(&file, &["1", "-c", "-s", "a,b", "-f", "csv"]),
+    ["name,handle,count", "\"top.a,b\",2,2"]
+  );
+  assert!(findfst(&file, &["1111", "-c"]).is_empty());
+}
+
+#[test]
+fn expr_formats() {
+  let file = TempFile::new("formats-expr-formats");
+  write_formats(&file);
+  let args = ["--expr", "bus == 0xa && `top.a,b`", "-f"];
+  assert_eq!(
+    parse_lines(&findfst(&file, &[&args[..], &["json"]].concat())),
+    [json!({
+      "time": 5, "scaled_time": 500, "time_unit": "ps",
+      "values": { "top.bus": "1010", "top.a,b": "1" },
+    })]
+  );
+  assert_eq!(
+    findfst(&file, &[&args[..], &["csv", "-a"]].concat()),
+    [
+      "time,scaled_time,time_unit,top.bus,\"top.a,b\"",
+      "5,500,ps,1010,1",
+      "15,1500,ps,1010,1",
+    ]
+  );
+  assert_eq!(
+    findfst(&file, &[&args[..], &["text", "-c"]].concat()),
+    ["2"]
+  );
+  assert_eq!(
+    findfst(&file, &[&args[..], &["json", "-c"]].concat()),
+    [r#"{"count":2}"#]
+  );
+  assert_eq!(
+    findfst(&file, &[&args[..], &["csv", "-c"]].concat()),
+    ["count", "2"]
+  );
+}
+
+#[test]
+fn names_only() {
+  let file = TempFile::new("formats-names-only");
+  write_formats(&file);
+  assert_eq!(findfst(&file, &["1010", "-n"]), ["top.bus", "top.msg"]);
+  for args in [&["1010", "-n", "-c"][..], &["1010", "-n", "-f", "json"]] {
+    assert!(!run_findfst(&file, args).status.success(), "{args:?}");
+  }
+}
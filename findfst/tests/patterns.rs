@@ -0,0 +1,147 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Writer, scope_type, var_dir, var_type};
+
+/// Writes a waveform with an 8-bit bus `top.bus` and a 1-bit signal `top.sig`
+/// with unknown bits, and a string `top.msg`.
+fn write_patterns(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let bus = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 8, "bus", None)
+    .unwrap();
+  let sig = writer
+    .create_var(var_type::VCD_WIRE, var_dir::OUTPUT, 1, "sig", None)
+    .unwrap();
+  let msg = writer
+    .create_var(var_type::GEN_STRING, var_dir::OUTPUT, 0, "msg", None)
+    .unwrap();
+  writer.set_upscope();
+  writer.emit_time_change(0).unwrap();
+  writer.emit_value_change(bus, b"00000000").unwrap();
+  writer.emit_value_change(sig, b"0").unwrap();
+  // Not a bit vector, even though it contains `x`.
+  writer.emit_var_len_value_change(msg, b"box").unwrap();
+  let changes: [(u64, _, &[u8]); 6] = [
+    (10, bus, b"10000001"),
+    (20, bus, b"1xxx0x1z"),
+    (25, sig, b"x"),
+    (30, bus, b"zzzz1111"),
+    (35, sig, b"1"),
+    (40, bus, b"11110000"),
+  ];
+  for (time, handle, value) in changes {
+    writer.emit_time_change(time).unwrap();
+    writer.emit_value_change(handle, value).unwrap();
+  }
+  writer.emit_time_change(50).unwrap();
+}
+
+/// Runs `findfst` on `top.bus` with the given arguments and `--all-matches`,
+/// returns times of matches.
+fn bus_times(file: &TempFile, args: &[&str]) -> Vec<u64> {
+  let args = [args, &["-a", "-s", "bus"]].concat();
+  findfst(file, &args)
+    .iter()
+    .map(|l| l[1..l.find(' ').unwrap()].parse().unwrap())
+    .collect()
+}
+
+/// Runs `findfst` with the given arguments, returns the error message.
+fn find_error(file: &TempFile, args: &[&str]) -> String {
+  let output = run_findfst(file, args);
+  assert!(!output.status.success(), "{args:?}");
+  String::from_utf8(output.stderr).unwrap().trim().into()
+}
+
+#[test]
+fn binary_patterns() {
+  let file = TempFile::new("patterns-binary-patterns");
+  write_patterns(&file);
+  // `x` and `z` only match themselves.
+  assert_eq!(findfst(&file, &["1???_0x1z"]), ["#20 top.bus 1xxx0x1z"]);
+  assert_eq!(bus_times(&file, &["1???_????"]), [10, 20, 40]);
+  assert_eq!(bus_times(&file, &["zzzz_????"]), [30]);
+  // Missing high bits are compared with `0`.
+  assert_eq!(bus_times(&file, &["1111"]), Vec::<u64>::new());
+  assert_eq!(bus_times(&file, &["1"]), Vec::<u64>::new());
+  assert_eq!(bus_times(&file, &["0"]), [0]);
+}
+
+#[test]
+fn hex_patterns() {
+  let file = TempFile::new("patterns-hex-patterns");
+  write_patterns(&file);
+  assert_eq!(bus_times(&file, &["-x", "8?"]), [10]);
+  assert_eq!(bus_times(&file, &["-x", "0x8?"]), [10]);
+  assert_eq!(bus_times(&file, &["-x", "F0"]), [40]);
+  assert_eq!(bus_times(&file, &["-x", "z?"]), [30]);
+  assert_eq!(bus_times(&file, &["-x", "?f"]), [30]);
+}
+
+#[test]
+fn masks() {
+  let file = TempFile::new("patterns-masks");
+  write_patterns(&file);
+  // Values and masks are aligned from the least significant bit.
+  assert_eq!(
+    bus_times(&file, &["1000_0000", "--mask", "1000_0000"]),
+    [10, 20, 40]
+  );
+  assert_eq!(bus_times(&file, &["1", "--mask", "1000_0000"]), [0]);
+  assert_eq!(
+    bus_times(&file, &["-x", "80", "--mask", "80"]),
+    [10, 20, 40]
+  );
+  // Bits beyond the mask are ignored.
+  assert_eq!(bus_times(&file, &["1", "--mask", "1"]), [10, 30]);
+  assert_eq!(bus_times(&file, &["-x", "f", "--mask", "f"]), [30]);
+  // Masked bits can still be unknown.
+  assert_eq!(bus_times(&file, &["x1z", "--mask", "111"]), [20]);
+}
+
+#[test]
+fn unknown_bits() {
+  let file = TempFile::new("patterns-unknown-bits");
+  write_patterns(&file);
+  // Strings are not matched.
+  assert_eq!(
+    findfst(&file, &["--has-x", "-a"]),
+    ["#20 top.bus 1xxx0x1z", "#25 top.sig x"]
+  );
+  assert_eq!(
+    findfst(&file, &["--has-z", "-a"]),
+    ["#20 top.bus 1xxx0x1z", "#30 top.bus zzzz1111"]
+  );
+  assert_eq!(
+    findfst(&file, &["--has-x", "--has-z", "-c"]),
+    ["top.bus 2", "top.sig 1"]
+  );
+}
+
+#[test]
+fn invalid_patterns() {
+  let file = TempFile::new("patterns-invalid-patterns");
+  write_patterns(&file);
+  assert_eq!(find_error(&file, &["12"]), "Invalid binary value: 12!");
+  assert_eq!(find_error(&file, &["__"]), "Invalid binary value: __!");
+  assert_eq!(
+    find_error(&file, &["-x", "g"]),
+    "Invalid hexadecimal value: g!"
+  );
+  assert_eq!(
+    find_error(&file, &["1", "--mask", "1?"]),
+    "Invalid mask: 1?!"
+  );
+  assert_eq!(find_error(&file, &["1", "--mask", "x"]), "Invalid mask: x!");
+  for args in [
+    &["1", "--has-x"][..],
+    &["1", "--mask", "1", "-r"],
+    &["--has-z", "--rising"],
+  ] {
+    find_error(&file, args);
+  }
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Writer, scope_type, var_dir, var_type};
+
+/// Writes a waveform with signals `top.sig` and `top.state`,
+/// flushes a value change block at time 15 if `flush` is `true`.
+fn write_signals(file: &TempFile, flush: bool) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let sig = writer
+    .create_var(var_type::VCD_WIRE, var_dir::OUTPUT, 1, "sig", None)
+    .unwrap();
+  let state = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 2, "state", None)
+    .unwrap();
+  writer.set_upscope();
+  let changes: [(u64, _, &[u8]); 9] = [
+    (0, sig, b"0"),
+    (0, state, b"00"),
+    (10, state, b"01"),
+    (18, state, b"10"),
+    (20, sig, b"1"),
+    (25, sig, b"0"),
+    (25, state, b"01"),
+    (30, sig, b"1"),
+    (30, state, b"10"),
+  ];
+  for (time, handle, value) in changes {
+    if flush && time == 18 {
+      writer.flush();
+    }
+    writer.emit_time_change(time).unwrap();
+    writer.emit_value_change(handle, value).unwrap();
+  }
+  writer.emit_time_change(40).unwrap();
+}
+
+/// Runs `findfst` with the given arguments on waveforms with and without
+/// multiple blocks, sequentially and in parallel, checks the output.
+fn check(name: &str, args: &[&str], expected: &[&str]) {
+  for flush in [false, true] {
+    let file = TempFile::new(&format!("{name}-{flush}"));
+    write_signals(&file, flush);
+    for jobs in ["1", "4"] {
+      let mut args = args.to_vec();
+      args.extend(["-j", jobs]);
+      assert_eq!(
+        findfst(&file, &args),
+        expected,
+        "flush: {flush}, jobs: {jobs}"
+      );
+    }
+  }
+}
+
+#[test]
+fn value_from_start() {
+  check(
+    "value-from-start",
+    &["10", "-a", "--start", "20"],
+    &["#30 top.state 10"],
+  );
+  check(
+    "value-until-end",
+    &["01", "-a", "-s", "state", "--start", "5", "--end", "25"],
+    &["#10 top.state 01", "#25 top.state 01"],
+  );
+}
+
+#[test]
+fn rising_from_start() {
+  check(
+    "rising-from-start",
+    &["--rising", "-a", "-s", "sig", "--start", "20"],
+    &["#20 top.sig 1", "#30 top.sig 1"],
+  );
+}
+
+#[test]
+fn transition_from_start() {
+  check(
+    "transition-from-start",
+    &["--changes-from", "10", "--to", "01", "-a", "--start", "25"],
+    &["#25 top.state 01"],
+  );
+  check(
+    "transition-before-start",
+    &["--changes-from", "01", "--to", "10", "-a", "--start", "20"],
+    &["#30 top.state 10"],
+  );
+}
+
+#[test]
+fn held_from_start() {
+  check(
+    "held-from-start",
+    &["10", "--held-for", "5", "-a", "--start", "20"],
+    &["#30 top.state 10"],
+  );
+}
+
+#[test]
+fn expr_from_start() {
+  check(
+    "expr-from-start",
+    &["--expr", "sig && state == 2", "-a", "--start", "20"],
+    &["#20 top.sig=1 top.state=10", "#30 top.sig=1 top.state=10"],
+  );
+}
@@ -1,6 +1,13 @@
-use std::{env, path::PathBuf};
-
 fn main() {
+  #[cfg(feature = "capi")]
+  build_capi();
+}
+
+/// Builds the C library and generates bindings.
+#[cfg(feature = "capi")]
+fn build_capi() {
+  use std::{env, path::PathBuf};
+
   #[cfg(windows)]
   let (zlib_include_dir, mman_include_dir) = {
     // Find zlib via vcpkg.
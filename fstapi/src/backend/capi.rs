@@ -0,0 +1,324 @@
+//! Backend built on the C library.
+
+use super::{Backend, BlockCallback, RawAttr, RawEnumTable, RawHier, RawScope, RawVar};
+use crate::consts::FileType;
+use crate::types::Handle;
+use crate::utils::*;
+use crate::{Result, capi};
+use std::borrow::Cow;
+use std::ffi::{CStr, c_char, c_int, c_uchar, c_void};
+use std::marker::PhantomData;
+use std::num::NonZeroU32;
+use std::path::Path;
+use std::{ptr, slice};
+
+/// Backend built on the C library.
+#[derive(Debug)]
+pub struct CBackend {
+  /// Non-null context pointer.
+  ctx: *mut c_void,
+  /// Time range limit applied to the context.
+  time_range: Option<(u64, u64)>,
+}
+
+// SAFETY: the context is exclusively owned by the backend,
+// and does not refer to any global or thread-local state.
+unsafe impl Send for CBackend {}
+
+impl Backend for CBackend {
+  type Hiers<'a> = CHiers<'a>;
+
+  fn open(path: &Path) -> Option<Self> {
+    let path = path.to_str()?.into_cstring().ok()?;
+    let ctx = unsafe { capi::fstReaderOpen(path.as_ptr()) };
+    (!ctx.is_null()).then_some(Self {
+      ctx,
+      time_range: None,
+    })
+  }
+
+  fn date(&self) -> &CStr {
+    unsafe { CStr::from_ptr(capi::fstReaderGetDateString(self.ctx)) }
+  }
+
+  fn version(&self) -> &CStr {
+    unsafe { CStr::from_ptr(capi::fstReaderGetVersionString(self.ctx)) }
+  }
+
+  fn start_time(&self) -> u64 {
+    unsafe { capi::fstReaderGetStartTime(self.ctx) }
+  }
+
+  fn end_time(&self) -> u64 {
+    unsafe { capi::fstReaderGetEndTime(self.ctx) }
+  }
+
+  fn file_type(&self) -> FileType {
+    unsafe { capi::fstReaderGetFileType(self.ctx) as FileType }
+  }
+
+  fn alias_count(&self) -> u64 {
+    unsafe { capi::fstReaderGetAliasCount(self.ctx) }
+  }
+
+  fn scope_count(&self) -> u64 {
+    unsafe { capi::fstReaderGetScopeCount(self.ctx) }
+  }
+
+  fn var_count(&self) -> u64 {
+    unsafe { capi::fstReaderGetVarCount(self.ctx) }
+  }
+
+  fn timescale(&self) -> i32 {
+    unsafe { capi::fstReaderGetTimescale(self.ctx) as i32 }
+  }
+
+  fn timezero(&self) -> i64 {
+    unsafe { capi::fstReaderGetTimezero(self.ctx) }
+  }
+
+  fn max_handle(&self) -> u32 {
+    unsafe { capi::fstReaderGetMaxHandle(self.ctx) }
+  }
+
+  fn value_change_block_count(&self) -> u64 {
+    unsafe { capi::fstReaderGetValueChangeSectionCount(self.ctx) }
+  }
+
+  fn double_endian_match(&self) -> bool {
+    unsafe { capi::fstReaderGetDoubleEndianMatchState(self.ctx) != 0 }
+  }
+
+  fn memory_used_by_writer(&self) -> u64 {
+    unsafe { capi::fstReaderGetMemoryUsedByWriter(self.ctx) }
+  }
+
+  fn seek_failed(&self) -> bool {
+    unsafe { capi::fstReaderGetFseekFailed(self.ctx) != 0 }
+  }
+
+  fn mask(&self, handle: Handle) -> bool {
+    unsafe { capi::fstReaderGetFacProcessMask(self.ctx, handle.into()) != 0 }
+  }
+
+  fn set_mask(&mut self, handle: Handle, enable: bool) {
+    if enable {
+      unsafe { capi::fstReaderSetFacProcessMask(self.ctx, handle.into()) }
+    } else {
+      unsafe { capi::fstReaderClrFacProcessMask(self.ctx, handle.into()) }
+    }
+  }
+
+  fn set_mask_all(&mut self, enable: bool) {
+    if enable {
+      unsafe { capi::fstReaderSetFacProcessMaskAll(self.ctx) }
+    } else {
+      unsafe { capi::fstReaderClrFacProcessMaskAll(self.ctx) }
+    }
+  }
+
+  fn set_time_range_limit(&mut self, range: Option<(u64, u64)>) {
+    self.time_range = range;
+    match range {
+      Some((start, end)) => unsafe { capi::fstReaderSetLimitTimeRange(self.ctx, start, end) },
+      None => unsafe { capi::fstReaderSetUnlimitedTimeRange(self.ctx) },
+    }
+  }
+
+  fn set_native_doubles(&mut self, enable: bool) {
+    unsafe { capi::fstReaderIterBlocksSetNativeDoublesOnCallback(self.ctx, enable as c_int) }
+  }
+
+  fn dump_activity_count(&self) -> u32 {
+    unsafe { capi::fstReaderGetNumberDumpActivityChanges(self.ctx) }
+  }
+
+  fn dump_activity_change(&self, index: u32) -> (u64, bool) {
+    let time = unsafe { capi::fstReaderGetDumpActivityChangeTime(self.ctx, index) };
+    let active = unsafe { capi::fstReaderGetDumpActivityChangeValue(self.ctx, index) };
+    (time, active != 0)
+  }
+
+  fn hiers(&mut self) -> CHiers<'_> {
+    unsafe { capi::fstReaderIterateHierRewind(self.ctx) };
+    CHiers {
+      ctx: self.ctx,
+      phantom: PhantomData,
+    }
+  }
+
+  fn iter_blocks(&mut self, callback: &mut BlockCallback) -> Result<()> {
+    /// Data passed to the C callbacks.
+    struct Data<'a, 'b> {
+      ctx: *mut c_void,
+      start: u64,
+      callback: &'a mut BlockCallback<'b>,
+      stopped: bool,
+    }
+
+    extern "C" fn c_callback<const VAR_LEN: bool>(
+      data: *mut c_void,
+      time: u64,
+      handle: capi::fstHandle,
+      value: *const c_uchar,
+      len: u32,
+    ) {
+      let data = unsafe { &mut *(data as *mut Data) };
+      if data.stopped {
+        return;
+      }
+      let handle = unsafe { Handle(NonZeroU32::new_unchecked(handle)) };
+      let value = unsafe { slice::from_raw_parts(value, len as usize) };
+      if (data.callback)(time, handle, value, VAR_LEN).is_break() {
+        data.stopped = true;
+        // The C library has no way to abort the iteration, so limit the
+        // end of the time range to make it stop after the current block.
+        unsafe { capi::fstReaderSetLimitTimeRange(data.ctx, data.start, time) }
+      }
+    }
+
+    let mut data = Data {
+      ctx: self.ctx,
+      start: self.time_range.map_or(0, |(s, _)| s),
+      callback,
+      stopped: false,
+    };
+    let ret = unsafe {
+      capi::fstReaderIterBlocks2(
+        self.ctx,
+        Some(c_callback::<false>),
+        Some(c_callback::<true>),
+        (&mut data) as *mut _ as *mut c_void,
+        ptr::null_mut(),
+      )
+    };
+    if data.stopped {
+      self.set_time_range_limit(self.time_range);
+    }
+    match ret {
+      0 => Err(self.last_error()),
+      _ => Ok(()),
+    }
+  }
+
+  fn value_at(&mut self, time: u64, handle: Handle, len: usize, buf: &mut Vec<u8>) -> Result<()> {
+    // Real values are formatted as strings, reserve space for them.
+    const MIN_BUF_LEN: usize = 32;
+    buf.clear();
+    buf.resize(len.max(MIN_BUF_LEN) + 1, 0);
+    let ret = unsafe {
+      capi::fstReaderGetValueFromHandleAtTime(
+        self.ctx,
+        time,
+        handle.into(),
+        buf.as_mut_ptr() as *mut c_char,
+      )
+    };
+    if ret.is_null() {
+      buf.clear();
+      return Err(self.last_error());
+    }
+    let len = buf.iter().position(|b| *b == 0).unwrap_or(buf.len());
+    buf.truncate(len);
+    Ok(())
+  }
+
+  fn dump_to_vcd(&mut self, path: Option<&Path>) -> Result<()> {
+    let path = path
+      .map(|p| PathToStr::to_str(&p)?.into_cstring())
+      .transpose()?;
+    let path = path.as_deref().map_or(ptr::null(), CStr::as_ptr);
+    match unsafe { capi::fstReaderDumpToVcdFile(self.ctx, path) } {
+      0 => Ok(()),
+      _ => Err(self.last_error()),
+    }
+  }
+
+  fn parse_enum_table(s: &CStr) -> Option<RawEnumTable> {
+    let etab = unsafe { capi::fstUtilityExtractEnumTableFromString(s.as_ptr()) };
+    let table = unsafe { etab.as_ref() }.map(|etab| {
+      let count = etab.elem_count as usize;
+      let (literals, values) = unsafe {
+        (
+          slice::from_raw_parts(etab.literal_arr, count),
+          slice::from_raw_parts(etab.val_arr, count),
+        )
+      };
+      let elems = literals
+        .iter()
+        .zip(values)
+        .map(|(l, v)| unsafe { (to_bytes(*l), to_bytes(*v)) })
+        .collect();
+      (unsafe { to_bytes(etab.name) }, elems)
+    });
+    unsafe { capi::fstUtilityFreeEnumTable(etab) };
+    table
+  }
+}
+
+impl Drop for CBackend {
+  fn drop(&mut self) {
+    unsafe { capi::fstReaderClose(self.ctx) }
+  }
+}
+
+/// Copies the given C string into bytes.
+///
+/// # Safety
+///
+/// `s` must be a valid nul-terminated C string.
+unsafe fn to_bytes(s: *const c_char) -> Vec<u8> {
+  unsafe { CStr::from_ptr(s) }.to_bytes().into()
+}
+
+/// An iterator over the hierarchies of [`CBackend`].
+#[derive(Debug)]
+pub struct CHiers<'a> {
+  ctx: *mut c_void,
+  phantom: PhantomData<&'a mut CBackend>,
+}
+
+impl<'a> Iterator for CHiers<'a> {
+  type Item = RawHier<'a>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    // The C library reuses its buffers for each item, so names are copied.
+    let name = |s: *const c_char| Cow::Owned(unsafe { CStr::from_ptr(s) }.into());
+    let hier = unsafe { capi::fstReaderIterateHier(self.ctx).as_ref() }?;
+    let hier = match hier.htyp as capi::fstHierType {
+      capi::fstHierType_FST_HT_SCOPE => {
+        let s = unsafe { &hier.u.scope };
+        RawHier::Scope(RawScope {
+          ty: s.typ,
+          name: name(s.name),
+          component: name(s.component),
+        })
+      }
+      capi::fstHierType_FST_HT_UPSCOPE => RawHier::Upscope,
+      capi::fstHierType_FST_HT_VAR => {
+        let v = unsafe { &hier.u.var };
+        RawHier::Var(RawVar {
+          ty: v.typ,
+          direction: v.direction,
+          name: name(v.name),
+          length: v.length,
+          handle: unsafe { Handle(NonZeroU32::new_unchecked(v.handle)) },
+          is_alias: v.is_alias() != 0,
+        })
+      }
+      capi::fstHierType_FST_HT_ATTRBEGIN => {
+        let a = unsafe { &hier.u.attr };
+        RawHier::AttrBegin(RawAttr {
+          ty: a.typ,
+          subtype: a.subtype,
+          name: name(a.name),
+          arg: a.arg,
+          arg_from_name: a.arg_from_name,
+        })
+      }
+      capi::fstHierType_FST_HT_ATTREND => RawHier::AttrEnd,
+      _ => unreachable!(),
+    };
+    Some(hier)
+  }
+}
@@ -0,0 +1,174 @@
+//! Backends of the reader.
+//!
+//! The reader is built on top of the [`Backend`] trait, which is implemented
+//! by the C library ([`capi::CBackend`]) and the native implementation
+//! in pure Rust ([`NativeBackend`](crate::native::NativeBackend)).
+
+#[cfg(all(feature = "capi", any(not(feature = "pure-rust"), test)))]
+pub mod capi;
+
+use crate::consts::FileType;
+use crate::types::Handle;
+use crate::{Error, Result};
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::fmt;
+use std::ops::ControlFlow;
+use std::path::Path;
+
+/// Callback of value changes, called with time, handle, value and `true`
+/// if the variable is variable-length.
+///
+/// Iteration stops after the callback returns [`ControlFlow::Break`].
+pub type BlockCallback<'a> = dyn FnMut(u64, Handle, &[u8], bool) -> ControlFlow<()> + 'a;
+
+/// Enumeration table parsed by the backend, in `(name, [(literal, value)])` form.
+pub type RawEnumTable = (Vec<u8>, Vec<(Vec<u8>, Vec<u8>)>);
+
+/// Backend of the reader, which decodes FST waveforms.
+pub trait Backend: Sized + Send {
+  /// Iterator over the hierarchies of the waveform.
+  type Hiers<'a>: Iterator<Item = RawHier<'a>> + fmt::Debug
+  where
+    Self: 'a;
+
+  /// Opens the waveform of the given path.
+  ///
+  /// Returns [`None`] if the file is not a valid FST waveform.
+  fn open(path: &Path) -> Option<Self>;
+
+  /// Returns date.
+  fn date(&self) -> &CStr;
+
+  /// Returns version.
+  fn version(&self) -> &CStr;
+
+  /// Returns start time.
+  fn start_time(&self) -> u64;
+
+  /// Returns end time.
+  fn end_time(&self) -> u64;
+
+  /// Returns file type.
+  fn file_type(&self) -> FileType;
+
+  /// Returns alias count.
+  fn alias_count(&self) -> u64;
+
+  /// Returns scope count.
+  fn scope_count(&self) -> u64;
+
+  /// Returns variable count.
+  fn var_count(&self) -> u64;
+
+  /// Returns timescale.
+  fn timescale(&self) -> i32;
+
+  /// Returns timezero.
+  fn timezero(&self) -> i64;
+
+  /// Returns the maximum handle, or zero if there is no variable.
+  fn max_handle(&self) -> u32;
+
+  /// Returns the number of value change blocks recorded in the header.
+  fn value_change_block_count(&self) -> u64;
+
+  /// Returns `true` if the byte order of doubles matches the current machine.
+  fn double_endian_match(&self) -> bool;
+
+  /// Returns the memory used by the writer, in bytes.
+  fn memory_used_by_writer(&self) -> u64;
+
+  /// Returns `true` if the seek operation failed.
+  fn seek_failed(&self) -> bool;
+
+  /// Returns process mask for the facility of the given handle.
+  fn mask(&self, handle: Handle) -> bool;
+
+  /// Sets or clears process mask for the facility of the given handle.
+  fn set_mask(&mut self, handle: Handle, enable: bool);
+
+  /// Sets or clears process mask for all facilities.
+  fn set_mask_all(&mut self, enable: bool);
+
+  /// Sets time range limit, or removes the limit if [`None`].
+  fn set_time_range_limit(&mut self, range: Option<(u64, u64)>);
+
+  /// Sets whether to use native doubles in callback when iterating over blocks.
+  fn set_native_doubles(&mut self, enable: bool);
+
+  /// Returns the number of dump activity changes.
+  fn dump_activity_count(&self) -> u32;
+
+  /// Returns the dump activity change of the given index,
+  /// in `(time, active)` form.
+  fn dump_activity_change(&self, index: u32) -> (u64, bool);
+
+  /// Returns an iterator over the hierarchies from the beginning.
+  fn hiers(&mut self) -> Self::Hiers<'_>;
+
+  /// Runs the given callback on value changes of all blocks,
+  /// only variables with process mask set are considered.
+  fn iter_blocks(&mut self, callback: &mut BlockCallback) -> Result<()>;
+
+  /// Writes the value of the variable of the given handle at the given time
+  /// to the given buffer, `len` is the length of the variable.
+  fn value_at(&mut self, time: u64, handle: Handle, len: usize, buf: &mut Vec<u8>) -> Result<()>;
+
+  /// Dumps the waveform as VCD to the given file,
+  /// or the standard output if [`None`].
+  fn dump_to_vcd(&mut self, path: Option<&Path>) -> Result<()>;
+
+  /// Parses the enumeration table from the given attribute name.
+  ///
+  /// Returns [`None`] if the name is not a valid enumeration table.
+  fn parse_enum_table(s: &CStr) -> Option<RawEnumTable>;
+
+  /// Returns the error of the last failed operation.
+  fn last_error(&self) -> Error {
+    if self.seek_failed() {
+      Error::SeekFailed
+    } else {
+      Error::InvalidOperation
+    }
+  }
+}
+
+/// Item in hierarchy returned by backends.
+#[derive(Debug)]
+pub enum RawHier<'a> {
+  Scope(RawScope<'a>),
+  Upscope,
+  Var(RawVar<'a>),
+  AttrBegin(RawAttr<'a>),
+  AttrEnd,
+}
+
+/// Scope in hierarchy.
+#[derive(Debug)]
+pub struct RawScope<'a> {
+  pub ty: u8,
+  pub name: Cow<'a, CStr>,
+  pub component: Cow<'a, CStr>,
+}
+
+/// Variable in hierarchy.
+#[derive(Debug)]
+pub struct RawVar<'a> {
+  pub ty: u8,
+  pub direction: u8,
+  pub name: Cow<'a, CStr>,
+  pub length: u32,
+  pub handle: Handle,
+  pub is_alias: bool,
+}
+
+/// Attribute in hierarchy.
+#[derive(Debug)]
+pub struct RawAttr<'a> {
+  pub ty: u8,
+  pub subtype: u8,
+  pub name: Cow<'a, CStr>,
+  pub arg: u64,
+  pub arg_from_name: u64,
+}
@@ -0,0 +1,141 @@
+use crate::consts::{BlockType, WriterPackType, block_type, writer_pack_type};
+use crate::{Error, Result};
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Metadata of a block (section) in FST waveform.
+///
+/// This struct is created by the [`blocks`](crate::Reader::blocks) method
+/// on [`Reader`](crate::Reader).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+  /// Type of the block.
+  pub ty: BlockType,
+  /// Offset of the block in the file, in bytes.
+  pub offset: u64,
+  /// Length of the block in the file, including the block type
+  /// and the section length, in bytes.
+  pub length: u64,
+  /// Length of the block data after decompression, in bytes.
+  ///
+  /// For value change blocks, this is the total length of the decompressed
+  /// initial values, value changes and time table.
+  pub uncompressed_length: u64,
+  /// Start time and end time of the block, for value change blocks.
+  pub time_range: Option<(u64, u64)>,
+  /// Compression of value changes, for value change blocks.
+  pub pack_type: Option<WriterPackType>,
+}
+
+impl Block {
+  /// Returns `true` if the block is a value change block.
+  pub fn is_value_change(&self) -> bool {
+    matches!(
+      self.ty,
+      block_type::VCDATA | block_type::VCDATA_DYN_ALIAS | block_type::VCDATA_DYN_ALIAS2
+    )
+  }
+}
+
+/// Reads metadata of all blocks of the waveform of the given path.
+pub(crate) fn read_blocks(path: &Path) -> Result<Vec<Block>> {
+  let file = File::open(path).map_err(|e| Error::Open(path.into(), e.kind()))?;
+  let mut file = BufReader::new(file);
+  let end = file.seek(SeekFrom::End(0)).map_err(|_| Error::SeekFailed)?;
+  let mut blocks = Vec::new();
+  let mut offset = 0;
+  while offset < end {
+    let Some(block) = read_block(&mut file, offset).map_err(|_| Error::SeekFailed)? else {
+      break;
+    };
+    offset += block.length;
+    blocks.push(block);
+  }
+  Ok(blocks)
+}
+
+/// Reads metadata of the block at the given offset.
+///
+/// Returns [`None`] if the block is incomplete,
+/// i.e. the waveform is still being written.
+fn read_block<R>(r: &mut R, offset: u64) -> io::Result<Option<Block>>
+where
+  R: Read + Seek,
+{
+  r.seek(SeekFrom::Start(offset))?;
+  let ty = read_u8(r)? as BlockType;
+  // Section length, which includes itself but not the block type.
+  let seclen = read_u64(r)?;
+  if seclen == 0 || ty == block_type::SKIP {
+    return Ok(None);
+  }
+  let mut block = Block {
+    ty,
+    offset,
+    length: seclen + 1,
+    uncompressed_length: seclen.saturating_sub(8),
+    time_range: None,
+    pack_type: None,
+  };
+  match ty {
+    block_type::GEOM
+    | block_type::HIER
+    | block_type::HIER_LZ4
+    | block_type::HIER_LZ4DUO
+    | block_type::ZWRAPPER => block.uncompressed_length = read_u64(r)?,
+    _ if block.is_value_change() => {
+      let start = read_u64(r)?;
+      let end = read_u64(r)?;
+      let mem_required = read_u64(r)?;
+      let frame_uclen = read_varint(r)?;
+      let frame_clen = read_varint(r)?;
+      let _frame_max_handle = read_varint(r)?;
+      r.seek(SeekFrom::Current(frame_clen as i64))?;
+      let _vc_max_handle = read_varint(r)?;
+      block.pack_type = match read_u8(r)? {
+        b'Z' => Some(writer_pack_type::ZLIB),
+        b'F' => Some(writer_pack_type::FASTLZ),
+        b'4' => Some(writer_pack_type::LZ4),
+        _ => None,
+      };
+      // The time table is at the end of the block.
+      let time_pos = seclen.checked_sub(24).ok_or(io::ErrorKind::InvalidData)?;
+      r.seek(SeekFrom::Start(offset + 1 + time_pos))?;
+      let time_uclen = read_u64(r)?;
+      block.uncompressed_length = frame_uclen
+        .saturating_add(mem_required)
+        .saturating_add(time_uclen);
+      block.time_range = Some((start, end));
+    }
+    _ => {}
+  }
+  Ok(Some(block))
+}
+
+/// Reads a byte.
+fn read_u8<R: Read>(r: &mut R) -> io::Result<u8> {
+  let mut buf = [0; 1];
+  r.read_exact(&mut buf)?;
+  Ok(buf[0])
+}
+
+/// Reads a big-endian 64-bit unsigned integer.
+fn read_u64<R: Read>(r: &mut R) -> io::Result<u64> {
+  let mut buf = [0; 8];
+  r.read_exact(&mut buf)?;
+  Ok(u64::from_be_bytes(buf))
+}
+
+/// Reads a variable-length integer.
+fn read_varint<R: Read>(r: &mut R) -> io::Result<u64> {
+  let mut value = 0;
+  for shift in (0..64).step_by(7) {
+    let b = read_u8(r)?;
+    value |= ((b & 0x7f) as u64) << shift;
+    if b & 0x80 == 0 {
+      return Ok(value);
+    }
+  }
+  Err(io::ErrorKind::InvalidData.into())
+}
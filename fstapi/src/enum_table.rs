@@ -0,0 +1,76 @@
+use crate::backend::Backend;
+use crate::utils::*;
+use crate::{Error, ReaderBackend, Result};
+
+/// Enumeration table, which maps values to their literals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnumTable {
+  name: String,
+  elems: Vec<(String, String)>,
+}
+
+impl EnumTable {
+  /// Parses the enumeration table from the given attribute name.
+  ///
+  /// The name should be in the form of `<name> <count> <literals...> <values...>`,
+  /// which is generated by
+  /// [`Writer::create_enum_table`](crate::Writer::create_enum_table).
+  pub fn from_attr_name(s: &str) -> Result<Self> {
+    // Check the form first, the C API does not handle missing spaces.
+    if s.split(' ').nth(1).is_none() {
+      return Err(Error::InvalidValue);
+    }
+    let s = s.into_cstring()?;
+    let (name, elems) = ReaderBackend::parse_enum_table(&s).ok_or(Error::InvalidValue)?;
+    let elems = elems
+      .into_iter()
+      .map(|(l, v)| Ok((to_string(l)?, to_string(v)?)))
+      .collect::<Result<_>>()?;
+    Ok(Self {
+      name: to_string(name)?,
+      elems,
+    })
+  }
+
+  /// Returns the name of the enumeration table.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Returns all elements in `(literal, value)` form.
+  pub fn elems(&self) -> &[(String, String)] {
+    &self.elems
+  }
+
+  /// Returns the literal of the given value.
+  ///
+  /// Values are compared as binary strings, leading zeros are ignored.
+  pub fn literal(&self, value: &[u8]) -> Option<&str> {
+    let value = trim_leading_zeros(value);
+    self
+      .elems
+      .iter()
+      .find(|(_, v)| trim_leading_zeros(v.as_bytes()) == value)
+      .map(|(l, _)| l.as_str())
+  }
+
+  /// Returns the value of the given literal.
+  pub fn value(&self, literal: &str) -> Option<&str> {
+    self
+      .elems
+      .iter()
+      .find(|(l, _)| l == literal)
+      .map(|(_, v)| v.as_str())
+  }
+}
+
+/// Converts the given bytes into [`String`].
+fn to_string(bytes: Vec<u8>) -> Result<String> {
+  String::from_utf8(bytes).map_err(|e| Error::InvalidUtf8Str(Some(e.utf8_error())))
+}
+
+/// Removes leading zeros of the given binary string.
+fn trim_leading_zeros(value: &[u8]) -> &[u8] {
+  let start = value.iter().position(|c| *c != b'0').unwrap_or(value.len());
+  &value[start..]
+}
@@ -0,0 +1,453 @@
+use crate::Result;
+use crate::consts::{AttrType, ScopeType, VarDir, VarType, attr_type, misc_type};
+use crate::enum_table::EnumTable;
+use crate::reader::{Attr, Hier, Reader, Scope, Var};
+use crate::types::{EnumHandle, Handle, Supplemental};
+use std::collections::HashMap;
+use std::slice;
+
+/// Identifier of a scope in [`Hierarchy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScopeId(usize);
+
+/// Identifier of a variable in [`Hierarchy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VarId(usize);
+
+/// Owned hierarchy tree of an FST waveform.
+///
+/// Scopes and variables can be looked up by their full names
+/// (separated by `.`), and variables can also be looked up by handles.
+#[derive(Clone, Debug, Default)]
+pub struct Hierarchy {
+  scopes: Vec<ScopeNode>,
+  vars: Vec<VarNode>,
+  root_scopes: Vec<ScopeId>,
+  root_vars: Vec<VarId>,
+  attrs: Vec<AttrNode>,
+  scope_paths: HashMap<String, ScopeId>,
+  var_paths: HashMap<String, VarId>,
+  handles: HashMap<Handle, VarId>,
+  enum_tables: HashMap<EnumHandle, EnumTable>,
+}
+
+impl Hierarchy {
+  /// Builds the hierarchy tree from the hierarchies of the given reader.
+  pub fn new(reader: &mut Reader) -> Result<Self> {
+    let mut hier = Self::default();
+    // Stack of scope identifiers and their full names.
+    let mut stack: Vec<(ScopeId, String)> = Vec::new();
+    let mut attrs = Vec::new();
+    for h in reader.hiers() {
+      match h {
+        Hier::Scope(s) => {
+          let id = ScopeId(hier.scopes.len());
+          let parent = stack.last().map(|(id, _)| *id);
+          let mut node = ScopeNode::new(s, parent)?;
+          node.attrs = std::mem::take(&mut attrs);
+          let path = match stack.last() {
+            Some((_, p)) => format!("{p}.{}", node.name),
+            None => node.name.clone(),
+          };
+          match parent {
+            Some(p) => hier.scopes[p.0].scopes.push(id),
+            None => hier.root_scopes.push(id),
+          }
+          hier.scopes.push(node);
+          hier.scope_paths.entry(path.clone()).or_insert(id);
+          stack.push((id, path));
+        }
+        Hier::Upscope => {
+          // Attributes at the end of a scope belong to the scope.
+          if let Some((id, _)) = stack.pop() {
+            hier.scopes[id.0].attrs.append(&mut attrs);
+          }
+        }
+        Hier::Var(v) => {
+          let id = VarId(hier.vars.len());
+          let scope = stack.last().map(|(id, _)| *id);
+          let mut node = VarNode::new(v, scope)?;
+          node.attrs = std::mem::take(&mut attrs);
+          node.enum_table = node.attrs.iter().rev().find_map(AttrNode::enum_table_ref);
+          let path = match stack.last() {
+            Some((_, p)) => format!("{p}.{}", node.name),
+            None => node.name.clone(),
+          };
+          match scope {
+            Some(s) => hier.scopes[s.0].vars.push(id),
+            None => hier.root_vars.push(id),
+          }
+          if !node.is_alias {
+            hier.handles.entry(node.handle).or_insert(id);
+          }
+          hier.vars.push(node);
+          hier.var_paths.entry(path).or_insert(id);
+        }
+        Hier::AttrBegin(a) => {
+          // Enumeration table definitions do not belong to any scope
+          // or variable, and have no attribute end.
+          if let Some(handle) = a.enum_handle()
+            && let Some(table) = a.enum_table()?
+          {
+            hier.enum_tables.insert(handle, table);
+            continue;
+          }
+          attrs.push(AttrNode::new(a)?);
+        }
+        Hier::AttrEnd => {}
+      }
+    }
+    hier.attrs = attrs;
+    Ok(hier)
+  }
+
+  /// Returns the scope of the given identifier.
+  pub fn scope(&self, id: ScopeId) -> &ScopeNode {
+    &self.scopes[id.0]
+  }
+
+  /// Returns the variable of the given identifier.
+  pub fn var(&self, id: VarId) -> &VarNode {
+    &self.vars[id.0]
+  }
+
+  /// Returns the number of scopes.
+  pub fn num_scopes(&self) -> usize {
+    self.scopes.len()
+  }
+
+  /// Returns the number of variables, including aliases.
+  pub fn num_vars(&self) -> usize {
+    self.vars.len()
+  }
+
+  /// Returns identifiers of the top-level scopes.
+  pub fn root_scopes(&self) -> &[ScopeId] {
+    &self.root_scopes
+  }
+
+  /// Returns identifiers of the variables that do not belong to any scope.
+  pub fn root_vars(&self) -> &[VarId] {
+    &self.root_vars
+  }
+
+  /// Returns attributes that do not belong to any scope or variable.
+  ///
+  /// Enumeration table definitions are not included,
+  /// see [`enum_table`](Hierarchy::enum_table).
+  pub fn attrs(&self) -> &[AttrNode] {
+    &self.attrs
+  }
+
+  /// Returns the scope of the given full name, like `top.cpu.core0`.
+  pub fn scope_by_path(&self, path: &str) -> Option<ScopeId> {
+    self.scope_paths.get(path).copied()
+  }
+
+  /// Returns the variable of the given full name, like `top.cpu.clk`.
+  pub fn var_by_path(&self, path: &str) -> Option<VarId> {
+    self.var_paths.get(path).copied()
+  }
+
+  /// Returns the first non-alias variable of the given handle.
+  pub fn var_by_handle(&self, handle: Handle) -> Option<VarId> {
+    self.handles.get(&handle).copied()
+  }
+
+  /// Returns the full name of the given scope.
+  pub fn scope_path(&self, id: ScopeId) -> String {
+    let scope = self.scope(id);
+    match scope.parent {
+      Some(p) => format!("{}.{}", self.scope_path(p), scope.name),
+      None => scope.name.clone(),
+    }
+  }
+
+  /// Returns the full name of the given variable.
+  pub fn var_path(&self, id: VarId) -> String {
+    let var = self.var(id);
+    match var.scope {
+      Some(s) => format!("{}.{}", self.scope_path(s), var.name),
+      None => var.name.clone(),
+    }
+  }
+
+  /// Returns the full name of the first non-alias variable of the given handle.
+  pub fn handle_path(&self, handle: Handle) -> Option<String> {
+    self.var_by_handle(handle).map(|id| self.var_path(id))
+  }
+
+  /// Returns the enumeration table of the given handle.
+  pub fn enum_table(&self, handle: EnumHandle) -> Option<&EnumTable> {
+    self.enum_tables.get(&handle)
+  }
+
+  /// Returns the enumeration table referenced by the given variable.
+  pub fn var_enum_table(&self, id: VarId) -> Option<&EnumTable> {
+    self.var(id).enum_table.and_then(|h| self.enum_table(h))
+  }
+
+  /// Returns the enumeration literal of the given value of the given variable.
+  pub fn enum_literal(&self, id: VarId, value: &[u8]) -> Option<&str> {
+    self.var_enum_table(id)?.literal(value)
+  }
+
+  /// Returns an iterator over all variables in the given scope
+  /// and its sub-scopes, recursively.
+  pub fn vars_under(&self, id: ScopeId) -> VarsUnder<'_> {
+    VarsUnder {
+      hier: self,
+      stack: vec![id],
+      vars: [].iter(),
+    }
+  }
+
+  /// Returns an iterator over all variables, including aliases.
+  pub fn vars(&self) -> impl Iterator<Item = (VarId, &VarNode)> {
+    self.vars.iter().enumerate().map(|(i, v)| (VarId(i), v))
+  }
+
+  /// Returns an iterator over all scopes.
+  pub fn scopes(&self) -> impl Iterator<Item = (ScopeId, &ScopeNode)> {
+    self.scopes.iter().enumerate().map(|(i, s)| (ScopeId(i), s))
+  }
+}
+
+/// A scope in [`Hierarchy`].
+#[derive(Clone, Debug)]
+pub struct ScopeNode {
+  ty: ScopeType,
+  name: String,
+  component: String,
+  attrs: Vec<AttrNode>,
+  parent: Option<ScopeId>,
+  scopes: Vec<ScopeId>,
+  vars: Vec<VarId>,
+}
+
+impl ScopeNode {
+  fn new(scope: Scope, parent: Option<ScopeId>) -> Result<Self> {
+    Ok(Self {
+      ty: scope.ty(),
+      name: scope.name()?.into(),
+      component: scope.component()?.into(),
+      attrs: Vec::new(),
+      parent,
+      scopes: Vec::new(),
+      vars: Vec::new(),
+    })
+  }
+
+  /// Returns scope type.
+  pub fn ty(&self) -> ScopeType {
+    self.ty
+  }
+
+  /// Returns scope name.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Returns scope component.
+  pub fn component(&self) -> &str {
+    &self.component
+  }
+
+  /// Returns attributes of the scope.
+  pub fn attrs(&self) -> &[AttrNode] {
+    &self.attrs
+  }
+
+  /// Returns the parent scope.
+  pub fn parent(&self) -> Option<ScopeId> {
+    self.parent
+  }
+
+  /// Returns the direct sub-scopes.
+  pub fn scopes(&self) -> &[ScopeId] {
+    &self.scopes
+  }
+
+  /// Returns the variables directly in the scope.
+  pub fn vars(&self) -> &[VarId] {
+    &self.vars
+  }
+}
+
+/// A variable in [`Hierarchy`].
+#[derive(Clone, Debug)]
+pub struct VarNode {
+  ty: VarType,
+  direction: VarDir,
+  name: String,
+  length: u32,
+  handle: Handle,
+  is_alias: bool,
+  attrs: Vec<AttrNode>,
+  scope: Option<ScopeId>,
+  enum_table: Option<EnumHandle>,
+  supplemental: Option<Supplemental>,
+}
+
+impl VarNode {
+  fn new(var: Var, scope: Option<ScopeId>) -> Result<Self> {
+    Ok(Self {
+      ty: var.ty(),
+      direction: var.direction(),
+      name: var.name()?.into(),
+      length: var.length(),
+      handle: var.handle(),
+      is_alias: var.is_alias(),
+      attrs: Vec::new(),
+      scope,
+      enum_table: None,
+      supplemental: var.supplemental().cloned(),
+    })
+  }
+
+  /// Returns variable type.
+  pub fn ty(&self) -> VarType {
+    self.ty
+  }
+
+  /// Returns variable direction.
+  pub fn direction(&self) -> VarDir {
+    self.direction
+  }
+
+  /// Returns variable name.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Returns variable length in bits.
+  pub fn length(&self) -> u32 {
+    self.length
+  }
+
+  /// Returns variable handle.
+  pub fn handle(&self) -> Handle {
+    self.handle
+  }
+
+  /// Returns `true` if variable is an alias.
+  ///
+  /// The aliased variable can be found by
+  /// [`Hierarchy::var_by_handle`].
+  pub fn is_alias(&self) -> bool {
+    self.is_alias
+  }
+
+  /// Returns attributes of the variable.
+  pub fn attrs(&self) -> &[AttrNode] {
+    &self.attrs
+  }
+
+  /// Returns the scope that the variable belongs to.
+  pub fn scope(&self) -> Option<ScopeId> {
+    self.scope
+  }
+
+  /// Returns supplemental type information of the variable.
+  pub fn supplemental(&self) -> Option<&Supplemental> {
+    self.supplemental.as_ref()
+  }
+
+  /// Returns the handle of the enumeration table referenced by the variable.
+  pub fn enum_table(&self) -> Option<EnumHandle> {
+    self.enum_table
+  }
+}
+
+/// An attribute in [`Hierarchy`].
+#[derive(Clone, Debug)]
+pub struct AttrNode {
+  ty: AttrType,
+  subtype: u32,
+  name: String,
+  arg: u64,
+  arg_from_name: u64,
+}
+
+impl AttrNode {
+  fn new(attr: Attr) -> Result<Self> {
+    Ok(Self {
+      ty: attr.ty(),
+      subtype: attr.subtype(),
+      name: attr.name()?.into(),
+      arg: attr.arg(),
+      arg_from_name: attr.arg_from_name(),
+    })
+  }
+
+  /// Returns attribute type.
+  pub fn ty(&self) -> AttrType {
+    self.ty
+  }
+
+  /// Returns attribute subtype.
+  ///
+  /// See [`Attr::subtype`] for details.
+  pub fn subtype(&self) -> u32 {
+    self.subtype
+  }
+
+  /// Returns attribute name.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Returns attribute argument.
+  pub fn arg(&self) -> u64 {
+    self.arg
+  }
+
+  /// Returns attribute argument generated by the attribute name.
+  ///
+  /// See [`Attr::arg_from_name`] for details.
+  pub fn arg_from_name(&self) -> u64 {
+    self.arg_from_name
+  }
+
+  /// Returns the enumeration table handle if the attribute is
+  /// an enumeration table definition or reference.
+  pub fn enum_handle(&self) -> Option<EnumHandle> {
+    if self.ty == attr_type::MISC && self.subtype == misc_type::ENUMTABLE {
+      EnumHandle::new(self.arg as u32)
+    } else {
+      None
+    }
+  }
+
+  /// Returns the enumeration table handle if the attribute is
+  /// an enumeration table reference, which has no name.
+  pub fn enum_table_ref(&self) -> Option<EnumHandle> {
+    self.enum_handle().filter(|_| self.name.is_empty())
+  }
+}
+
+/// An iterator over the variables in a scope and its sub-scopes.
+///
+/// This struct is created by the [`vars_under`](Hierarchy::vars_under)
+/// method on [`Hierarchy`].
+#[derive(Debug)]
+pub struct VarsUnder<'a> {
+  hier: &'a Hierarchy,
+  stack: Vec<ScopeId>,
+  vars: slice::Iter<'a, VarId>,
+}
+
+impl Iterator for VarsUnder<'_> {
+  type Item = VarId;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Some(id) = self.vars.next() {
+        return Some(*id);
+      }
+      let scope = self.hier.scope(self.stack.pop()?);
+      self.stack.extend(scope.scopes.iter().rev());
+      self.vars = scope.vars.iter();
+    }
+  }
+}
@@ -16,7 +16,8 @@
 //!
 //! Create an FST waveform:
 //!
-//! ```no_run
+#![cfg_attr(feature = "capi", doc = "```no_run")]
+#![cfg_attr(not(feature = "capi"), doc = "```ignore")]
 //! use fstapi::{Writer, var_type, var_dir};
 //!
 //! # fn main() -> fstapi::Result<()> {
@@ -52,25 +53,66 @@
 //! # }
 //! ```
 //!
+//! # Features
+//!
+//! * `capi` (default): builds the C library of GTKWave with bindgen, which
+//!   provides both [`Reader`] and [`Writer`]. Requires a C toolchain,
+//!   clang and zlib at build time.
+//! * `pure-rust`: provides [`Reader`] implemented in pure Rust, which
+//!   decodes waveforms without the C library. Takes precedence over the
+//!   C library for reading if both features are enabled.
+//!
+//! [`Writer`] is only available with the `capi` feature.
+//!
 //! # More Examples
 //!
 //! See the GitHub repository: [fst-tools](https://github.com/MaxXSoft/fst-tools),
 //! which contains 3 command line tools with this library
 //! for manipulating FST waveforms.
 
+#[cfg(not(any(feature = "capi", feature = "pure-rust")))]
+compile_error!("at least one of features `capi` and `pure-rust` must be enabled");
+
+mod backend;
+mod block;
+#[cfg(feature = "capi")]
 mod capi;
 mod consts;
+mod enum_table;
+mod hierarchy;
+#[cfg(feature = "pure-rust")]
+mod native;
+mod pool;
 mod reader;
 mod types;
 mod utils;
+mod value;
+#[cfg(feature = "capi")]
 mod writer;
 
+// Backend of the reader, the native one is preferred if enabled.
+#[cfg(not(feature = "pure-rust"))]
+use backend::capi::CBackend as ReaderBackend;
+#[cfg(not(feature = "pure-rust"))]
+use capi as ffi;
+#[cfg(feature = "pure-rust")]
+use native as ffi;
+#[cfg(feature = "pure-rust")]
+use native::NativeBackend as ReaderBackend;
+
+pub use block::*;
 pub use consts::*;
+pub use enum_table::*;
+pub use hierarchy::*;
+pub use pool::*;
 pub use reader::*;
 pub use types::*;
+pub use value::*;
+#[cfg(feature = "capi")]
 pub use writer::*;
 
-use std::fmt;
+use std::path::PathBuf;
+use std::{fmt, io};
 
 /// Error that may returned from FST-related APIs.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -85,6 +127,29 @@ pub enum Error {
   ContextCreate,
   /// Invalid operation.
   InvalidOperation,
+  /// Invalid value of variable.
+  InvalidValue,
+  /// Failed to open the file of the given path.
+  Open(PathBuf, io::ErrorKind),
+  /// The file of the given path is not a valid FST waveform.
+  NotFst(PathBuf),
+  /// Failed to create the file of the given path.
+  Create(PathBuf, io::ErrorKind),
+  /// Seek failed, the file may be truncated or corrupt.
+  SeekFailed,
+  /// The dump size limit has been reached.
+  DumpSizeLimitReached,
+  /// Invalid handle, which is not a handle of any variable.
+  InvalidHandle(Handle),
+  /// Invalid time, which is less than the previous time.
+  InvalidTime(u64),
+  /// Invalid value length, which does not match the length of the variable.
+  InvalidLength {
+    /// Length of the variable.
+    expected: u32,
+    /// Length of the given value.
+    actual: usize,
+  },
 }
 
 impl fmt::Display for Error {
@@ -96,6 +161,28 @@ impl fmt::Display for Error {
       Self::CStringConv(e) => write!(f, "CString conversion error, {e}"),
       Self::ContextCreate => write!(f, "context creation error"),
       Self::InvalidOperation => write!(f, "invalid operation"),
+      Self::InvalidValue => write!(f, "invalid value"),
+      Self::Open(p, k) => write!(f, "failed to open `{}`, {k}", p.display()),
+      Self::NotFst(p) => write!(f, "`{}` is not a valid FST waveform", p.display()),
+      Self::Create(p, k) => write!(f, "failed to create `{}`, {k}", p.display()),
+      Self::SeekFailed => write!(f, "seek failed, the file may be truncated or corrupt"),
+      Self::DumpSizeLimitReached => write!(f, "dump size limit reached"),
+      Self::InvalidHandle(h) => write!(f, "invalid handle {h}"),
+      Self::InvalidTime(t) => write!(f, "invalid time {t}, time can not go backwards"),
+      Self::InvalidLength { expected, actual } => {
+        write!(f, "invalid value length {actual}, expected {expected}")
+      }
+    }
+  }
+}
+
+impl std::error::Error for Error {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Self::InvalidUtf8Str(Some(e)) => Some(e),
+      Self::CStrConv(e) => Some(e),
+      Self::CStringConv(e) => Some(e),
+      _ => None,
     }
   }
 }
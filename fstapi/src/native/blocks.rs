@@ -0,0 +1,705 @@
+//! Value change blocks of the native backend.
+
+use super::context::Context;
+use super::decode::{self, corrupt, svarint, u64_at, varint, varint32};
+use super::format_g;
+use super::vcd::VcdWriter;
+use crate::backend::BlockCallback;
+use crate::consts::*;
+use crate::types::Handle;
+use std::io;
+
+/// Characters of non-binary values of 1-bit variables.
+const RCV_STR: &[u8; 8] = b"xzhuwl-?";
+
+/// Returns `true` if the given block type is a value change block.
+pub fn is_vc_block(ty: u8) -> bool {
+  matches!(
+    ty as u32,
+    block_type::VCDATA | block_type::VCDATA_DYN_ALIAS | block_type::VCDATA_DYN_ALIAS2
+  )
+}
+
+/// A value change block, with the time table and the chain table decoded.
+pub struct Block {
+  /// Data of the block, starting from the section length.
+  data: Vec<u8>,
+  pub beg_time: u64,
+  pub end_time: u64,
+  pub time_table: Vec<u64>,
+  /// Position and compressed length of the frame data.
+  frame: (usize, usize),
+  frame_uclen: usize,
+  pub frame_max_handle: usize,
+  pub vc_max_handle: usize,
+  /// Position of the pack type of value change data.
+  vc_start: usize,
+  pack_type: u8,
+  /// Positions of value change chains relative to `vc_start`,
+  /// zero for variables without value changes.
+  chain_table: Vec<u64>,
+  /// Lengths of value change chains.
+  chain_lens: Vec<i64>,
+}
+
+impl Block {
+  /// Parses the given value change block.
+  pub fn parse(ty: u8, data: Vec<u8>) -> io::Result<Self> {
+    let seclen = data.len();
+    let beg_time = u64_at(&data, 8)?;
+    let end_time = u64_at(&data, 16)?;
+
+    // Read the time table.
+    let tsec_pos = seclen.checked_sub(24).ok_or_else(corrupt)?;
+    let tsec_uclen = u64_at(&data, tsec_pos)? as usize;
+    let tsec_clen = u64_at(&data, tsec_pos + 8)? as usize;
+    let tsec_nitems = u64_at(&data, tsec_pos + 16)? as usize;
+    let tsec_start = tsec_pos.checked_sub(tsec_clen).ok_or_else(corrupt)?;
+    let tsec = &data[tsec_start..tsec_pos];
+    let tsec = if tsec_uclen != tsec_clen {
+      decode::zlib(tsec, tsec_uclen)?
+    } else {
+      tsec.to_vec()
+    };
+    let mut time_table = Vec::with_capacity(tsec_nitems.min(tsec.len()));
+    let (mut pos, mut time) = (0, 0u64);
+    for _ in 0..tsec_nitems {
+      let (delta, len) = varint(tsec.get(pos..).ok_or_else(corrupt)?)?;
+      pos += len;
+      time = time.wrapping_add(delta);
+      time_table.push(time);
+    }
+
+    // Read the frame header.
+    let mut pos = 32;
+    let mut next = || -> io::Result<usize> {
+      let (value, len) = varint(data.get(pos..).ok_or_else(corrupt)?)?;
+      pos += len;
+      Ok(value as usize)
+    };
+    let frame_uclen = next()?;
+    let frame_clen = next()?;
+    let frame_max_handle = next()?;
+    let frame = (pos, frame_clen);
+    pos += frame_clen;
+    let (vc_max_handle, len) = varint(data.get(pos..).ok_or_else(corrupt)?)?;
+    let vc_max_handle = vc_max_handle as usize;
+    let vc_start = pos + len;
+    let pack_type = *data.get(vc_start).ok_or_else(corrupt)?;
+
+    // Read the chain table.
+    let indx_pntr = tsec_start.checked_sub(8).ok_or_else(corrupt)?;
+    let chain_clen = u64_at(&data, indx_pntr)? as usize;
+    let indx_pos = indx_pntr.checked_sub(chain_clen).ok_or_else(corrupt)?;
+    let (chain_table, chain_lens) = parse_chain_table(
+      ty,
+      &data[indx_pos..indx_pntr],
+      vc_max_handle,
+      indx_pos.checked_sub(vc_start).ok_or_else(corrupt)? as u64,
+    )?;
+
+    Ok(Self {
+      data,
+      beg_time,
+      end_time,
+      time_table,
+      frame,
+      frame_uclen,
+      frame_max_handle,
+      vc_max_handle,
+      vc_start,
+      pack_type,
+      chain_table,
+      chain_lens,
+    })
+  }
+
+  /// Decompresses the frame data, which contains initial values of variables.
+  pub fn frame(&self) -> io::Result<Vec<u8>> {
+    let (pos, clen) = self.frame;
+    let frame = self.data.get(pos..pos + clen).ok_or_else(corrupt)?;
+    if clen == self.frame_uclen {
+      Ok(frame.to_vec())
+    } else {
+      decode::zlib(frame, self.frame_uclen)
+    }
+  }
+
+  /// Returns the number of entries in the chain table.
+  pub fn chain_count(&self) -> usize {
+    self.chain_table.len()
+  }
+
+  /// Returns `true` if the variable of the given index has value changes.
+  pub fn has_chain(&self, idx: usize) -> bool {
+    self.chain_table.get(idx).is_some_and(|p| *p != 0)
+  }
+
+  /// Decompresses the value change chain of the variable of the given index.
+  pub fn chain(&self, idx: usize) -> io::Result<Vec<u8>> {
+    let pos = self.vc_start + self.chain_table[idx] as usize;
+    let len = usize::try_from(self.chain_lens[idx]).map_err(|_| corrupt())?;
+    let chain = self.data.get(pos..pos + len).ok_or_else(corrupt)?;
+    let (uclen, skip) = varint32(chain)?;
+    let cdata = &chain[skip..];
+    match (uclen, self.pack_type) {
+      (0, _) => Ok(cdata.to_vec()),
+      (len, b'4') => decode::lz4(cdata, len as usize),
+      (len, b'F') => decode::fastlz(cdata, len as usize),
+      (len, _) => decode::zlib(cdata, len as usize),
+    }
+  }
+
+  /// Returns the time of the given index in the time table.
+  fn time(&self, idx: usize) -> io::Result<u64> {
+    self.time_table.get(idx).copied().ok_or_else(corrupt)
+  }
+}
+
+/// Parses the chain table of a value change block,
+/// returns positions and lengths of all chains.
+fn parse_chain_table(
+  ty: u8,
+  data: &[u8],
+  vc_max_handle: usize,
+  end: u64,
+) -> io::Result<(Vec<u64>, Vec<i64>)> {
+  let mut table = Vec::with_capacity(vc_max_handle + 1);
+  let mut lens = Vec::with_capacity(vc_max_handle + 1);
+  let (mut pos, mut pval, mut pidx) = (0, 0u64, 0);
+  let mut push_chain = |table: &mut Vec<u64>, lens: &mut Vec<i64>, offset: u64| {
+    pval += offset;
+    if !table.is_empty() {
+      lens[pidx] = (pval - table[pidx]) as i64;
+    }
+    pidx = table.len();
+    table.push(pval);
+    lens.push(0);
+  };
+  if ty as u32 == block_type::VCDATA_DYN_ALIAS2 {
+    let mut prev_alias = 0;
+    while pos < data.len() {
+      if data[pos] & 1 != 0 {
+        let (val, len) = svarint(&data[pos..])?;
+        pos += len;
+        let shval = val >> 1;
+        if shval > 0 {
+          push_chain(&mut table, &mut lens, shval as u64);
+        } else {
+          if shval < 0 {
+            prev_alias = shval;
+          }
+          table.push(0);
+          lens.push(prev_alias);
+        }
+      } else {
+        let (val, len) = varint32(&data[pos..])?;
+        pos += len;
+        let count = (val >> 1) as usize;
+        if table.len() + count > vc_max_handle {
+          return Err(corrupt());
+        }
+        table.resize(table.len() + count, 0);
+        lens.resize(table.len(), 0);
+      }
+    }
+  } else {
+    while pos < data.len() {
+      let (val, len) = varint32(&data[pos..])?;
+      pos += len;
+      if val == 0 {
+        let (alias, len) = varint32(data.get(pos..).ok_or_else(corrupt)?)?;
+        pos += len;
+        table.push(0);
+        lens.push(-(alias as i64));
+      } else if val & 1 != 0 {
+        push_chain(&mut table, &mut lens, (val >> 1) as u64);
+      } else {
+        let count = (val >> 1) as usize;
+        if table.len() + count > vc_max_handle {
+          return Err(corrupt());
+        }
+        table.resize(table.len() + count, 0);
+        lens.resize(table.len(), 0);
+      }
+    }
+  }
+  if table.len() > vc_max_handle {
+    return Err(corrupt());
+  }
+  if let Some(len) = lens.get_mut(pidx) {
+    *len = end.wrapping_sub(table[pidx]) as i64;
+  }
+
+  // Resolve aliases.
+  for i in 0..table.len() {
+    if lens[i] < 0 && table[i] == 0 {
+      let alias = (-lens[i] - 1) as usize;
+      if alias < i {
+        table[i] = table[alias];
+        lens[i] = lens[alias];
+      }
+    }
+  }
+  Ok((table, lens))
+}
+
+/// Output of value changes.
+pub enum Output<'a> {
+  /// Passes value changes to the callback,
+  /// until the callback stops the iteration.
+  Callback {
+    callback: &'a mut BlockCallback<'a>,
+    stopped: bool,
+  },
+  /// Writes value changes as VCD.
+  Vcd(VcdWriter<'a>),
+}
+
+impl Output<'_> {
+  /// Outputs a value change to the callback.
+  fn value(&mut self, time: u64, handle: u32, value: &[u8], var_len: bool) -> io::Result<()> {
+    if let Self::Callback { callback, stopped } = self
+      && !*stopped
+      && let Some(handle) = Handle::new(handle)
+    {
+      *stopped = callback(time, handle, value, var_len).is_break();
+    }
+    Ok(())
+  }
+
+  /// Returns `true` if the callback has stopped the iteration.
+  fn stopped(&self) -> bool {
+    matches!(self, Self::Callback { stopped: true, .. })
+  }
+
+  /// Outputs a value change of a 1-bit variable.
+  fn bit(&mut self, time: u64, handle: u32, value: u8) -> io::Result<()> {
+    match self {
+      Self::Callback { .. } => self.value(time, handle, &[value], false),
+      Self::Vcd(w) => w.bit(handle, value),
+    }
+  }
+
+  /// Outputs a value change of a vector variable.
+  fn vector(&mut self, time: u64, handle: u32, ty: u8, value: &[u8]) -> io::Result<()> {
+    match self {
+      Self::Callback { .. } => self.value(time, handle, value, false),
+      Self::Vcd(w) => w.vector(handle, ty, value),
+    }
+  }
+
+  /// Outputs a value change of a real variable.
+  fn real(&mut self, ctx: &Context, time: u64, handle: u32, value: &[u8]) -> io::Result<()> {
+    let d = ctx.double(value);
+    match self {
+      Self::Callback { .. } if ctx.native_doubles => {
+        self.value(time, handle, &d.to_ne_bytes(), false)
+      }
+      Self::Callback { .. } => self.value(time, handle, format_g(d).as_bytes(), false),
+      Self::Vcd(w) => w.real(handle, d),
+    }
+  }
+
+  /// Outputs a value change of a variable-length variable.
+  fn varlen(&mut self, time: u64, handle: u32, value: &[u8]) -> io::Result<()> {
+    match self {
+      Self::Callback { .. } => self.value(time, handle, value, true),
+      Self::Vcd(w) => w.string(handle, value),
+    }
+  }
+
+  /// Outputs a time change of a time table entry.
+  fn time_change(&mut self, time: u64, write_time: bool) -> io::Result<()> {
+    match self {
+      Self::Callback { .. } => Ok(()),
+      Self::Vcd(w) => w.time_change(time, write_time),
+    }
+  }
+}
+
+/// Iterates over all value change blocks, outputs value changes
+/// of variables with process mask set.
+pub fn iter_blocks(ctx: &mut Context, mut out: Output) -> io::Result<()> {
+  let mut pos = 0;
+  let mut secnum = 0;
+  let mut blocks_skipped = false;
+  let mut previous_time = None;
+  while let Some((ty, seclen)) = ctx.read_block_header(pos) {
+    if ty as u32 == block_type::SKIP {
+      break;
+    }
+    pos += 1;
+    if !is_vc_block(ty) {
+      pos += seclen;
+      continue;
+    }
+    if seclen == 0 {
+      break;
+    }
+
+    // Check the time range limit.
+    let head = ctx.read_at(pos + 8, 16)?;
+    let (beg_time, end_time) = (u64_at(&head, 0)?, u64_at(&head, 8)?);
+    if let Some((start, end)) = ctx.limit_range {
+      if end_time < start {
+        blocks_skipped = true;
+        pos += seclen;
+        continue;
+      }
+      if beg_time > end {
+        break;
+      }
+    }
+
+    let data = ctx.read_at(pos, seclen as usize)?;
+    let block = Block::parse(ty, data)?;
+    if secnum == 0 && (block.time_table.first() != Some(&beg_time) || blocks_skipped) {
+      out.time_change(beg_time, beg_time != 0)?;
+      output_frame(ctx, &block, &mut out)?;
+    }
+    output_block(ctx, &block, &mut out, &mut previous_time)?;
+    if out.stopped() {
+      break;
+    }
+
+    secnum += 1;
+    if secnum == ctx.header.vc_section_count {
+      break;
+    }
+    pos += seclen;
+  }
+  if let Output::Vcd(w) = &mut out {
+    w.flush()?;
+  }
+  Ok(())
+}
+
+/// Outputs the frame data of the given block.
+fn output_frame(ctx: &Context, block: &Block, out: &mut Output) -> io::Result<()> {
+  let frame = block.frame()?;
+  let time = block.beg_time;
+  let mut offset = 0;
+  for idx in 0..block.frame_max_handle.min(ctx.signal_lens.len()) {
+    let len = ctx.signal_lens[idx] as usize;
+    if ctx.process_mask[idx] && len != 0 {
+      let value = frame.get(offset..offset + len).ok_or_else(corrupt)?;
+      let handle = idx as u32 + 1;
+      if len == 1 {
+        out.bit(time, handle, value[0])?;
+      } else if ctx.signal_typs[idx] as u32 != var_type::VCD_REAL {
+        out.vector(time, handle, ctx.signal_typs[idx], value)?;
+      } else {
+        out.real(ctx, time, handle, value)?;
+      }
+    }
+    offset += len;
+  }
+  Ok(())
+}
+
+/// Outputs value changes in the given block.
+fn output_block(
+  ctx: &Context,
+  block: &Block,
+  out: &mut Output,
+  previous_time: &mut Option<u64>,
+) -> io::Result<()> {
+  let count = block.chain_count().min(ctx.signal_lens.len());
+  let nitems = block.time_table.len();
+  // Decompressed value change chains of all masked variables,
+  // and the read position in each chain.
+  let mut chains = vec![Vec::new(); count];
+  let mut heads = vec![0; count];
+  // Linked lists of variables to be processed at each time table index.
+  let mut tc_head = vec![0; nitems.max(1)];
+  let mut scatter = vec![0; count];
+
+  let tdelta_of = |chain: &[u8], pos: usize, is_bit: bool| -> io::Result<usize> {
+    let (vli, _) = varint32(chain.get(pos..).ok_or_else(corrupt)?)?;
+    Ok(if is_bit {
+      vli >> (2 << (vli & 1))
+    } else {
+      vli >> 1
+    } as usize)
+  };
+
+  for i in 0..count {
+    if block.has_chain(i) && ctx.process_mask[i] {
+      chains[i] = block.chain(i)?;
+      let tdelta = tdelta_of(&chains[i], 0, ctx.signal_lens[i] == 1)?;
+      let head = tc_head.get_mut(tdelta).ok_or_else(corrupt)?;
+      scatter[i] = *head;
+      *head = i + 1;
+    }
+  }
+
+  for i in 0..nitems {
+    if out.stopped() {
+      break;
+    }
+    let time = block.time_table[i];
+    if let Output::Vcd(_) = out
+      && *previous_time != Some(time)
+    {
+      if ctx.limit_range.is_some_and(|(_, end)| time > end) {
+        break;
+      }
+      out.time_change(time, true)?;
+      *previous_time = Some(time);
+    }
+    while tc_head[i] != 0 {
+      let idx = tc_head[i] - 1;
+      let chain = &chains[idx];
+      let pos = heads[idx];
+      let (vli, mut skip) = varint32(chain.get(pos..).ok_or_else(corrupt)?)?;
+      let handle = idx as u32 + 1;
+      let len = ctx.signal_lens[idx] as usize;
+      let is_bit = len == 1;
+      if is_bit {
+        let value = if vli & 1 == 0 {
+          ((vli >> 1) & 1) as u8 | b'0'
+        } else {
+          RCV_STR[((vli >> 1) & 7) as usize]
+        };
+        out.bit(time, handle, value)?;
+      } else if len == 0 {
+        let (vlen, len) = varint32(chain.get(pos + skip..).ok_or_else(corrupt)?)?;
+        skip += len;
+        let vdata = chain.get(pos + skip..pos + skip + vlen as usize);
+        let vdata = vdata.ok_or_else(corrupt)?;
+        if vli & 1 == 0 {
+          out.varlen(time, handle, vdata)?;
+        }
+        skip += vlen as usize;
+      } else {
+        let vdata = chain.get(pos + skip..).ok_or_else(corrupt)?;
+        let ty = ctx.signal_typs[idx];
+        if ty as u32 != var_type::VCD_REAL {
+          if vli & 1 == 0 {
+            let bytes = vdata.get(..len.div_ceil(8)).ok_or_else(corrupt)?;
+            out.vector(time, handle, ty, &unpack_bits(bytes, len))?;
+            skip += bytes.len();
+          } else {
+            out.vector(time, handle, ty, vdata.get(..len).ok_or_else(corrupt)?)?;
+            skip += len;
+          }
+        } else if vli & 1 == 0 {
+          // Very rare case, but possible.
+          let byte = *vdata.first().ok_or_else(corrupt)?;
+          out.real(ctx, time, handle, &unpack_bits(&[byte], 8))?;
+          skip += 1;
+        } else {
+          out.real(ctx, time, handle, vdata.get(..8).ok_or_else(corrupt)?)?;
+          skip += len;
+        }
+      }
+
+      heads[idx] += skip;
+      tc_head[i] = scatter[idx];
+      scatter[idx] = 0;
+      if heads[idx] < chain.len() {
+        let tdelta = tdelta_of(chain, heads[idx], is_bit)?;
+        let head = tc_head.get_mut(i + tdelta).ok_or_else(corrupt)?;
+        scatter[idx] = *head;
+        *head = idx + 1;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Unpacks the given MSB-first packed bits to `len` binary characters.
+fn unpack_bits(bytes: &[u8], len: usize) -> Vec<u8> {
+  (0..len)
+    .map(|j| ((bytes[j / 8] >> (7 - (j & 7))) & 1) | b'0')
+    .collect()
+}
+
+/// Value change block cached for reading values at specific times.
+pub struct RvatBlock {
+  block: Block,
+  /// Time range in which values can be read from this block.
+  range: (u64, u64),
+  frame: Vec<u8>,
+  /// Offsets of variables in the frame data.
+  sig_offs: Vec<usize>,
+  /// Index and decompressed value change chain of the last read variable.
+  chain: Option<(usize, Vec<u8>)>,
+}
+
+impl RvatBlock {
+  /// Loads the value change block that contains the given time.
+  ///
+  /// If there is no such block, loads the last block before the given time,
+  /// since variables hold their values until the next block
+  /// or the end of the waveform.
+  fn load(ctx: &mut Context, time: u64) -> io::Result<Option<Self>> {
+    let mut pos = 0;
+    let (mut last, mut next_beg) = (None, ctx.header.end_time.saturating_add(1));
+    let found = loop {
+      let Some((ty, seclen)) = ctx.read_block_header(pos) else {
+        break None;
+      };
+      if ty as u32 == block_type::SKIP || seclen == 0 {
+        break None;
+      }
+      pos += 1;
+      if is_vc_block(ty) {
+        let head = ctx.read_at(pos + 8, 16)?;
+        let (beg_time, end_time) = (u64_at(&head, 0)?, u64_at(&head, 8)?);
+        if beg_time <= time && time <= end_time {
+          break Some((ty, pos, seclen, end_time));
+        } else if end_time < time {
+          last = Some((ty, pos, seclen, end_time));
+        } else {
+          next_beg = next_beg.min(beg_time);
+        }
+      }
+      pos += seclen;
+    };
+    let (found, range) = match (found, last) {
+      (Some(found), _) => (found, None),
+      (None, Some(last)) if time <= ctx.header.end_time => (last, Some((last.3 + 1, next_beg - 1))),
+      _ => return Ok(None),
+    };
+    let (mut ty, mut pos, mut seclen, end_time) = found;
+
+    // Value changes at the end time of a block may be in the next block.
+    if time == end_time && time != ctx.header.end_time {
+      let next = pos + seclen;
+      if let Some((ty2, seclen2)) = ctx.read_block_header(next)
+        && is_vc_block(ty2)
+        && seclen2 != 0
+        && u64_at(&ctx.read_at(next + 9, 8)?, 0)? == time
+      {
+        (ty, pos, seclen) = (ty2, next + 1, seclen2);
+      }
+    }
+
+    let block = Block::parse(ty, ctx.read_at(pos, seclen as usize)?)?;
+    let frame = block.frame()?;
+    let sig_offs = ctx
+      .signal_lens
+      .iter()
+      .scan(0, |offset, len| {
+        let cur = *offset;
+        *offset += *len as usize;
+        Some(cur)
+      })
+      .collect();
+    let range = range.unwrap_or((block.beg_time, block.end_time));
+    Ok(Some(Self {
+      block,
+      range,
+      frame,
+      sig_offs,
+      chain: None,
+    }))
+  }
+
+  /// Returns the value of the given variable from the frame data.
+  fn frame_value(&self, ctx: &Context, idx: usize) -> Option<Vec<u8>> {
+    if idx >= self.block.frame_max_handle {
+      return None;
+    }
+    let len = ctx.signal_lens[idx] as usize;
+    let offset = self.sig_offs[idx];
+    let value = self.frame.get(offset..offset + len)?;
+    if len != 1 && ctx.signal_typs[idx] as u32 == var_type::VCD_REAL {
+      Some(format_g(ctx.double(value)).into_bytes())
+    } else {
+      Some(value.to_vec())
+    }
+  }
+
+  /// Returns the value of the given variable at the given time.
+  fn value(&mut self, ctx: &Context, time: u64, idx: usize) -> io::Result<Option<Vec<u8>>> {
+    if !self.block.has_chain(idx) {
+      return Ok(self.frame_value(ctx, idx));
+    }
+    if self.chain.as_ref().is_none_or(|(i, _)| *i != idx) {
+      self.chain = Some((idx, self.block.chain(idx)?));
+    }
+    let chain = &self.chain.as_ref().unwrap().1;
+
+    // Find the last value change at or before the given time.
+    let len = ctx.signal_lens[idx] as usize;
+    let mut found = None;
+    let (mut pos, mut tidx) = (0, 0);
+    while pos < chain.len() {
+      let (vli, skip) = varint32(&chain[pos..])?;
+      let tdelta = if len == 1 {
+        vli >> (2 << (vli & 1))
+      } else {
+        vli >> 1
+      } as usize;
+      if self.block.time(tidx + tdelta)? > time {
+        break;
+      }
+      found = Some((vli, pos + skip));
+      tidx += tdelta;
+      pos += skip;
+      if len != 1 {
+        pos += if vli & 1 == 0 { len.div_ceil(8) } else { len };
+      }
+    }
+    let Some((vli, vpos)) = found else {
+      return Ok(self.frame_value(ctx, idx));
+    };
+
+    let value = if len == 1 {
+      if vli & 1 == 0 {
+        vec![((vli >> 1) & 1) as u8 | b'0']
+      } else {
+        vec![RCV_STR[((vli >> 1) & 7) as usize]]
+      }
+    } else {
+      let vdata = &chain[vpos..];
+      if ctx.signal_typs[idx] as u32 != var_type::VCD_REAL {
+        if vli & 1 == 0 {
+          unpack_bits(vdata.get(..len.div_ceil(8)).ok_or_else(corrupt)?, len)
+        } else {
+          vdata.get(..len).ok_or_else(corrupt)?.to_vec()
+        }
+      } else {
+        let d = if vli & 1 == 0 {
+          // Very rare case, but possible.
+          ctx.double(&unpack_bits(vdata.get(..1).ok_or_else(corrupt)?, 8))
+        } else {
+          ctx.double(vdata.get(..8).ok_or_else(corrupt)?)
+        };
+        format!("r{}", format_g(d)).into_bytes()
+      }
+    };
+    Ok(Some(value))
+  }
+}
+
+/// Returns the value of the variable of the given handle at the given time.
+///
+/// Returns [`None`] if the value is not available.
+pub fn value_at(ctx: &mut Context, time: u64, handle: u32) -> io::Result<Option<Vec<u8>>> {
+  let idx = handle as usize;
+  if idx == 0 || idx > ctx.signal_lens.len() || ctx.signal_lens[idx - 1] == 0 {
+    return Ok(None);
+  }
+  let cached = ctx
+    .rvat
+    .as_ref()
+    .is_some_and(|r| r.range.0 <= time && time <= r.range.1);
+  if !cached {
+    ctx.rvat = None;
+    ctx.rvat = RvatBlock::load(ctx, time)?;
+  }
+  let Some(mut rvat) = ctx.rvat.take() else {
+    return Ok(None);
+  };
+  let ret = if idx > rvat.block.vc_max_handle {
+    Ok(None)
+  } else {
+    rvat.value(ctx, time, idx - 1)
+  };
+  ctx.rvat = Some(rvat);
+  ret
+}
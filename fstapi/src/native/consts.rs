@@ -0,0 +1,193 @@
+//! Enumerations of FST format, mirrors the ones in `csrc/fstapi.h`.
+//!
+//! Names are the same as the ones generated by bindgen, so the native
+//! backend can be used as a drop-in replacement of the C API.
+
+#![allow(non_upper_case_globals, non_camel_case_types)]
+
+pub type fstWriterPackType = u32;
+pub const fstWriterPackType_FST_WR_PT_ZLIB: fstWriterPackType = 0;
+pub const fstWriterPackType_FST_WR_PT_FASTLZ: fstWriterPackType = 1;
+pub const fstWriterPackType_FST_WR_PT_LZ4: fstWriterPackType = 2;
+
+pub type fstFileType = u32;
+pub const fstFileType_FST_FT_MIN: fstFileType = 0;
+pub const fstFileType_FST_FT_VERILOG: fstFileType = 0;
+pub const fstFileType_FST_FT_VHDL: fstFileType = 1;
+pub const fstFileType_FST_FT_VERILOG_VHDL: fstFileType = 2;
+pub const fstFileType_FST_FT_MAX: fstFileType = 2;
+
+pub type fstBlockType = u32;
+pub const fstBlockType_FST_BL_HDR: fstBlockType = 0;
+pub const fstBlockType_FST_BL_VCDATA: fstBlockType = 1;
+pub const fstBlockType_FST_BL_BLACKOUT: fstBlockType = 2;
+pub const fstBlockType_FST_BL_GEOM: fstBlockType = 3;
+pub const fstBlockType_FST_BL_HIER: fstBlockType = 4;
+pub const fstBlockType_FST_BL_VCDATA_DYN_ALIAS: fstBlockType = 5;
+pub const fstBlockType_FST_BL_HIER_LZ4: fstBlockType = 6;
+pub const fstBlockType_FST_BL_HIER_LZ4DUO: fstBlockType = 7;
+pub const fstBlockType_FST_BL_VCDATA_DYN_ALIAS2: fstBlockType = 8;
+pub const fstBlockType_FST_BL_ZWRAPPER: fstBlockType = 254;
+pub const fstBlockType_FST_BL_SKIP: fstBlockType = 255;
+
+pub type fstScopeType = u32;
+pub const fstScopeType_FST_ST_MIN: fstScopeType = 0;
+pub const fstScopeType_FST_ST_VCD_MODULE: fstScopeType = 0;
+pub const fstScopeType_FST_ST_VCD_TASK: fstScopeType = 1;
+pub const fstScopeType_FST_ST_VCD_FUNCTION: fstScopeType = 2;
+pub const fstScopeType_FST_ST_VCD_BEGIN: fstScopeType = 3;
+pub const fstScopeType_FST_ST_VCD_FORK: fstScopeType = 4;
+pub const fstScopeType_FST_ST_VCD_GENERATE: fstScopeType = 5;
+pub const fstScopeType_FST_ST_VCD_STRUCT: fstScopeType = 6;
+pub const fstScopeType_FST_ST_VCD_UNION: fstScopeType = 7;
+pub const fstScopeType_FST_ST_VCD_CLASS: fstScopeType = 8;
+pub const fstScopeType_FST_ST_VCD_INTERFACE: fstScopeType = 9;
+pub const fstScopeType_FST_ST_VCD_PACKAGE: fstScopeType = 10;
+pub const fstScopeType_FST_ST_VCD_PROGRAM: fstScopeType = 11;
+pub const fstScopeType_FST_ST_VHDL_ARCHITECTURE: fstScopeType = 12;
+pub const fstScopeType_FST_ST_VHDL_PROCEDURE: fstScopeType = 13;
+pub const fstScopeType_FST_ST_VHDL_FUNCTION: fstScopeType = 14;
+pub const fstScopeType_FST_ST_VHDL_RECORD: fstScopeType = 15;
+pub const fstScopeType_FST_ST_VHDL_PROCESS: fstScopeType = 16;
+pub const fstScopeType_FST_ST_VHDL_BLOCK: fstScopeType = 17;
+pub const fstScopeType_FST_ST_VHDL_FOR_GENERATE: fstScopeType = 18;
+pub const fstScopeType_FST_ST_VHDL_IF_GENERATE: fstScopeType = 19;
+pub const fstScopeType_FST_ST_VHDL_GENERATE: fstScopeType = 20;
+pub const fstScopeType_FST_ST_VHDL_PACKAGE: fstScopeType = 21;
+pub const fstScopeType_FST_ST_MAX: fstScopeType = 21;
+pub const fstScopeType_FST_ST_GEN_ATTRBEGIN: fstScopeType = 252;
+pub const fstScopeType_FST_ST_GEN_ATTREND: fstScopeType = 253;
+pub const fstScopeType_FST_ST_VCD_SCOPE: fstScopeType = 254;
+pub const fstScopeType_FST_ST_VCD_UPSCOPE: fstScopeType = 255;
+
+pub type fstVarType = u32;
+pub const fstVarType_FST_VT_MIN: fstVarType = 0;
+pub const fstVarType_FST_VT_VCD_EVENT: fstVarType = 0;
+pub const fstVarType_FST_VT_VCD_INTEGER: fstVarType = 1;
+pub const fstVarType_FST_VT_VCD_PARAMETER: fstVarType = 2;
+pub const fstVarType_FST_VT_VCD_REAL: fstVarType = 3;
+pub const fstVarType_FST_VT_VCD_REAL_PARAMETER: fstVarType = 4;
+pub const fstVarType_FST_VT_VCD_REG: fstVarType = 5;
+pub const fstVarType_FST_VT_VCD_SUPPLY0: fstVarType = 6;
+pub const fstVarType_FST_VT_VCD_SUPPLY1: fstVarType = 7;
+pub const fstVarType_FST_VT_VCD_TIME: fstVarType = 8;
+pub const fstVarType_FST_VT_VCD_TRI: fstVarType = 9;
+pub const fstVarType_FST_VT_VCD_TRIAND: fstVarType = 10;
+pub const fstVarType_FST_VT_VCD_TRIOR: fstVarType = 11;
+pub const fstVarType_FST_VT_VCD_TRIREG: fstVarType = 12;
+pub const fstVarType_FST_VT_VCD_TRI0: fstVarType = 13;
+pub const fstVarType_FST_VT_VCD_TRI1: fstVarType = 14;
+pub const fstVarType_FST_VT_VCD_WAND: fstVarType = 15;
+pub const fstVarType_FST_VT_VCD_WIRE: fstVarType = 16;
+pub const fstVarType_FST_VT_VCD_WOR: fstVarType = 17;
+pub const fstVarType_FST_VT_VCD_PORT: fstVarType = 18;
+pub const fstVarType_FST_VT_VCD_SPARRAY: fstVarType = 19;
+pub const fstVarType_FST_VT_VCD_REALTIME: fstVarType = 20;
+pub const fstVarType_FST_VT_GEN_STRING: fstVarType = 21;
+pub const fstVarType_FST_VT_SV_BIT: fstVarType = 22;
+pub const fstVarType_FST_VT_SV_LOGIC: fstVarType = 23;
+pub const fstVarType_FST_VT_SV_INT: fstVarType = 24;
+pub const fstVarType_FST_VT_SV_SHORTINT: fstVarType = 25;
+pub const fstVarType_FST_VT_SV_LONGINT: fstVarType = 26;
+pub const fstVarType_FST_VT_SV_BYTE: fstVarType = 27;
+pub const fstVarType_FST_VT_SV_ENUM: fstVarType = 28;
+pub const fstVarType_FST_VT_SV_SHORTREAL: fstVarType = 29;
+pub const fstVarType_FST_VT_MAX: fstVarType = 29;
+
+pub type fstVarDir = u32;
+pub const fstVarDir_FST_VD_MIN: fstVarDir = 0;
+pub const fstVarDir_FST_VD_IMPLICIT: fstVarDir = 0;
+pub const fstVarDir_FST_VD_INPUT: fstVarDir = 1;
+pub const fstVarDir_FST_VD_OUTPUT: fstVarDir = 2;
+pub const fstVarDir_FST_VD_INOUT: fstVarDir = 3;
+pub const fstVarDir_FST_VD_BUFFER: fstVarDir = 4;
+pub const fstVarDir_FST_VD_LINKAGE: fstVarDir = 5;
+pub const fstVarDir_FST_VD_MAX: fstVarDir = 5;
+
+pub type fstAttrType = u32;
+pub const fstAttrType_FST_AT_MIN: fstAttrType = 0;
+pub const fstAttrType_FST_AT_MISC: fstAttrType = 0;
+pub const fstAttrType_FST_AT_ARRAY: fstAttrType = 1;
+pub const fstAttrType_FST_AT_ENUM: fstAttrType = 2;
+pub const fstAttrType_FST_AT_PACK: fstAttrType = 3;
+pub const fstAttrType_FST_AT_MAX: fstAttrType = 3;
+
+pub type fstMiscType = u32;
+pub const fstMiscType_FST_MT_MIN: fstMiscType = 0;
+pub const fstMiscType_FST_MT_COMMENT: fstMiscType = 0;
+pub const fstMiscType_FST_MT_ENVVAR: fstMiscType = 1;
+pub const fstMiscType_FST_MT_SUPVAR: fstMiscType = 2;
+pub const fstMiscType_FST_MT_PATHNAME: fstMiscType = 3;
+pub const fstMiscType_FST_MT_SOURCESTEM: fstMiscType = 4;
+pub const fstMiscType_FST_MT_SOURCEISTEM: fstMiscType = 5;
+pub const fstMiscType_FST_MT_VALUELIST: fstMiscType = 6;
+pub const fstMiscType_FST_MT_ENUMTABLE: fstMiscType = 7;
+pub const fstMiscType_FST_MT_UNKNOWN: fstMiscType = 8;
+pub const fstMiscType_FST_MT_MAX: fstMiscType = 8;
+
+pub type fstArrayType = u32;
+pub const fstArrayType_FST_AR_MIN: fstArrayType = 0;
+pub const fstArrayType_FST_AR_NONE: fstArrayType = 0;
+pub const fstArrayType_FST_AR_UNPACKED: fstArrayType = 1;
+pub const fstArrayType_FST_AR_PACKED: fstArrayType = 2;
+pub const fstArrayType_FST_AR_SPARSE: fstArrayType = 3;
+pub const fstArrayType_FST_AR_MAX: fstArrayType = 3;
+
+pub type fstEnumValueType = u32;
+pub const fstEnumValueType_FST_EV_SV_INTEGER: fstEnumValueType = 0;
+pub const fstEnumValueType_FST_EV_SV_BIT: fstEnumValueType = 1;
+pub const fstEnumValueType_FST_EV_SV_LOGIC: fstEnumValueType = 2;
+pub const fstEnumValueType_FST_EV_SV_INT: fstEnumValueType = 3;
+pub const fstEnumValueType_FST_EV_SV_SHORTINT: fstEnumValueType = 4;
+pub const fstEnumValueType_FST_EV_SV_LONGINT: fstEnumValueType = 5;
+pub const fstEnumValueType_FST_EV_SV_BYTE: fstEnumValueType = 6;
+pub const fstEnumValueType_FST_EV_SV_UNSIGNED_INTEGER: fstEnumValueType = 7;
+pub const fstEnumValueType_FST_EV_SV_UNSIGNED_BIT: fstEnumValueType = 8;
+pub const fstEnumValueType_FST_EV_SV_UNSIGNED_LOGIC: fstEnumValueType = 9;
+pub const fstEnumValueType_FST_EV_SV_UNSIGNED_INT: fstEnumValueType = 10;
+pub const fstEnumValueType_FST_EV_SV_UNSIGNED_SHORTINT: fstEnumValueType = 11;
+pub const fstEnumValueType_FST_EV_SV_UNSIGNED_LONGINT: fstEnumValueType = 12;
+pub const fstEnumValueType_FST_EV_SV_UNSIGNED_BYTE: fstEnumValueType = 13;
+pub const fstEnumValueType_FST_EV_REG: fstEnumValueType = 14;
+pub const fstEnumValueType_FST_EV_TIME: fstEnumValueType = 15;
+pub const fstEnumValueType_FST_EV_MAX: fstEnumValueType = 15;
+
+pub type fstPackType = u32;
+pub const fstPackType_FST_PT_NONE: fstPackType = 0;
+pub const fstPackType_FST_PT_UNPACKED: fstPackType = 1;
+pub const fstPackType_FST_PT_PACKED: fstPackType = 2;
+pub const fstPackType_FST_PT_TAGGED_PACKED: fstPackType = 3;
+pub const fstPackType_FST_PT_MAX: fstPackType = 3;
+
+pub type fstSupplementalVarType = u32;
+pub const fstSupplementalVarType_FST_SVT_MIN: fstSupplementalVarType = 0;
+pub const fstSupplementalVarType_FST_SVT_NONE: fstSupplementalVarType = 0;
+pub const fstSupplementalVarType_FST_SVT_VHDL_SIGNAL: fstSupplementalVarType = 1;
+pub const fstSupplementalVarType_FST_SVT_VHDL_VARIABLE: fstSupplementalVarType = 2;
+pub const fstSupplementalVarType_FST_SVT_VHDL_CONSTANT: fstSupplementalVarType = 3;
+pub const fstSupplementalVarType_FST_SVT_VHDL_FILE: fstSupplementalVarType = 4;
+pub const fstSupplementalVarType_FST_SVT_VHDL_MEMORY: fstSupplementalVarType = 5;
+pub const fstSupplementalVarType_FST_SVT_MAX: fstSupplementalVarType = 5;
+
+pub type fstSupplementalDataType = u32;
+pub const fstSupplementalDataType_FST_SDT_MIN: fstSupplementalDataType = 0;
+pub const fstSupplementalDataType_FST_SDT_NONE: fstSupplementalDataType = 0;
+pub const fstSupplementalDataType_FST_SDT_VHDL_BOOLEAN: fstSupplementalDataType = 1;
+pub const fstSupplementalDataType_FST_SDT_VHDL_BIT: fstSupplementalDataType = 2;
+pub const fstSupplementalDataType_FST_SDT_VHDL_BIT_VECTOR: fstSupplementalDataType = 3;
+pub const fstSupplementalDataType_FST_SDT_VHDL_STD_ULOGIC: fstSupplementalDataType = 4;
+pub const fstSupplementalDataType_FST_SDT_VHDL_STD_ULOGIC_VECTOR: fstSupplementalDataType = 5;
+pub const fstSupplementalDataType_FST_SDT_VHDL_STD_LOGIC: fstSupplementalDataType = 6;
+pub const fstSupplementalDataType_FST_SDT_VHDL_STD_LOGIC_VECTOR: fstSupplementalDataType = 7;
+pub const fstSupplementalDataType_FST_SDT_VHDL_UNSIGNED: fstSupplementalDataType = 8;
+pub const fstSupplementalDataType_FST_SDT_VHDL_SIGNED: fstSupplementalDataType = 9;
+pub const fstSupplementalDataType_FST_SDT_VHDL_INTEGER: fstSupplementalDataType = 10;
+pub const fstSupplementalDataType_FST_SDT_VHDL_REAL: fstSupplementalDataType = 11;
+pub const fstSupplementalDataType_FST_SDT_VHDL_NATURAL: fstSupplementalDataType = 12;
+pub const fstSupplementalDataType_FST_SDT_VHDL_POSITIVE: fstSupplementalDataType = 13;
+pub const fstSupplementalDataType_FST_SDT_VHDL_TIME: fstSupplementalDataType = 14;
+pub const fstSupplementalDataType_FST_SDT_VHDL_CHARACTER: fstSupplementalDataType = 15;
+pub const fstSupplementalDataType_FST_SDT_VHDL_STRING: fstSupplementalDataType = 16;
+pub const fstSupplementalDataType_FST_SDT_MAX: fstSupplementalDataType = 16;
+pub const fstSupplementalDataType_FST_SDT_SVT_SHIFT_COUNT: fstSupplementalDataType = 10;
+pub const fstSupplementalDataType_FST_SDT_ABS_MAX: fstSupplementalDataType = 1023;
@@ -0,0 +1,711 @@
+//! Reader context of the native backend, which holds the header,
+//! hierarchy and geometry information of an FST waveform.
+
+use super::decode::{self, corrupt, u64_at, varint, varint32};
+use super::vcd;
+use crate::backend::{RawAttr, RawHier, RawScope, RawVar};
+use crate::consts::*;
+use crate::types::Handle;
+use memmap2::Mmap;
+use std::borrow::Cow;
+use std::ffi::{CStr, CString};
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Maximum length of names returned by hierarchy iteration.
+const ID_NAM_SIZ: usize = 512;
+/// Maximum length of names written to VCD.
+const ID_NAM_ATTR_SIZ: usize = 65536 + 4096;
+/// Length of the header block, including the section length.
+const HDR_LENGTH: usize = 329;
+/// Value for checking the endianness of doubles.
+const DOUBLE_ENDTEST: f64 = std::f64::consts::E;
+/// Tag of scopes in hierarchy.
+const ST_VCD_SCOPE: u8 = scope_type::VCD_SCOPE as u8;
+/// Tag of upscopes in hierarchy.
+const ST_VCD_UPSCOPE: u8 = scope_type::VCD_UPSCOPE as u8;
+/// Tag of attribute begins in hierarchy.
+const ST_GEN_ATTRBEGIN: u8 = scope_type::GEN_ATTRBEGIN as u8;
+/// Tag of attribute ends in hierarchy.
+const ST_GEN_ATTREND: u8 = scope_type::GEN_ATTREND as u8;
+
+/// Trait for sources of waveform data.
+pub trait Source: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> Source for T {}
+
+/// Header of an FST waveform.
+#[derive(Debug, Default)]
+pub struct Header {
+  pub start_time: u64,
+  pub end_time: u64,
+  pub double_endian_match: bool,
+  pub mem_used: u64,
+  pub scope_count: u64,
+  pub var_count: u64,
+  pub max_handle: u32,
+  pub num_alias: u64,
+  pub vc_section_count: u64,
+  pub timescale: i8,
+  pub version: CString,
+  pub date: CString,
+  pub file_type: u8,
+  pub timezero: i64,
+}
+
+/// An item in hierarchy data.
+pub enum HierItem<'a> {
+  Scope {
+    ty: u8,
+    name: &'a CStr,
+    component: &'a CStr,
+  },
+  Upscope,
+  AttrBegin {
+    ty: u8,
+    subtype: u8,
+    name: &'a CStr,
+    arg: u64,
+  },
+  AttrEnd,
+  Var {
+    ty: u8,
+    direction: u8,
+    name: &'a CStr,
+    length: u32,
+    alias: u32,
+  },
+}
+
+impl<'a> HierItem<'a> {
+  /// Parses the item at the given position of the hierarchy data,
+  /// returns the item and the position of the next item.
+  ///
+  /// Returns [`None`] if there is no more item.
+  pub fn parse(data: &'a [u8], pos: usize) -> Option<(Self, usize)> {
+    let mut pos = pos;
+    let tag = byte(data, &mut pos)?;
+    let item = match tag {
+      ST_VCD_SCOPE => {
+        let ty = byte(data, &mut pos)?;
+        let name = c_str(data, &mut pos)?;
+        let component = c_str(data, &mut pos)?;
+        Self::Scope {
+          ty,
+          name,
+          component,
+        }
+      }
+      ST_VCD_UPSCOPE => Self::Upscope,
+      ST_GEN_ATTRBEGIN => {
+        let ty = byte(data, &mut pos)?;
+        let subtype = byte(data, &mut pos)?;
+        let name = c_str(data, &mut pos)?;
+        let arg = var_int(data, &mut pos)?;
+        Self::AttrBegin {
+          ty,
+          subtype,
+          name,
+          arg,
+        }
+      }
+      ST_GEN_ATTREND => Self::AttrEnd,
+      ty if ty as u32 <= var_type::MAX => {
+        let direction = byte(data, &mut pos)?;
+        let name = c_str(data, &mut pos)?;
+        let length = var_int(data, &mut pos)? as u32;
+        let alias = var_int(data, &mut pos)? as u32;
+        Self::Var {
+          ty,
+          direction,
+          name,
+          length,
+          alias,
+        }
+      }
+      _ => return None,
+    };
+    Some((item, pos))
+  }
+}
+
+/// Reads a byte at the given position, and advances the position.
+fn byte(data: &[u8], pos: &mut usize) -> Option<u8> {
+  let b = *data.get(*pos)?;
+  *pos += 1;
+  Some(b)
+}
+
+/// Reads a variable-length integer at the given position,
+/// and advances the position.
+fn var_int(data: &[u8], pos: &mut usize) -> Option<u64> {
+  let (value, len) = varint(data.get(*pos..)?).ok()?;
+  *pos += len;
+  Some(value)
+}
+
+/// Reads a nul-terminated string at the given position,
+/// and advances the position to the byte after the nul.
+fn c_str<'a>(data: &'a [u8], pos: &mut usize) -> Option<&'a CStr> {
+  let s = CStr::from_bytes_until_nul(data.get(*pos..)?).ok()?;
+  *pos += s.count_bytes() + 1;
+  Some(s)
+}
+
+/// Reads the separate hierarchy file of the waveform of the given path,
+/// which is `<path>.hier`.
+fn read_hier_file(path: &Path) -> Option<Vec<u8>> {
+  let mut name = path.as_os_str().to_owned();
+  name.push(".hier");
+  std::fs::read(name).ok()
+}
+
+/// Bytes of a waveform shared by multiple contexts.
+#[derive(Clone)]
+struct SharedBytes(Arc<dyn AsRef<[u8]> + Send + Sync>);
+
+impl AsRef<[u8]> for SharedBytes {
+  fn as_ref(&self) -> &[u8] {
+    self.0.as_ref().as_ref()
+  }
+}
+
+/// Waveform data shared by multiple contexts, which is memory-mapped
+/// from the waveform file.
+pub struct SharedData {
+  bytes: Arc<dyn AsRef<[u8]> + Send + Sync>,
+  hier: Option<Vec<u8>>,
+}
+
+impl SharedData {
+  /// Memory-maps the waveform of the given path.
+  ///
+  /// Gzip-wrapped waveforms are decompressed into memory instead.
+  pub fn open(path: &Path) -> io::Result<Self> {
+    let file = File::open(path)?;
+    // SAFETY: the mapping is read-only, and the waveform file is not
+    // expected to be modified while being read.
+    let map = unsafe { Mmap::map(&file) }?;
+    let bytes: Arc<dyn AsRef<[u8]> + Send + Sync> = match unwrap_gzip(&map) {
+      Some(data) => Arc::new(data),
+      None => Arc::new(map),
+    };
+    Ok(Self {
+      bytes,
+      hier: read_hier_file(path),
+    })
+  }
+}
+
+/// Decompresses the given data if it is a gzip-wrapped waveform.
+///
+/// Returns [`None`] if the data is not wrapped or can not be decompressed.
+fn unwrap_gzip(data: &[u8]) -> Option<Vec<u8>> {
+  if data.first().copied()? as u32 != block_type::ZWRAPPER {
+    return None;
+  }
+  let uclen = u64_at(data, 9).ok()? as usize;
+  decode::gzip(data.get(17..)?, uclen).ok()
+}
+
+/// Reader context.
+pub struct Context {
+  source: Box<dyn Source>,
+  pub header: Header,
+  /// Block type and position of the hierarchy section.
+  hier_section: Option<(u8, u64)>,
+  /// Decompressed hierarchy data.
+  hier: Option<Vec<u8>>,
+  pub signal_lens: Vec<u32>,
+  pub signal_typs: Vec<u8>,
+  pub process_mask: Vec<bool>,
+  pub limit_range: Option<(u64, u64)>,
+  pub native_doubles: bool,
+  pub blackouts: Vec<(u64, bool)>,
+  pub failed: bool,
+  pub(super) rvat: Option<super::blocks::RvatBlock>,
+}
+
+impl Context {
+  /// Opens the waveform of the given path.
+  ///
+  /// Returns [`None`] if the file is not a valid FST waveform.
+  pub fn open(path: &Path) -> Option<Self> {
+    let file = File::open(path).ok()?;
+    Self::new(Box::new(BufReader::new(file)), read_hier_file(path))
+  }
+
+  /// Opens the given shared waveform data.
+  ///
+  /// Returns [`None`] if the data is not a valid FST waveform.
+  pub fn open_shared(data: &SharedData) -> Option<Self> {
+    let source = Cursor::new(SharedBytes(data.bytes.clone()));
+    Self::new(Box::new(source), data.hier.clone())
+  }
+
+  /// Creates a new context from the given source and hierarchy data.
+  fn new(source: Box<dyn Source>, hier: Option<Vec<u8>>) -> Option<Self> {
+    let mut ctx = Self {
+      source,
+      header: Header::default(),
+      hier_section: None,
+      hier,
+      signal_lens: Vec::new(),
+      signal_typs: Vec::new(),
+      process_mask: Vec::new(),
+      limit_range: None,
+      native_doubles: false,
+      blackouts: Vec::new(),
+      failed: false,
+      rvat: None,
+    };
+    let hdr_seen = ctx.init().ok()?;
+    let h = &ctx.header;
+    let valid = hdr_seen
+      && h.vc_section_count != 0
+      && h.max_handle != 0
+      && (ctx.hier.is_some() || ctx.hier_section.is_some());
+    valid.then_some(ctx)
+  }
+
+  /// Reads `len` bytes at the given position of the source.
+  pub fn read_at(&mut self, pos: u64, len: usize) -> io::Result<Vec<u8>> {
+    let mut buf = vec![0; len];
+    let ret = self
+      .source
+      .seek(SeekFrom::Start(pos))
+      .and_then(|_| self.source.read_exact(&mut buf));
+    if ret.is_err() {
+      self.failed = true;
+    }
+    ret.map(|_| buf)
+  }
+
+  /// Reads the block type and section length at the given position.
+  ///
+  /// Returns [`None`] if the end of file is reached.
+  pub fn read_block_header(&mut self, pos: u64) -> Option<(u8, u64)> {
+    let mut buf = [0; 9];
+    self.source.seek(SeekFrom::Start(pos)).ok()?;
+    let len = read_full(&mut self.source, &mut buf).ok()?;
+    match len {
+      0 => None,
+      9 => Some((buf[0], u64::from_be_bytes(buf[1..].try_into().unwrap()))),
+      _ => Some((buf[0], 0)),
+    }
+  }
+
+  /// Reads all blocks except value change blocks,
+  /// returns `true` if the header has been read.
+  fn init(&mut self) -> io::Result<bool> {
+    // Unwrap the gzip-wrapped waveform.
+    if let Some((ty, seclen)) = self.read_block_header(0)
+      && ty as u32 == block_type::ZWRAPPER
+    {
+      if seclen == 0 {
+        return Ok(false);
+      }
+      let uclen = u64_at(&self.read_at(9, 8)?, 0)? as usize;
+      self.source.seek(SeekFrom::Start(17))?;
+      match decode::gzip(&mut self.source, uclen) {
+        Ok(data) => self.source = Box::new(Cursor::new(data)),
+        Err(_) => return Ok(false),
+      }
+    }
+
+    let end = self.source.seek(SeekFrom::End(0))?;
+    let mut pos = 0;
+    let mut hdr_seen = false;
+    let mut hdr_incomplete = false;
+    let mut has_geom = false;
+    let mut vc_section_count = 0;
+    while pos < end {
+      let Some((ty, seclen)) = self.read_block_header(pos) else {
+        break;
+      };
+      if (hdr_incomplete && seclen == 0) || (!hdr_seen && ty as u32 != block_type::HDR) {
+        break;
+      }
+      pos += 1;
+      match ty as u32 {
+        block_type::HDR if !hdr_seen => {
+          let data = self.read_at(pos, HDR_LENGTH)?;
+          if !self.read_header(&data)? {
+            break;
+          }
+          hdr_seen = true;
+          hdr_incomplete = self.header.start_time == 0 && self.header.end_time == 0;
+        }
+        block_type::VCDATA | block_type::VCDATA_DYN_ALIAS | block_type::VCDATA_DYN_ALIAS2 => {
+          if hdr_incomplete {
+            let data = self.read_at(pos + 8, 16)?;
+            if vc_section_count == 0 {
+              self.header.start_time = u64_at(&data, 0)?;
+            }
+            self.header.end_time = u64_at(&data, 8)?;
+          }
+          vc_section_count += 1;
+        }
+        block_type::GEOM if !hdr_incomplete => {
+          let data = self.read_at(pos, seclen as usize)?;
+          self.read_geom(&data)?;
+          has_geom = true;
+        }
+        block_type::HIER | block_type::HIER_LZ4 | block_type::HIER_LZ4DUO => {
+          self.hier_section = Some((ty, pos))
+        }
+        block_type::BLACKOUT => {
+          let data = self.read_at(pos + 8, seclen.saturating_sub(8) as usize)?;
+          self.read_blackouts(&data)?;
+        }
+        _ => {}
+      }
+      pos += seclen;
+    }
+
+    if hdr_seen {
+      self.header.vc_section_count = vc_section_count;
+      if !has_geom {
+        // Recreate lengths and types of signals from the hierarchy.
+        self.process_hier(None)?;
+      }
+    }
+    Ok(hdr_seen)
+  }
+
+  /// Reads the header block, returns `false` if the header is invalid.
+  fn read_header(&mut self, data: &[u8]) -> io::Result<bool> {
+    let h = &mut self.header;
+    h.start_time = u64_at(data, 8)?;
+    h.end_time = u64_at(data, 16)?;
+    let endtest: [u8; 8] = data[24..32].try_into().unwrap();
+    h.double_endian_match = f64::from_ne_bytes(endtest) == DOUBLE_ENDTEST;
+    if !h.double_endian_match {
+      let mut reversed = endtest;
+      reversed.reverse();
+      if f64::from_ne_bytes(reversed) != DOUBLE_ENDTEST {
+        return Ok(false);
+      }
+    }
+    h.mem_used = u64_at(data, 32)?;
+    h.scope_count = u64_at(data, 40)?;
+    h.var_count = u64_at(data, 48)?;
+    let max_handle = u64_at(data, 56)?;
+    h.max_handle = max_handle as u32;
+    h.num_alias = h.var_count.wrapping_sub(max_handle);
+    h.vc_section_count = u64_at(data, 64)?;
+    h.timescale = data[72] as i8;
+    h.version = to_cstring(&data[73..201]);
+    h.date = to_cstring(&data[201..320]);
+    h.file_type = data[320];
+    h.timezero = u64_at(data, 321)? as i64;
+    Ok(true)
+  }
+
+  /// Reads the geometry block, which contains lengths of all signals.
+  fn read_geom(&mut self, data: &[u8]) -> io::Result<()> {
+    let uclen = u64_at(data, 8)? as usize;
+    let max_handle = u64_at(data, 16)? as u32;
+    let cdata = data.get(24..).ok_or_else(corrupt)?;
+    let geom = if cdata.len() != uclen {
+      decode::zlib(cdata, uclen)?
+    } else {
+      cdata.to_vec()
+    };
+    self.header.max_handle = max_handle;
+    self.process_mask = vec![false; max_handle as usize];
+    self.signal_lens = Vec::with_capacity(max_handle as usize);
+    self.signal_typs = Vec::with_capacity(max_handle as usize);
+    let mut pos = 0;
+    for _ in 0..max_handle {
+      let (val, len) = varint32(&geom[pos..])?;
+      pos += len;
+      let (len, ty) = match val {
+        0 => (8, var_type::VCD_REAL),
+        0xffffffff => (0, var_type::VCD_WIRE),
+        v => (v, var_type::VCD_WIRE),
+      };
+      self.signal_lens.push(len);
+      self.signal_typs.push(ty as u8);
+    }
+    Ok(())
+  }
+
+  /// Reads the blackout block, which contains dump activity changes.
+  fn read_blackouts(&mut self, data: &[u8]) -> io::Result<()> {
+    let (count, mut pos) = varint32(data)?;
+    let mut time = 0u64;
+    self.blackouts.clear();
+    for _ in 0..count {
+      let active = *data.get(pos).ok_or_else(corrupt)? != 0;
+      let (delta, len) = varint(&data[pos + 1..])?;
+      pos += 1 + len;
+      time = time.wrapping_add(delta);
+      self.blackouts.push((time, active));
+    }
+    Ok(())
+  }
+
+  /// Loads and decompresses the hierarchy data,
+  /// returns `false` on failure.
+  pub fn load_hier(&mut self) -> bool {
+    if self.hier.is_some() {
+      return true;
+    }
+    let Some((ty, pos)) = self.hier_section else {
+      return false;
+    };
+    let ret = (|| {
+      let head = self.read_at(pos, 16)?;
+      let seclen = u64_at(&head, 0)?;
+      let uclen = u64_at(&head, 8)? as usize;
+      let cdata = self.read_at(pos + 16, seclen.saturating_sub(16) as usize)?;
+      match ty as u32 {
+        block_type::HIER => decode::gzip(&cdata[..], uclen),
+        block_type::HIER_LZ4 => decode::lz4(&cdata, uclen),
+        _ => {
+          let (uclen2, len) = varint(&cdata)?;
+          let data = decode::lz4(&cdata[len..], uclen2 as usize)?;
+          decode::lz4(&data, uclen)
+        }
+      }
+    })();
+    match ret {
+      Ok(hier) => {
+        self.hier = Some(hier);
+        true
+      }
+      Err(_) => false,
+    }
+  }
+
+  /// Returns an iterator over the hierarchy, which is empty
+  /// if the hierarchy can not be loaded.
+  pub fn hiers(&mut self) -> Hiers<'_> {
+    let data = if self.load_hier() {
+      self.hier.as_deref().unwrap()
+    } else {
+      &[]
+    };
+    Hiers {
+      data,
+      pos: 0,
+      current_handle: 0,
+      arg_from_name: 0,
+    }
+  }
+
+  /// Processes the whole hierarchy, recreates lengths and types of signals,
+  /// and writes VCD header to the given writer if provided.
+  ///
+  /// Process masks of all signals are cleared.
+  pub fn process_hier(&mut self, mut vcd: Option<&mut dyn Write>) -> io::Result<()> {
+    if !self.load_hier() {
+      return Err(corrupt());
+    }
+    let hier = self.hier.as_deref().unwrap();
+    if let Some(w) = vcd.as_mut() {
+      vcd::write_header(*w, &self.header)?;
+    }
+    let mut signal_lens = Vec::new();
+    let mut signal_typs = Vec::new();
+    let mut num_alias = 0;
+    let mut pos = 0;
+    while let Some((item, next)) = HierItem::parse(hier, pos) {
+      pos = next;
+      match item {
+        HierItem::Scope { ty, name, .. } => {
+          if let Some(w) = vcd.as_mut() {
+            vcd::write_scope(*w, ty, trunc(name.to_bytes(), ID_NAM_ATTR_SIZ))?;
+          }
+        }
+        HierItem::Upscope => {
+          if let Some(w) = vcd.as_mut() {
+            vcd::write_upscope(*w)?;
+          }
+        }
+        HierItem::Var {
+          ty,
+          name,
+          length,
+          alias,
+          ..
+        } => {
+          let is_real = matches!(
+            ty as u32,
+            var_type::VCD_REAL
+              | var_type::VCD_REAL_PARAMETER
+              | var_type::VCD_REALTIME
+              | var_type::SV_SHORTREAL
+          );
+          let mut len = length;
+          let handle = if alias == 0 {
+            signal_lens.push(length);
+            signal_typs.push(if is_real {
+              var_type::VCD_REAL as u8
+            } else {
+              ty
+            });
+            signal_lens.len() as u32
+          } else {
+            num_alias += 1;
+            alias
+          };
+          if is_real {
+            len = if ty as u32 == var_type::SV_SHORTREAL {
+              32
+            } else {
+              64
+            };
+          }
+          if let Some(w) = vcd.as_mut() {
+            let name = trunc(name.to_bytes(), ID_NAM_ATTR_SIZ);
+            vcd::write_var(*w, ty, len, handle, name)?;
+          }
+        }
+        HierItem::AttrBegin { .. } | HierItem::AttrEnd => {}
+      }
+    }
+    if let Some(w) = vcd.as_mut() {
+      vcd::write_enddefinitions(*w)?;
+    }
+    let h = &mut self.header;
+    h.max_handle = signal_lens.len() as u32;
+    h.num_alias = num_alias;
+    h.var_count = h.max_handle as u64 + num_alias;
+    self.process_mask = vec![false; signal_lens.len()];
+    self.signal_lens = signal_lens;
+    self.signal_typs = signal_typs;
+    Ok(())
+  }
+
+  /// Decodes a double from the given bytes in the waveform.
+  pub fn double(&self, bytes: &[u8]) -> f64 {
+    let mut bytes: [u8; 8] = bytes[..8].try_into().unwrap();
+    if !self.header.double_endian_match {
+      bytes.reverse();
+    }
+    f64::from_ne_bytes(bytes)
+  }
+}
+
+/// Reads as many bytes as possible to fill the given buffer,
+/// returns the number of bytes read.
+fn read_full(source: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+  let mut len = 0;
+  while len < buf.len() {
+    match source.read(&mut buf[len..]) {
+      Ok(0) => break,
+      Ok(n) => len += n,
+      Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+      Err(e) => return Err(e),
+    }
+  }
+  Ok(len)
+}
+
+/// Converts the given nul-padded bytes to [`CString`].
+fn to_cstring(bytes: &[u8]) -> CString {
+  let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+  CString::new(&bytes[..len]).unwrap()
+}
+
+/// Truncates the given name to `len` + 1 bytes.
+fn trunc(name: &[u8], len: usize) -> &[u8] {
+  &name[..name.len().min(len + 1)]
+}
+
+/// Truncates the given name to the length limit of hierarchy iteration.
+fn hier_name(name: &CStr) -> Cow<'_, CStr> {
+  let bytes = name.to_bytes();
+  if bytes.len() <= ID_NAM_SIZ + 1 {
+    Cow::Borrowed(name)
+  } else {
+    Cow::Owned(CString::new(trunc(bytes, ID_NAM_SIZ)).unwrap())
+  }
+}
+
+/// An iterator over the hierarchy, yields the same items
+/// as the hierarchy iteration of the C library.
+#[derive(Debug)]
+pub struct Hiers<'a> {
+  data: &'a [u8],
+  pos: usize,
+  /// Handle of the last non-alias variable.
+  current_handle: u32,
+  /// Argument generated by the name of the last source stem attribute.
+  arg_from_name: u64,
+}
+
+impl<'a> Iterator for Hiers<'a> {
+  type Item = RawHier<'a>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let (item, next) = HierItem::parse(self.data, self.pos)?;
+    self.pos = next;
+    let hier = match item {
+      HierItem::Scope {
+        ty,
+        name,
+        component,
+      } => RawHier::Scope(RawScope {
+        ty,
+        name: hier_name(name),
+        component: hier_name(component),
+      }),
+      HierItem::Upscope => RawHier::Upscope,
+      HierItem::AttrBegin {
+        ty,
+        subtype,
+        name,
+        arg,
+      } => {
+        // Keep the argument generated by the name of the previous attribute
+        // if the name is not overloaded, which is the same as the C API.
+        if ty as u32 == attr_type::MISC
+          && (subtype as u32 == misc_type::SOURCESTEM || subtype as u32 == misc_type::SOURCEISTEM)
+        {
+          self.arg_from_name = varint(name.to_bytes()).map_or(0, |(v, _)| v);
+        }
+        RawHier::AttrBegin(RawAttr {
+          ty,
+          subtype,
+          name: hier_name(name),
+          arg,
+          arg_from_name: self.arg_from_name,
+        })
+      }
+      HierItem::AttrEnd => RawHier::AttrEnd,
+      HierItem::Var {
+        ty,
+        direction,
+        name,
+        length,
+        alias,
+      } => {
+        let length = if ty as u32 == var_type::VCD_PORT {
+          length.wrapping_sub(2) / 3
+        } else {
+          length
+        };
+        let handle = if alias == 0 {
+          self.current_handle += 1;
+          self.current_handle
+        } else {
+          alias
+        };
+        RawHier::Var(RawVar {
+          ty,
+          direction,
+          name: hier_name(name),
+          length,
+          handle: Handle::new(handle)?,
+          is_alias: alias != 0,
+        })
+      }
+    };
+    Some(hier)
+  }
+}
@@ -0,0 +1,181 @@
+//! Decoding helpers of the native backend, including variable-length
+//! integers and decompressors.
+
+use flate2::read::{GzDecoder, ZlibDecoder};
+use std::io::{self, Read};
+
+/// Returns an error indicating the waveform is corrupt.
+pub fn corrupt() -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, "corrupt FST waveform")
+}
+
+/// Reads a big-endian `u64` from the given bytes.
+pub fn u64_at(data: &[u8], pos: usize) -> io::Result<u64> {
+  let bytes = data.get(pos..pos + 8).ok_or_else(corrupt)?;
+  Ok(u64::from_be_bytes(bytes.try_into().unwrap()))
+}
+
+/// Reads an unsigned variable-length integer from the given bytes,
+/// returns the integer and its length in bytes.
+pub fn varint(data: &[u8]) -> io::Result<(u64, usize)> {
+  let mut value = 0;
+  for (i, b) in data.iter().enumerate().take(10) {
+    value |= ((b & 0x7f) as u64) << (i * 7);
+    if b & 0x80 == 0 {
+      return Ok((value, i + 1));
+    }
+  }
+  Err(corrupt())
+}
+
+/// Reads a 32-bit unsigned variable-length integer from the given bytes,
+/// returns the integer and its length in bytes.
+pub fn varint32(data: &[u8]) -> io::Result<(u32, usize)> {
+  varint(data).map(|(v, len)| (v as u32, len))
+}
+
+/// Reads a signed variable-length integer from the given bytes,
+/// returns the integer and its length in bytes.
+pub fn svarint(data: &[u8]) -> io::Result<(i64, usize)> {
+  let mut value = 0i64;
+  let mut shift = 0;
+  for (i, b) in data.iter().enumerate().take(10) {
+    value |= ((b & 0x7f) as i64) << shift;
+    shift += 7;
+    if b & 0x80 == 0 {
+      if shift < 64 && b & 0x40 != 0 {
+        // Sign extend.
+        value |= -1 << shift;
+      }
+      return Ok((value, i + 1));
+    }
+  }
+  Err(corrupt())
+}
+
+/// Maximum compression ratio of deflate streams.
+const MAX_DEFLATE_RATIO: usize = 1032;
+
+/// Maximum compression ratio of LZ4 blocks.
+const MAX_LZ4_RATIO: usize = 255;
+
+/// Checks if `len` bytes can be decompressed from `clen` bytes
+/// with the given maximum compression ratio.
+fn check_ratio(clen: usize, len: usize, max_ratio: usize) -> io::Result<()> {
+  if len <= clen.saturating_mul(max_ratio) {
+    Ok(())
+  } else {
+    Err(corrupt())
+  }
+}
+
+/// Returns an empty buffer with capacity for `len` bytes,
+/// returns an error if the allocation fails.
+fn buffer(len: usize) -> io::Result<Vec<u8>> {
+  let mut buf = Vec::new();
+  buf.try_reserve_exact(len).map_err(|_| corrupt())?;
+  Ok(buf)
+}
+
+/// Decompresses the given zlib stream, which should be decompressed
+/// to exactly `len` bytes.
+pub fn zlib(data: &[u8], len: usize) -> io::Result<Vec<u8>> {
+  check_ratio(data.len(), len, MAX_DEFLATE_RATIO)?;
+  let mut buf = buffer(len)?;
+  ZlibDecoder::new(data).read_to_end(&mut buf)?;
+  check_len(buf, len)
+}
+
+/// Decompresses the given gzip stream, which should be decompressed
+/// to exactly `len` bytes.
+pub fn gzip(data: impl Read, len: usize) -> io::Result<Vec<u8>> {
+  let mut buf = buffer(len)?;
+  GzDecoder::new(data)
+    .take(len as u64)
+    .read_to_end(&mut buf)?;
+  check_len(buf, len)
+}
+
+/// Decompresses the given LZ4 block, which should be decompressed
+/// to exactly `len` bytes.
+pub fn lz4(data: &[u8], len: usize) -> io::Result<Vec<u8>> {
+  check_ratio(data.len(), len, MAX_LZ4_RATIO)?;
+  let mut buf = buffer(len)?;
+  buf.resize(len, 0);
+  let n = lz4_flex::block::decompress_into(data, &mut buf).map_err(|_| corrupt())?;
+  buf.truncate(n);
+  check_len(buf, len)
+}
+
+/// Decompresses the given FastLZ block, which should be decompressed
+/// to exactly `len` bytes.
+///
+/// Both level 1 and level 2 blocks are supported.
+pub fn fastlz(data: &[u8], len: usize) -> io::Result<Vec<u8>> {
+  const MAX_L2_DISTANCE: usize = 8191;
+
+  let level2 = match data.first().map(|b| (b >> 5) + 1) {
+    Some(1) => false,
+    Some(2) => true,
+    _ => return Err(corrupt()),
+  };
+  let mut buf = buffer(len)?;
+  let mut ip = 1;
+  let mut ctrl = (data[0] & 31) as usize;
+  loop {
+    if ctrl >= 32 {
+      // Back reference.
+      let mut count = (ctrl >> 5) - 1;
+      let mut ofs = (ctrl & 31) << 8;
+      let next = |ip: &mut usize| {
+        let b = *data.get(*ip).ok_or_else(corrupt)?;
+        *ip += 1;
+        Ok::<_, io::Error>(b as usize)
+      };
+      if count == 7 - 1 {
+        if level2 {
+          loop {
+            let code = next(&mut ip)?;
+            count += code;
+            if code != 255 {
+              break;
+            }
+          }
+        } else {
+          count += next(&mut ip)?;
+        }
+      }
+      let code = next(&mut ip)?;
+      ofs += code;
+      if level2 && code == 255 && ofs == (31 << 8) + 255 {
+        // Match from 16-bit distance.
+        ofs = (next(&mut ip)? << 8) + next(&mut ip)? + MAX_L2_DISTANCE;
+      }
+      let start = buf.len().checked_sub(ofs + 1).ok_or_else(corrupt)?;
+      for i in 0..count + 3 {
+        buf.push(buf[start + i]);
+      }
+    } else {
+      // Literal run.
+      let run = data.get(ip..ip + ctrl + 1).ok_or_else(corrupt)?;
+      buf.extend_from_slice(run);
+      ip += ctrl + 1;
+    }
+    if buf.len() >= len || ip >= data.len() {
+      break;
+    }
+    ctrl = data[ip] as usize;
+    ip += 1;
+  }
+  buf.truncate(len);
+  check_len(buf, len)
+}
+
+/// Checks if the length of the given buffer is `len`.
+fn check_len(buf: Vec<u8>, len: usize) -> io::Result<Vec<u8>> {
+  if buf.len() == len {
+    Ok(buf)
+  } else {
+    Err(corrupt())
+  }
+}
@@ -0,0 +1,304 @@
+//! Native backend of the reader, implemented in pure Rust.
+//!
+//! The backend decodes waveforms the same way as the C library,
+//! so the reader behaves the same on top of either backend.
+
+mod blocks;
+mod consts;
+mod context;
+mod decode;
+#[cfg(all(test, feature = "capi"))]
+mod tests;
+mod vcd;
+
+pub use consts::*;
+pub use context::SharedData;
+
+use crate::Result;
+use crate::backend::{Backend, BlockCallback, RawEnumTable};
+use crate::consts::FileType;
+use crate::types::Handle;
+use context::{Context, Hiers};
+use std::ffi::CStr;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Backend implemented in pure Rust.
+pub struct NativeBackend {
+  ctx: Context,
+}
+
+impl NativeBackend {
+  /// Opens a backend on the given shared waveform data.
+  ///
+  /// Returns [`None`] if the data is not a valid FST waveform.
+  pub fn open_shared(data: &SharedData) -> Option<Self> {
+    Context::open_shared(data).map(|ctx| Self { ctx })
+  }
+}
+
+impl fmt::Debug for NativeBackend {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("NativeBackend").finish_non_exhaustive()
+  }
+}
+
+impl Backend for NativeBackend {
+  type Hiers<'a> = Hiers<'a>;
+
+  fn open(path: &Path) -> Option<Self> {
+    Context::open(path).map(|ctx| Self { ctx })
+  }
+
+  fn date(&self) -> &CStr {
+    &self.ctx.header.date
+  }
+
+  fn version(&self) -> &CStr {
+    &self.ctx.header.version
+  }
+
+  fn start_time(&self) -> u64 {
+    self.ctx.header.start_time
+  }
+
+  fn end_time(&self) -> u64 {
+    self.ctx.header.end_time
+  }
+
+  fn file_type(&self) -> FileType {
+    self.ctx.header.file_type as FileType
+  }
+
+  fn alias_count(&self) -> u64 {
+    self.ctx.header.num_alias
+  }
+
+  fn scope_count(&self) -> u64 {
+    self.ctx.header.scope_count
+  }
+
+  fn var_count(&self) -> u64 {
+    self.ctx.header.var_count
+  }
+
+  fn timescale(&self) -> i32 {
+    self.ctx.header.timescale as i32
+  }
+
+  fn timezero(&self) -> i64 {
+    self.ctx.header.timezero
+  }
+
+  fn max_handle(&self) -> u32 {
+    self.ctx.header.max_handle
+  }
+
+  fn value_change_block_count(&self) -> u64 {
+    self.ctx.header.vc_section_count
+  }
+
+  fn double_endian_match(&self) -> bool {
+    self.ctx.header.double_endian_match
+  }
+
+  fn memory_used_by_writer(&self) -> u64 {
+    self.ctx.header.mem_used
+  }
+
+  fn seek_failed(&self) -> bool {
+    self.ctx.failed
+  }
+
+  fn mask(&self, handle: Handle) -> bool {
+    let idx = u32::from(handle) as usize - 1;
+    self.ctx.process_mask.get(idx).is_some_and(|m| *m)
+  }
+
+  fn set_mask(&mut self, handle: Handle, enable: bool) {
+    let idx = u32::from(handle) as usize - 1;
+    if let Some(m) = self.ctx.process_mask.get_mut(idx) {
+      *m = enable;
+    }
+  }
+
+  fn set_mask_all(&mut self, enable: bool) {
+    self.ctx.process_mask.fill(enable);
+  }
+
+  fn set_time_range_limit(&mut self, range: Option<(u64, u64)>) {
+    self.ctx.limit_range = range;
+  }
+
+  fn set_native_doubles(&mut self, enable: bool) {
+    self.ctx.native_doubles = enable;
+  }
+
+  fn dump_activity_count(&self) -> u32 {
+    self.ctx.blackouts.len() as u32
+  }
+
+  fn dump_activity_change(&self, index: u32) -> (u64, bool) {
+    let blackouts = &self.ctx.blackouts;
+    blackouts.get(index as usize).copied().unwrap_or_default()
+  }
+
+  fn hiers(&mut self) -> Hiers<'_> {
+    self.ctx.hiers()
+  }
+
+  fn iter_blocks(&mut self, callback: &mut BlockCallback) -> Result<()> {
+    let out = blocks::Output::Callback {
+      callback,
+      stopped: false,
+    };
+    blocks::iter_blocks(&mut self.ctx, out).map_err(|_| self.last_error())
+  }
+
+  fn value_at(&mut self, time: u64, handle: Handle, _len: usize, buf: &mut Vec<u8>) -> Result<()> {
+    buf.clear();
+    match blocks::value_at(&mut self.ctx, time, handle.into()) {
+      Ok(Some(value)) => {
+        buf.extend_from_slice(&value);
+        Ok(())
+      }
+      _ => Err(self.last_error()),
+    }
+  }
+
+  fn dump_to_vcd(&mut self, path: Option<&Path>) -> Result<()> {
+    let ret = match path {
+      Some(path) => {
+        File::create(path).and_then(|f| dump_to_vcd(&mut self.ctx, &mut BufWriter::new(f)))
+      }
+      None => dump_to_vcd(&mut self.ctx, &mut BufWriter::new(io::stdout().lock())),
+    };
+    ret.map_err(|_| self.last_error())
+  }
+
+  fn parse_enum_table(s: &CStr) -> Option<RawEnumTable> {
+    let (name, rest) = split_once(s.to_bytes())?;
+    let count = atoi(rest);
+    let num_spaces = rest.iter().filter(|b| **b == b' ').count();
+    if num_spaces as i64 != 2 * count as i64 {
+      return None;
+    }
+    let mut items = rest.split(|b| *b == b' ').skip(1);
+    let mut next = || unescape(items.next().unwrap_or_default());
+    let literals: Vec<_> = (0..count).map(|_| next()).collect();
+    let values: Vec<_> = (0..count).map(|_| next()).collect();
+    Some((name.into(), literals.into_iter().zip(values).collect()))
+  }
+}
+
+/// Dumps the waveform as VCD to the given writer.
+fn dump_to_vcd(ctx: &mut Context, w: &mut dyn Write) -> io::Result<()> {
+  ctx.process_hier(Some(&mut *w))?;
+  let blackouts = ctx.blackouts.clone();
+  let out = blocks::Output::Vcd(vcd::VcdWriter::new(w, &blackouts));
+  blocks::iter_blocks(ctx, out)
+}
+
+/// Splits the given bytes at the first space.
+fn split_once(s: &[u8]) -> Option<(&[u8], &[u8])> {
+  let pos = s.iter().position(|b| *b == b' ')?;
+  Some((&s[..pos], &s[pos + 1..]))
+}
+
+/// Parses the leading integer of the given bytes, same as `atoi`.
+fn atoi(s: &[u8]) -> i32 {
+  let s = s.trim_ascii_start();
+  let (neg, s) = match s.first() {
+    Some(b'-') => (true, &s[1..]),
+    Some(b'+') => (false, &s[1..]),
+    _ => (false, s),
+  };
+  let value = s
+    .iter()
+    .take_while(|b| b.is_ascii_digit())
+    .fold(0i32, |v, b| {
+      v.wrapping_mul(10).wrapping_add((b - b'0') as i32)
+    });
+  if neg { value.wrapping_neg() } else { value }
+}
+
+/// Unescapes the given bytes, same as `fstUtilityEscToBin`.
+fn unescape(s: &[u8]) -> Vec<u8> {
+  let mut buf = Vec::with_capacity(s.len());
+  let mut iter = s.iter().copied();
+  let mut next = || iter.next().unwrap_or(0);
+  loop {
+    let c = match next() {
+      0 => break,
+      b'\\' => match next() {
+        b'a' => 0x07,
+        b'b' => 0x08,
+        b'f' => 0x0c,
+        b'n' => b'\n',
+        b'r' => b'\r',
+        b't' => b'\t',
+        b'v' => 0x0b,
+        b'x' => {
+          let hex = |c: u8| match c.to_ascii_uppercase() {
+            c @ b'A'..=b'F' => c - b'A' + 10,
+            c => c.wrapping_sub(b'0'),
+          };
+          let hi = hex(next());
+          hi.wrapping_mul(16).wrapping_add(hex(next()))
+        }
+        c @ b'0'..=b'7' => {
+          let (c1, c2) = (next(), next());
+          (c - b'0')
+            .wrapping_mul(64)
+            .wrapping_add(c1.wrapping_sub(b'0').wrapping_mul(8))
+            .wrapping_add(c2.wrapping_sub(b'0'))
+        }
+        0 => break,
+        c => c,
+      },
+      c => c,
+    };
+    buf.push(c);
+  }
+  // Strings are truncated at nul, which is the same as the C API.
+  if let Some(pos) = buf.iter().position(|b| *b == 0) {
+    buf.truncate(pos);
+  }
+  buf
+}
+
+/// Formats the given double in `%.16g` format of C.
+pub fn format_g(d: f64) -> String {
+  let sign = if d.is_sign_negative() { "-" } else { "" };
+  if d.is_nan() {
+    return format!("{sign}nan");
+  }
+  if d.is_infinite() {
+    return format!("{sign}inf");
+  }
+  if d == 0.0 {
+    return format!("{sign}0");
+  }
+  const PRECISION: i32 = 16;
+  let sci = format!("{:.*e}", PRECISION as usize - 1, d);
+  let (mantissa, exp) = sci.split_once('e').unwrap();
+  let exp: i32 = exp.parse().unwrap();
+  if (-4..PRECISION).contains(&exp) {
+    let fixed = format!("{:.*}", (PRECISION - 1 - exp) as usize, d);
+    strip_zeros(&fixed).into()
+  } else {
+    let exp_sign = if exp < 0 { '-' } else { '+' };
+    format!("{}e{exp_sign}{:02}", strip_zeros(mantissa), exp.abs())
+  }
+}
+
+/// Strips trailing zeros after the decimal point.
+fn strip_zeros(s: &str) -> &str {
+  if s.contains('.') {
+    s.trim_end_matches('0').trim_end_matches('.')
+  } else {
+    s
+  }
+}
@@ -0,0 +1,338 @@
+//! Tests comparing the native backend with the C library,
+//! by decoding the same waveforms with both backends.
+
+use super::NativeBackend;
+use crate::backend::capi::CBackend;
+use crate::backend::{Backend, RawHier};
+use crate::consts::*;
+use crate::{Supplemental, Writer};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+/// A temporary file, which is removed when dropped.
+struct TempFile(PathBuf);
+
+impl TempFile {
+  fn new(name: &str) -> Self {
+    let file_name = format!("fstapi-native-{}-{name}", process::id());
+    Self(env::temp_dir().join(file_name))
+  }
+
+  fn path(&self) -> &Path {
+    &self.0
+  }
+}
+
+impl Drop for TempFile {
+  fn drop(&mut self) {
+    let _ = fs::remove_file(&self.0);
+    // The hierarchy is written to a separate file if it is not compressed.
+    let mut hier = self.0.clone().into_os_string();
+    hier.push(".hier");
+    let _ = fs::remove_file(hier);
+  }
+}
+
+/// A value change, with time, handle, value and `true` if the
+/// variable is variable-length.
+type Change = (u64, u32, Vec<u8>, bool);
+
+/// Everything decoded from a waveform by one backend.
+struct Decoded {
+  header: Vec<i128>,
+  hiers: Vec<String>,
+  /// Handles, lengths and types of variables.
+  vars: Vec<(u32, u32, u32)>,
+  changes: Vec<Change>,
+  limited_changes: Vec<Change>,
+  vcd: String,
+}
+
+/// Collects all value changes decoded by the given backend.
+fn changes<B: Backend>(backend: &mut B) -> Vec<Change> {
+  let mut changes = Vec::new();
+  backend
+    .iter_blocks(&mut |time, handle, value, var_len| {
+      changes.push((time, handle.into(), value.into(), var_len));
+      ControlFlow::Continue(())
+    })
+    .unwrap();
+  changes
+}
+
+/// Decodes the waveform of the given path by the given backend.
+fn decode<B: Backend>(file: &TempFile, (start, end): (u64, u64), name: &str) -> Decoded {
+  let mut backend = B::open(file.path()).unwrap();
+  let header = vec![
+    backend.start_time() as i128,
+    backend.end_time() as i128,
+    backend.file_type() as i128,
+    backend.alias_count() as i128,
+    backend.scope_count() as i128,
+    backend.var_count() as i128,
+    backend.timescale() as i128,
+    backend.timezero() as i128,
+    backend.max_handle() as i128,
+    backend.value_change_block_count() as i128,
+    backend.dump_activity_count() as i128,
+  ];
+  let mut hiers = vec![format!("{:?} {:?}", backend.version(), backend.date())];
+  let mut vars = Vec::new();
+  for hier in backend.hiers() {
+    hiers.push(match hier {
+      RawHier::Scope(s) => format!("scope {} {:?} {:?}", s.ty, s.name, s.component),
+      RawHier::Upscope => "upscope".into(),
+      RawHier::Var(v) => {
+        let handle = u32::from(v.handle);
+        if !v.is_alias {
+          vars.push((handle, v.length, v.ty as u32));
+        }
+        format!(
+          "var {} {} {:?} {} {handle} {}",
+          v.ty, v.direction, v.name, v.length, v.is_alias,
+        )
+      }
+      RawHier::AttrBegin(a) => {
+        // `arg_from_name` is only meaningful for source stems.
+        let arg_from_name = match a.subtype as u32 {
+          misc_type::SOURCESTEM | misc_type::SOURCEISTEM => a.arg_from_name,
+          _ => 0,
+        };
+        format!(
+          "attr {} {} {:?} {} {arg_from_name}",
+          a.ty, a.subtype, a.name, a.arg
+        )
+      }
+      RawHier::AttrEnd => "attrend".into(),
+    });
+  }
+  backend.set_mask_all(true);
+  let changes = changes(&mut backend);
+  backend.set_time_range_limit(Some((start, end)));
+  let limited_changes = self::changes(&mut backend);
+  backend.set_time_range_limit(None);
+  let vcd_file = TempFile::new(&format!("{name}.vcd"));
+  backend.dump_to_vcd(Some(vcd_file.path())).unwrap();
+  let vcd = fs::read_to_string(vcd_file.path()).unwrap();
+  Decoded {
+    header,
+    hiers,
+    vars,
+    changes,
+    limited_changes,
+    vcd,
+  }
+}
+
+/// Reads the value at the given time by the given backend,
+/// with a newly opened backend.
+fn value_at<B: Backend>(file: &TempFile, handle: u32, len: u32, time: u64) -> Option<Vec<u8>> {
+  let mut backend = B::open(file.path()).unwrap();
+  let mut buf = Vec::new();
+  let handle = crate::Handle::new(handle).unwrap();
+  backend
+    .value_at(time, handle, len as usize, &mut buf)
+    .ok()
+    .map(|_| buf)
+}
+
+/// Block end times of the test waveform.
+const BLOCK_ENDS: [u64; 3] = [90, 190, 290];
+/// End time of the test waveform.
+const END_TIME: u64 = 350;
+
+/// Writes the test waveform by the given writer.
+fn write_waveform(mut writer: Writer) {
+  writer.set_env_var("FST_TEST=1").unwrap();
+  let table = writer
+    .create_enum_table("state", 2, &[("IDLE", "0"), ("RUN", "1"), ("DONE", "10")])
+    .unwrap();
+  writer.set_source_stem("top.v", 1, false).unwrap();
+  writer
+    .set_source_instantiation_stem("tb.v", 10, false)
+    .unwrap();
+  writer
+    .set_scope(scope_type::VCD_MODULE, "top", "Top")
+    .unwrap();
+  writer
+    .set_attr_begin(attr_type::MISC, misc_type::COMMENT, "top level", 0)
+    .unwrap();
+  writer.set_attr_end();
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  let counter = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 8, "counter", None)
+    .unwrap();
+  let wide = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 70, "wide", None)
+    .unwrap();
+  let real = writer
+    .create_var(var_type::VCD_REAL, var_dir::IMPLICIT, 64, "real", None)
+    .unwrap();
+  let string = writer
+    .create_var(var_type::GEN_STRING, var_dir::IMPLICIT, 0, "string", None)
+    .unwrap();
+  writer.emit_enum_table_ref(table);
+  let state = writer
+    .create_var(var_type::SV_ENUM, var_dir::OUTPUT, 2, "state", None)
+    .unwrap();
+  let supplemental = Supplemental::new(
+    "my_int",
+    supplemental_var_type::VHDL_SIGNAL,
+    supplemental_data_type::VHDL_INTEGER,
+  );
+  let int = writer
+    .create_var2(
+      var_type::SV_INT,
+      var_dir::OUTPUT,
+      32,
+      "int",
+      None,
+      &supplemental,
+    )
+    .unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "sub", "").unwrap();
+  writer
+    .create_var(
+      var_type::VCD_REG,
+      var_dir::OUTPUT,
+      8,
+      "alias",
+      Some(counter),
+    )
+    .unwrap();
+  writer.set_upscope();
+  writer.set_upscope();
+  for time in (0..=BLOCK_ENDS[2]).step_by(5) {
+    writer.emit_time_change(time).unwrap();
+    let n = time / 5;
+    writer
+      .emit_value_change(clk, if n % 2 == 0 { b"0" } else { b"1" })
+      .unwrap();
+    if n % 3 == 0 {
+      writer
+        .emit_value_change(counter, format!("{:08b}", n % 256).as_bytes())
+        .unwrap();
+      let bits: Vec<_> = (0..70).map(|i| b"01xz"[(i + n as usize) % 4]).collect();
+      writer.emit_value_change(wide, &bits).unwrap();
+      writer
+        .emit_value_change(state, ["00", "01", "10"][(n / 3 % 3) as usize].as_bytes())
+        .unwrap();
+    }
+    if n % 4 == 0 {
+      writer
+        .emit_value_change(real, &(n as f64 * 0.25).to_ne_bytes())
+        .unwrap();
+      writer
+        .emit_var_len_value_change(string, format!("s{n}").as_bytes())
+        .unwrap();
+      writer
+        .emit_value_change(int, format!("{:032b}", n * 1000).as_bytes())
+        .unwrap();
+    }
+    if time == 120 {
+      writer.emit_dump_active(false);
+    } else if time == 150 {
+      writer.emit_dump_active(true);
+    }
+    if BLOCK_ENDS.contains(&time) {
+      writer.flush();
+    }
+  }
+  writer.emit_time_change(END_TIME).unwrap();
+}
+
+/// Returns the value of the given variable at the given time,
+/// from the given value changes.
+fn value_from_changes(changes: &[Change], handle: u32, time: u64) -> Option<&[u8]> {
+  changes
+    .iter()
+    .rev()
+    .find(|(t, h, _, _)| *t <= time && *h == handle)
+    .map(|(_, _, v, _)| v.as_slice())
+}
+
+/// Checks if the given slices are the same,
+/// reports the first different element if not.
+fn assert_same<T: PartialEq + std::fmt::Debug>(c: &[T], native: &[T]) {
+  let index = c.iter().zip(native).position(|(c, n)| c != n);
+  if let Some(i) = index {
+    panic!("element {i} differs: {:?} != {:?}", c[i], native[i]);
+  }
+  assert_eq!(c.len(), native.len());
+}
+
+/// Decodes the waveform by both backends, and checks the results.
+fn check(file: &TempFile) {
+  let range = (100, 200);
+  let c = decode::<CBackend>(file, range, "capi");
+  let native = decode::<NativeBackend>(file, range, "native");
+  assert_eq!(c.header, native.header);
+  assert_eq!(c.hiers, native.hiers);
+  assert_eq!(c.vars, native.vars);
+  assert_same(&c.changes, &native.changes);
+  assert_same(&c.limited_changes, &native.limited_changes);
+  let vcd_lines: Vec<_> = c.vcd.lines().collect();
+  assert_same(&vcd_lines, &native.vcd.lines().collect::<Vec<_>>());
+  assert!(!native.changes.is_empty());
+  assert!(!native.limited_changes.is_empty());
+
+  // Read values of fixed-length non-real variables at all times,
+  // and compare them with the value changes.
+  let fixed = native
+    .vars
+    .iter()
+    .filter(|(_, len, ty)| *len != 0 && *ty != var_type::VCD_REAL);
+  for &(handle, len, _) in fixed {
+    for time in 0..=END_TIME {
+      let value = value_at::<NativeBackend>(file, handle, len, time);
+      let expected = value_from_changes(&c.changes, handle, time);
+      assert_eq!(value.as_deref(), expected, "handle: {handle}, time: {time}");
+      // The C library fails at ends of blocks and after the last block.
+      if time < BLOCK_ENDS[2] && !BLOCK_ENDS.contains(&time) {
+        let c_value = value_at::<CBackend>(file, handle, len, time);
+        assert_eq!(value, c_value, "handle: {handle}, time: {time}");
+      }
+    }
+  }
+}
+
+#[test]
+fn compare_zlib() {
+  let file = TempFile::new("zlib.fst");
+  write_waveform(Writer::create(file.path(), true).unwrap());
+  check(&file);
+}
+
+#[test]
+fn compare_fastlz() {
+  let file = TempFile::new("fastlz.fst");
+  let writer = Writer::create(file.path(), true).unwrap();
+  write_waveform(writer.pack_type(writer_pack_type::FASTLZ));
+  check(&file);
+}
+
+#[test]
+fn compare_lz4() {
+  let file = TempFile::new("lz4.fst");
+  let writer = Writer::create(file.path(), true).unwrap();
+  write_waveform(writer.pack_type(writer_pack_type::LZ4));
+  check(&file);
+}
+
+#[test]
+fn compare_uncompressed_hier() {
+  let file = TempFile::new("uncompressed-hier.fst");
+  write_waveform(Writer::create(file.path(), false).unwrap());
+  check(&file);
+}
+
+#[test]
+fn compare_gzip_wrapped() {
+  let file = TempFile::new("gzip-wrapped.fst");
+  let writer = Writer::create(file.path(), true).unwrap();
+  write_waveform(writer.repack_on_close(true));
+  check(&file);
+}
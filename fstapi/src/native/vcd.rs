@@ -0,0 +1,246 @@
+//! VCD output of the native backend, which produces the same output
+//! as `fstReaderDumpToVcdFile` in the C API.
+
+use super::context::Header;
+use super::format_g;
+use crate::consts::*;
+use std::io::{self, Write};
+
+/// Names of variable types in VCD, indexed by [`fstVarType`].
+const VAR_TYPES: [&str; 30] = [
+  "event",
+  "integer",
+  "parameter",
+  "real",
+  "real_parameter",
+  "reg",
+  "supply0",
+  "supply1",
+  "time",
+  "tri",
+  "triand",
+  "trior",
+  "trireg",
+  "tri0",
+  "tri1",
+  "wand",
+  "wire",
+  "wor",
+  "port",
+  "sparray",
+  "realtime",
+  "string",
+  "bit",
+  "logic",
+  "int",
+  "shortint",
+  "longint",
+  "byte",
+  "enum",
+  "shortreal",
+];
+
+/// Names of scope types in VCD, indexed by [`fstScopeType`].
+const MOD_TYPES: [&str; 22] = [
+  "module",
+  "task",
+  "function",
+  "begin",
+  "fork",
+  "generate",
+  "struct",
+  "union",
+  "class",
+  "interface",
+  "package",
+  "program",
+  "vhdl_architecture",
+  "vhdl_procedure",
+  "vhdl_function",
+  "vhdl_record",
+  "vhdl_process",
+  "vhdl_block",
+  "vhdl_for_generate",
+  "vhdl_if_generate",
+  "vhdl_generate",
+  "vhdl_package",
+];
+
+/// Writes the header of VCD, including date, version, timezero and timescale.
+pub fn write_header(w: &mut dyn Write, header: &Header) -> io::Result<()> {
+  let date = header.date.to_string_lossy();
+  let version = header.version.to_string_lossy();
+  write!(w, "$date\n\t{date}\n$end\n$version\n\t{version}\n$end\n")?;
+  if header.timezero != 0 {
+    write!(w, "$timezero\n\t{}\n$end\n", header.timezero)?;
+  }
+  const DIMS: [&str; 8] = ["", "m", "u", "n", "p", "f", "a", "z"];
+  let t = header.timescale as i32;
+  let (scale, dim) = if (-21..=2).contains(&t) {
+    let scale = [1, 10, 100][t.rem_euclid(3) as usize];
+    (scale, DIMS[((2 - t) / 3) as usize])
+  } else {
+    (1, "n")
+  };
+  write!(w, "$timescale\n\t{scale}{dim}s\n$end\n")
+}
+
+/// Writes a scope definition.
+pub fn write_scope(w: &mut dyn Write, ty: u8, name: &[u8]) -> io::Result<()> {
+  let ty = MOD_TYPES.get(ty as usize).unwrap_or(&MOD_TYPES[0]);
+  write!(w, "$scope {ty} ")?;
+  w.write_all(name)?;
+  w.write_all(b" $end\n")
+}
+
+/// Writes an upscope.
+pub fn write_upscope(w: &mut dyn Write) -> io::Result<()> {
+  w.write_all(b"$upscope $end\n")
+}
+
+/// Writes a variable definition.
+pub fn write_var(w: &mut dyn Write, ty: u8, len: u32, handle: u32, name: &[u8]) -> io::Result<()> {
+  let len = if ty as u32 == var_type::VCD_PORT {
+    len.wrapping_sub(2) / 3
+  } else {
+    len
+  };
+  write!(w, "$var {} {len} ", VAR_TYPES[ty as usize])?;
+  w.write_all(&vcd_id(handle))?;
+  w.write_all(b" ")?;
+  w.write_all(name)?;
+  w.write_all(b" $end\n")
+}
+
+/// Writes the end of definitions.
+pub fn write_enddefinitions(w: &mut dyn Write) -> io::Result<()> {
+  w.write_all(b"$enddefinitions $end\n")
+}
+
+/// Returns the VCD identifier of the given handle.
+fn vcd_id(handle: u32) -> Vec<u8> {
+  let mut id = Vec::new();
+  let mut value = handle;
+  while value != 0 {
+    value -= 1;
+    id.push(b'!' + (value % 94) as u8);
+    value /= 94;
+  }
+  id
+}
+
+/// Escapes the given binary data, same as `fstUtilityBinToEsc`.
+fn escape(data: &[u8]) -> Vec<u8> {
+  let mut buf = Vec::with_capacity(data.len());
+  for &b in data {
+    let esc = match b {
+      0x07 => b'a',
+      0x08 => b'b',
+      0x0c => b'f',
+      b'\n' => b'n',
+      b'\r' => b'r',
+      b'\t' => b't',
+      0x0b => b'v',
+      b'\'' | b'"' | b'\\' | b'?' => b,
+      b'!'..=b'~' => {
+        buf.push(b);
+        continue;
+      }
+      _ => {
+        buf.extend_from_slice(&[b'\\', b'0' + b / 64, b'0' + b / 8 % 8, b'0' + b % 8]);
+        continue;
+      }
+    };
+    buf.extend_from_slice(&[b'\\', esc]);
+  }
+  buf
+}
+
+/// Writer of VCD value changes.
+pub struct VcdWriter<'a> {
+  w: &'a mut dyn Write,
+  /// State of `$dumpvars` section, 0 for not started,
+  /// 1 for started and 2 for ended.
+  dumpvars_state: u8,
+  blackouts: &'a [(u64, bool)],
+}
+
+impl<'a> VcdWriter<'a> {
+  /// Creates a new VCD writer.
+  pub fn new(w: &'a mut dyn Write, blackouts: &'a [(u64, bool)]) -> Self {
+    Self {
+      w,
+      dumpvars_state: 0,
+      blackouts,
+    }
+  }
+
+  /// Writes a time change, and the dump activity change at the given time.
+  ///
+  /// The time itself is written only if `write_time` is `true`.
+  pub fn time_change(&mut self, time: u64, write_time: bool) -> io::Result<()> {
+    if write_time {
+      if self.dumpvars_state == 1 {
+        self.w.write_all(b"$end\n")?;
+        self.dumpvars_state = 2;
+      }
+      writeln!(self.w, "#{time}")?;
+      if self.dumpvars_state == 0 {
+        self.w.write_all(b"$dumpvars\n")?;
+        self.dumpvars_state = 1;
+      }
+    }
+    if let Some(((t, active), rest)) = self.blackouts.split_first()
+      && *t == time
+    {
+      let act = if *active { "on" } else { "off" };
+      writeln!(self.w, "$dump{act} $end")?;
+      self.blackouts = rest;
+    }
+    Ok(())
+  }
+
+  /// Writes a value change of a 1-bit variable.
+  pub fn bit(&mut self, handle: u32, value: u8) -> io::Result<()> {
+    self.w.write_all(&[value])?;
+    self.end(handle)
+  }
+
+  /// Writes a value change of a vector variable.
+  pub fn vector(&mut self, handle: u32, ty: u8, value: &[u8]) -> io::Result<()> {
+    let prefix = if ty as u32 != var_type::VCD_PORT {
+      b'b'
+    } else {
+      b'p'
+    };
+    self.w.write_all(&[prefix])?;
+    self.w.write_all(value)?;
+    self.w.write_all(b" ")?;
+    self.end(handle)
+  }
+
+  /// Writes a value change of a real variable.
+  pub fn real(&mut self, handle: u32, value: f64) -> io::Result<()> {
+    write!(self.w, "r{} ", format_g(value))?;
+    self.end(handle)
+  }
+
+  /// Writes a value change of a variable-length variable.
+  pub fn string(&mut self, handle: u32, value: &[u8]) -> io::Result<()> {
+    self.w.write_all(b"s")?;
+    self.w.write_all(&escape(value))?;
+    self.w.write_all(b" ")?;
+    self.end(handle)
+  }
+
+  /// Writes the identifier and the line break.
+  fn end(&mut self, handle: u32) -> io::Result<()> {
+    self.w.write_all(&vcd_id(handle))?;
+    self.w.write_all(b"\n")
+  }
+
+  /// Flushes the output.
+  pub fn flush(&mut self) -> io::Result<()> {
+    self.w.flush()
+  }
+}
@@ -0,0 +1,353 @@
+#[cfg(feature = "pure-rust")]
+use crate::Error;
+use crate::Result;
+#[cfg(feature = "pure-rust")]
+use crate::native::{NativeBackend, SharedData};
+use crate::reader::{Reader, TimeWindow, ValueChange};
+use crate::types::Handle;
+use std::num::NonZeroUsize;
+use std::ops::{ControlFlow, Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::{panic, thread};
+
+/// A pool of [`Reader`]s of the same FST waveform,
+/// for reading the waveform from multiple threads.
+///
+/// Each thread takes a reader from the pool by [`get`](ReaderPool::get),
+/// and the reader is put back to the pool when dropped, so it can be
+/// reused by other threads later.
+///
+/// With the `pure-rust` feature, the waveform file is opened and
+/// memory-mapped only once, and shared by all readers of the pool.
+/// Otherwise, each reader opens the waveform file separately.
+///
+/// The pool can also decode value change blocks in parallel, see
+/// [`for_each_block`](ReaderPool::for_each_block) and
+/// [`fold_blocks`](ReaderPool::fold_blocks).
+///
+/// # Examples
+///
+/// Scan different time ranges of a waveform in parallel:
+///
+/// ```no_run
+/// use fstapi::ReaderPool;
+/// use std::thread;
+///
+/// # fn main() -> fstapi::Result<()> {
+/// let pool = ReaderPool::open("hello.fst")?;
+/// let end_time = pool.get()?.end_time();
+/// let counts = thread::scope(|s| {
+///   let handles: Vec<_> = (0..4)
+///     .map(|i| {
+///       let pool = &pool;
+///       s.spawn(move || -> fstapi::Result<usize> {
+///         let mut reader = pool.get()?;
+///         reader.set_mask_all();
+///         reader.set_time_range_limit(end_time / 4 * i, end_time / 4 * (i + 1));
+///         let mut count = 0;
+///         reader.for_each_block(|_, _, _, _| count += 1)?;
+///         Ok(count)
+///       })
+///     })
+///     .collect();
+///   handles.into_iter().map(|h| h.join().unwrap()).collect::<fstapi::Result<Vec<_>>>()
+/// })?;
+/// println!("{counts:?}");
+/// # Ok(())
+/// # }
+/// ```
+pub struct ReaderPool {
+  path: PathBuf,
+  #[cfg(feature = "pure-rust")]
+  data: SharedData,
+  idle: Mutex<Vec<Reader>>,
+  threads: usize,
+}
+
+impl ReaderPool {
+  /// Opens an FST waveform from the given path.
+  pub fn open<P>(path: P) -> Result<Self>
+  where
+    P: AsRef<Path>,
+  {
+    let path = path.as_ref();
+    #[cfg(feature = "pure-rust")]
+    let data = SharedData::open(path).map_err(|e| Error::Open(path.into(), e.kind()))?;
+    let pool = Self {
+      path: path.into(),
+      #[cfg(feature = "pure-rust")]
+      data,
+      idle: Mutex::new(Vec::new()),
+      threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+    };
+    // Open the first reader to check if the waveform is valid.
+    let reader = pool.open_reader()?;
+    pool.idle_readers().push(reader);
+    Ok(pool)
+  }
+
+  /// Sets the number of threads for decoding value change blocks
+  /// in parallel, defaults to the available parallelism.
+  pub fn threads(mut self, threads: usize) -> Self {
+    self.threads = threads.max(1);
+    self
+  }
+
+  /// Returns the path of the waveform.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// Takes a reader from the pool,
+  /// or opens a new one if there is no idle reader.
+  ///
+  /// The returned reader has the same settings as a newly opened reader,
+  /// i.e. all masks cleared, no time range limit and no native doubles.
+  pub fn get(&self) -> Result<PooledReader<'_>> {
+    let reader = match self.idle_readers().pop() {
+      Some(reader) => reader,
+      None => self.open_reader()?,
+    };
+    Ok(PooledReader {
+      pool: self,
+      reader: Some(reader),
+    })
+  }
+
+  /// Runs the given callback on each value change of the waveform,
+  /// with value change blocks decoded in parallel.
+  ///
+  /// `setup` is called on each reader before decoding, for setting masks,
+  /// time range limit or native doubles of the reader. Value changes are
+  /// delivered to the callback in time order, the same as
+  /// [`Reader::for_each_block`] on a reader set up by `setup`, except that
+  /// value changes after the end of the time range limit are skipped.
+  pub fn for_each_block<S, F>(&self, setup: S, mut callback: F) -> Result<()>
+  where
+    S: Fn(&mut Reader) + Sync,
+    F: FnMut(u64, Handle, &[u8], bool),
+  {
+    self
+      .try_for_each_block(setup, |time, handle, value, var_len| {
+        callback(time, handle, value, var_len);
+        ControlFlow::<()>::Continue(())
+      })
+      .map(|_| ())
+  }
+
+  /// Runs the given callback on each value change of the waveform,
+  /// with value change blocks decoded in parallel, stops iterating when
+  /// the callback returns [`ControlFlow::Break`].
+  ///
+  /// See [`for_each_block`](ReaderPool::for_each_block) for details.
+  pub fn try_for_each_block<S, F, B>(&self, setup: S, mut callback: F) -> Result<ControlFlow<B>>
+  where
+    S: Fn(&mut Reader) + Sync,
+    F: FnMut(u64, Handle, &[u8], bool) -> ControlFlow<B>,
+  {
+    let mut reader = self.get()?;
+    setup(&mut reader);
+    let windows = reader.time_windows();
+    // Decode directly if the windows can not be decoded in parallel.
+    if self.threads == 1 || windows.len() <= 1 {
+      return try_for_each_until_end(&mut reader, callback);
+    }
+    drop(reader);
+    // Decode a few windows per thread at a time,
+    // to limit the number of value changes held in memory.
+    for windows in windows.chunks(self.threads * 2) {
+      let parts = self.fold_windows(
+        windows,
+        &setup,
+        Vec::new,
+        |changes, time, handle, value, var_len| {
+          changes.push(ValueChange {
+            time,
+            handle,
+            value: value.into(),
+            var_len,
+          })
+        },
+      )?;
+      for change in parts.into_iter().flatten() {
+        let flow = callback(change.time, change.handle, &change.value, change.var_len);
+        if flow.is_break() {
+          return Ok(flow);
+        }
+      }
+    }
+    Ok(ControlFlow::Continue(()))
+  }
+
+  /// Folds value changes of the waveform in parallel,
+  /// returns the partial results in time order.
+  ///
+  /// The waveform is split into time windows at the start times of value
+  /// change blocks, so each window covers one block. Value changes in each
+  /// window are folded by `fold` into a value created by `init`, and windows
+  /// are processed by multiple threads concurrently. The partial results can then be reduced by the
+  /// caller.
+  ///
+  /// `setup` is called on each reader before decoding, see
+  /// [`for_each_block`](ReaderPool::for_each_block) for details.
+  pub fn fold_blocks<S, T, I, F>(&self, setup: S, init: I, fold: F) -> Result<Vec<T>>
+  where
+    S: Fn(&mut Reader) + Sync,
+    T: Send,
+    I: Fn() -> T + Sync,
+    F: Fn(&mut T, u64, Handle, &[u8], bool) + Sync,
+  {
+    let windows = self.time_windows(&setup)?;
+    self.fold_windows(&windows, &setup, init, fold)
+  }
+
+  /// Returns time windows of a reader set up by `setup`.
+  fn time_windows<S>(&self, setup: &S) -> Result<Vec<TimeWindow>>
+  where
+    S: Fn(&mut Reader),
+  {
+    let mut reader = self.get()?;
+    setup(&mut reader);
+    Ok(reader.time_windows())
+  }
+
+  /// Folds value changes in the given time windows in parallel,
+  /// returns the partial results in the order of windows.
+  fn fold_windows<S, T, I, F>(
+    &self,
+    windows: &[TimeWindow],
+    setup: &S,
+    init: I,
+    fold: F,
+  ) -> Result<Vec<T>>
+  where
+    S: Fn(&mut Reader) + Sync,
+    T: Send,
+    I: Fn() -> T + Sync,
+    F: Fn(&mut T, u64, Handle, &[u8], bool) + Sync,
+  {
+    let next = AtomicUsize::new(0);
+    let worker = || -> Result<Vec<(usize, T)>> {
+      let mut reader = self.get()?;
+      setup(&mut reader);
+      let mut parts = Vec::new();
+      loop {
+        let index = next.fetch_add(1, Ordering::Relaxed);
+        let Some(window) = windows.get(index) else {
+          break;
+        };
+        let mut part = init();
+        let ret = reader.for_each_in_window(*window, |time, handle, value, var_len| {
+          fold(&mut part, time, handle, value, var_len)
+        });
+        if let Err(e) = ret {
+          // Stop other threads from taking more windows.
+          next.store(windows.len(), Ordering::Relaxed);
+          return Err(e);
+        }
+        parts.push((index, part));
+      }
+      Ok(parts)
+    };
+    let threads = self.threads.min(windows.len());
+    let results: Vec<_> = thread::scope(|s| {
+      let handles: Vec<_> = (0..threads).map(|_| s.spawn(worker)).collect();
+      handles
+        .into_iter()
+        .map(|h| h.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+        .collect()
+    });
+    let mut parts = Vec::with_capacity(windows.len());
+    for result in results {
+      parts.extend(result?);
+    }
+    parts.sort_unstable_by_key(|(i, _)| *i);
+    Ok(parts.into_iter().map(|(_, part)| part).collect())
+  }
+
+  /// Opens a new reader.
+  #[cfg(feature = "pure-rust")]
+  fn open_reader(&self) -> Result<Reader> {
+    match NativeBackend::open_shared(&self.data) {
+      Some(backend) => Ok(Reader::from_backend(backend, self.path.clone())),
+      None => Err(Error::NotFst(self.path.clone())),
+    }
+  }
+
+  /// Opens a new reader.
+  #[cfg(not(feature = "pure-rust"))]
+  fn open_reader(&self) -> Result<Reader> {
+    Reader::open(&self.path)
+  }
+
+  /// Returns the idle readers.
+  fn idle_readers(&self) -> MutexGuard<'_, Vec<Reader>> {
+    // The list is always valid even if another thread panicked.
+    self.idle.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+/// Runs the given callback on each value change of the given reader,
+/// skips value changes after the end of the time range limit.
+fn try_for_each_until_end<F, B>(reader: &mut Reader, mut callback: F) -> Result<ControlFlow<B>>
+where
+  F: FnMut(u64, Handle, &[u8], bool) -> ControlFlow<B>,
+{
+  let end = reader.time_range_limit().map_or(u64::MAX, |(_, end)| end);
+  let flow = reader.try_for_each_block(|time, handle, value, var_len| {
+    // Value changes are delivered in time order.
+    if time > end {
+      return ControlFlow::Break(None);
+    }
+    callback(time, handle, value, var_len).map_break(Some)
+  })?;
+  Ok(match flow {
+    ControlFlow::Break(Some(b)) => ControlFlow::Break(b),
+    _ => ControlFlow::Continue(()),
+  })
+}
+
+/// A reader taken from a [`ReaderPool`].
+///
+/// This struct is created by the [`get`](ReaderPool::get) method
+/// on [`ReaderPool`]. The reader is put back to the pool when dropped.
+pub struct PooledReader<'a> {
+  pool: &'a ReaderPool,
+  /// Always [`Some`] until dropped or detached.
+  reader: Option<Reader>,
+}
+
+impl PooledReader<'_> {
+  /// Detaches the reader from the pool, so it will not be put back.
+  pub fn detach(mut self) -> Reader {
+    self.reader.take().unwrap()
+  }
+}
+
+impl Deref for PooledReader<'_> {
+  type Target = Reader;
+
+  fn deref(&self) -> &Self::Target {
+    self.reader.as_ref().unwrap()
+  }
+}
+
+impl DerefMut for PooledReader<'_> {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    self.reader.as_mut().unwrap()
+  }
+}
+
+impl Drop for PooledReader<'_> {
+  fn drop(&mut self) {
+    // The reader may be left in the middle of an operation when panicking.
+    if let Some(mut reader) = self.reader.take()
+      && !thread::panicking()
+    {
+      reader.reset();
+      self.pool.idle_readers().push(reader);
+    }
+  }
+}
@@ -1,18 +1,38 @@
+use crate::backend::{Backend, RawAttr, RawHier, RawScope, RawVar};
+use crate::block::{Block, read_blocks};
 use crate::consts::{AttrType, FileType, ScopeType, VarDir, VarType};
-use crate::types::Handle;
-use crate::utils::*;
-use crate::{Error, Result, capi};
-use std::marker::PhantomData;
-use std::num::NonZeroU32;
+use crate::consts::{attr_type, misc_type, supplemental_data_type, var_type};
+use crate::enum_table::EnumTable;
+use crate::types::{EnumHandle, Handle, Supplemental};
+use crate::value::Value;
+use crate::{Error, ReaderBackend, Result};
+use std::collections::VecDeque;
+use std::ffi::CStr;
+use std::fs::File;
+use std::iter;
+use std::ops::ControlFlow;
 use std::os::raw;
-use std::path::Path;
-use std::{ptr, slice};
+use std::path::{Path, PathBuf};
 
 /// FST waveform reader.
 #[derive(Debug)]
 pub struct Reader {
-  /// Non-null context pointer.
-  ctx: *mut raw::c_void,
+  /// Backend which decodes the waveform.
+  backend: ReaderBackend,
+  /// Path of the waveform.
+  path: PathBuf,
+  /// Lengths in bits and types of all variables, indexed by handle.
+  /// Collected from the hierarchies on demand.
+  var_infos: Option<Box<[(u32, VarType)]>>,
+  /// Time range limit set by user.
+  time_range: Option<(u64, u64)>,
+  /// Time ranges of all value change blocks.
+  /// Collected from the blocks on demand.
+  vc_ranges: Option<Box<[(u64, u64)]>>,
+  /// Another reader of the same waveform, for reading values
+  /// by iterating over blocks.
+  #[cfg(not(feature = "pure-rust"))]
+  value_reader: Option<Box<Reader>>,
 }
 
 impl Reader {
@@ -21,68 +41,83 @@ impl Reader {
   where
     P: AsRef<Path>,
   {
-    let path = path.to_str()?.into_cstring()?;
-    let ctx = unsafe { capi::fstReaderOpen(path.as_ptr()) };
-    if ctx.is_null() {
-      Err(Error::ContextCreate)
-    } else {
-      Ok(Self { ctx })
+    // Check if the file can be opened first,
+    // since the C API does not report the reason of failure.
+    if let Err(e) = File::open(&path) {
+      return Err(Error::Open(path.as_ref().into(), e.kind()));
+    }
+    match ReaderBackend::open(path.as_ref()) {
+      Some(backend) => Ok(Self::from_backend(backend, path.as_ref().into())),
+      None => Err(Error::NotFst(path.as_ref().into())),
+    }
+  }
+
+  /// Creates a reader from the given backend and the path of the waveform.
+  pub(crate) fn from_backend(backend: ReaderBackend, path: PathBuf) -> Self {
+    Self {
+      backend,
+      path,
+      var_infos: None,
+      time_range: None,
+      vc_ranges: None,
+      #[cfg(not(feature = "pure-rust"))]
+      value_reader: None,
     }
   }
 
   /// Returns date.
   pub fn date(&self) -> Result<&str> {
-    unsafe { capi::fstReaderGetDateString(self.ctx).to_str() }
+    to_str(self.backend.date())
   }
 
   /// Returns date as raw C string.
   pub fn date_raw(&self) -> *const raw::c_char {
-    unsafe { capi::fstReaderGetDateString(self.ctx) }
+    self.backend.date().as_ptr()
   }
 
   /// Returns version.
   pub fn version(&self) -> Result<&str> {
-    unsafe { capi::fstReaderGetVersionString(self.ctx).to_str() }
+    to_str(self.backend.version())
   }
 
   /// Returns version as raw C string.
   pub fn version_raw(&self) -> *const raw::c_char {
-    unsafe { capi::fstReaderGetVersionString(self.ctx) }
+    self.backend.version().as_ptr()
   }
 
   /// Returns start time.
   pub fn start_time(&self) -> u64 {
-    unsafe { capi::fstReaderGetStartTime(self.ctx) }
+    self.backend.start_time()
   }
 
   /// Returns end time.
   pub fn end_time(&self) -> u64 {
-    unsafe { capi::fstReaderGetEndTime(self.ctx) }
+    self.backend.end_time()
   }
 
   /// Returns file type.
   pub fn file_type(&self) -> FileType {
-    unsafe { capi::fstReaderGetFileType(self.ctx) as FileType }
+    self.backend.file_type()
   }
 
   /// Returns alias count.
   pub fn alias_count(&self) -> u64 {
-    unsafe { capi::fstReaderGetAliasCount(self.ctx) }
+    self.backend.alias_count()
   }
 
   /// Returns scope count.
   pub fn scope_count(&self) -> u64 {
-    unsafe { capi::fstReaderGetScopeCount(self.ctx) }
+    self.backend.scope_count()
   }
 
   /// Returns variable count.
   pub fn var_count(&self) -> u64 {
-    unsafe { capi::fstReaderGetVarCount(self.ctx) }
+    self.backend.var_count()
   }
 
   /// Returns timescale.
   pub fn timescale(&self) -> i32 {
-    unsafe { capi::fstReaderGetTimescale(self.ctx) as i32 }
+    self.backend.timescale()
   }
 
   /// Returns timescale as string.
@@ -103,61 +138,243 @@ impl Reader {
 
   /// Returns timezero.
   pub fn timezero(&self) -> i64 {
-    unsafe { capi::fstReaderGetTimezero(self.ctx) }
+    self.backend.timezero()
   }
 
   /// Returns process mask for the facility of the given handle.
   pub fn mask(&self, handle: Handle) -> bool {
-    unsafe { capi::fstReaderGetFacProcessMask(self.ctx, handle.into()) != 0 }
+    self.backend.mask(handle)
   }
 
   /// Clears process mask for the facility of the given handle.
   pub fn clear_mask(&mut self, handle: Handle) {
-    unsafe { capi::fstReaderClrFacProcessMask(self.ctx, handle.into()) }
+    self.backend.set_mask(handle, false)
   }
 
   /// Clears process mask for all facilities.
   pub fn clear_mask_all(&mut self) {
-    unsafe { capi::fstReaderClrFacProcessMaskAll(self.ctx) }
+    self.backend.set_mask_all(false)
   }
 
   /// Sets process mask for the facility of the given handle.
   pub fn set_mask(&mut self, handle: Handle) {
-    unsafe { capi::fstReaderSetFacProcessMask(self.ctx, handle.into()) }
+    self.backend.set_mask(handle, true)
   }
 
   /// Sets process mask for all facilities.
   pub fn set_mask_all(&mut self) {
-    unsafe { capi::fstReaderSetFacProcessMaskAll(self.ctx) }
+    self.backend.set_mask_all(true)
+  }
+
+  /// Returns time range limit, or [`None`] if there is no limit.
+  pub fn time_range_limit(&self) -> Option<(u64, u64)> {
+    self.time_range
   }
 
   /// Sets time range limit.
   pub fn set_time_range_limit(&mut self, start_time: u64, end_time: u64) {
-    unsafe { capi::fstReaderSetLimitTimeRange(self.ctx, start_time, end_time) }
+    self.time_range = Some((start_time, end_time));
+    self.apply_time_range_limit(self.time_range);
   }
 
   /// Resets time range limit.
   pub fn reset_time_range_limit(&mut self) {
-    unsafe { capi::fstReaderSetUnlimitedTimeRange(self.ctx) }
+    self.time_range = None;
+    self.apply_time_range_limit(None);
+  }
+
+  /// Applies the given time range limit to the backend,
+  /// without updating the limit set by user.
+  fn apply_time_range_limit(&mut self, range: Option<(u64, u64)>) {
+    self.backend.set_time_range_limit(range);
   }
 
   /// Sets whether to use native doubles in callback when iterating over blocks.
   pub fn set_native_doubles_on_callback(&mut self, enable: bool) {
-    unsafe { capi::fstReaderIterBlocksSetNativeDoublesOnCallback(self.ctx, enable as i32) }
+    self.backend.set_native_doubles(enable)
+  }
+
+  /// Resets all settings of the reader, including masks, time range limit
+  /// and native doubles, to the ones of a newly opened reader.
+  ///
+  /// Data cached from the waveform is kept.
+  pub(crate) fn reset(&mut self) {
+    self.clear_mask_all();
+    self.reset_time_range_limit();
+    self.set_native_doubles_on_callback(false);
+  }
+
+  /// Returns the maximum handle of the waveform.
+  pub fn max_handle(&self) -> Option<Handle> {
+    Handle::new(self.backend.max_handle())
+  }
+
+  /// Returns the number of value change blocks recorded in the header.
+  pub fn value_change_block_count(&self) -> u64 {
+    self.backend.value_change_block_count()
+  }
+
+  /// Returns `true` if the byte order of doubles in the waveform
+  /// matches the current machine.
+  pub fn double_endian_match(&self) -> bool {
+    self.backend.double_endian_match()
+  }
+
+  /// Returns the memory used by the writer when writing the waveform,
+  /// in bytes.
+  pub fn memory_used_by_writer(&self) -> u64 {
+    self.backend.memory_used_by_writer()
+  }
+
+  /// Returns an iterator over the dump activity changes of the waveform,
+  /// in `(time, active)` form.
+  ///
+  /// Dumping is active at the beginning of the waveform, and can be turned
+  /// off/on by `$dumpoff`/`$dumpon` in VCD, or by
+  /// [`Writer::emit_dump_active`](crate::Writer::emit_dump_active).
+  pub fn dump_activity_changes(&self) -> DumpActivityChanges<'_> {
+    DumpActivityChanges {
+      backend: &self.backend,
+      index: 0,
+      len: self.backend.dump_activity_count(),
+    }
+  }
+
+  /// Returns metadata of all blocks of the waveform, in file order.
+  ///
+  /// For gzip-wrapped waveforms, only the wrapper block is returned.
+  pub fn blocks(&self) -> Result<Vec<Block>> {
+    read_blocks(&self.path)
+  }
+
+  /// Returns the value of the variable of the given handle at the given time.
+  ///
+  /// The value is the last value change of the variable that occurred
+  /// at or before `time`, in the same format as the values passed to
+  /// the callback of [`for_each_block`](Reader::for_each_block),
+  /// except that real values are formatted as VCD real strings (like `r2.5`).
+  ///
+  /// This method does not require iterating over the whole waveform,
+  /// and is unaffected by process masks and time range limit.
+  /// For times after the end time of the waveform, returns the last value
+  /// of the variable.
+  ///
+  /// Returns [`Error::InvalidHandle`] if the handle is not a handle of any
+  /// variable, or is a handle of a variable-length variable (like strings).
+  pub fn value_at(&mut self, handle: Handle, time: u64) -> Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    self.value_at_into(handle, time, &mut buf)?;
+    Ok(buf)
+  }
+
+  /// Writes the value of the variable of the given handle at the given time
+  /// to the given buffer, reuses the allocated memory of the buffer.
+  ///
+  /// See [`value_at`](Reader::value_at) for details.
+  pub fn value_at_into(&mut self, handle: Handle, time: u64, buf: &mut Vec<u8>) -> Result<()> {
+    // Variable-length variables have no length.
+    let (len, ty) = match self.var_info(handle) {
+      Some((len, ty)) if len != 0 => (len as usize, ty),
+      _ => return Err(Error::InvalidHandle(handle)),
+    };
+    buf.clear();
+    // Variables hold their last values after the end of the waveform.
+    let time = time.min(self.end_time());
+    #[cfg(not(feature = "pure-rust"))]
+    if let Some(start) = self.value_at_iter_start(time) {
+      self.value_at_by_iter(handle, start, time, buf)?;
+      fix_real_value(ty, buf);
+      return Ok(());
+    }
+    self.backend.value_at(time, handle, len, buf)?;
+    fix_real_value(ty, buf);
+    Ok(())
+  }
+
+  /// Returns the start of the time range to iterate over for reading
+  /// the value at the given time, or [`None`] if the value can be read
+  /// by the C library directly.
+  ///
+  /// The C library crashes if `time` is the end time of a value change block
+  /// but not the end time of the waveform, and fails if `time` is after the
+  /// last block, so values at such times are read by iterating over blocks.
+  #[cfg(not(feature = "pure-rust"))]
+  fn value_at_iter_start(&mut self, time: u64) -> Option<u64> {
+    let end_time = self.end_time();
+    let ranges = self.vc_ranges();
+    // Blocks are unknown if the waveform is gzip-wrapped.
+    if ranges.is_empty() {
+      return Some(0);
+    }
+    match ranges.iter().find(|(b, e)| *b <= time && time <= *e) {
+      Some((_, end)) => (time == *end && time != end_time).then_some(time),
+      None => ranges.iter().rev().map(|(_, e)| *e).find(|e| *e < time),
+    }
+  }
+
+  /// Writes the value of the variable of the given handle at the given time
+  /// to the given buffer, by iterating over blocks from `start`.
+  #[cfg(not(feature = "pure-rust"))]
+  fn value_at_by_iter(
+    &mut self,
+    handle: Handle,
+    start: u64,
+    time: u64,
+    buf: &mut Vec<u8>,
+  ) -> Result<()> {
+    let reader = match &mut self.value_reader {
+      Some(reader) => reader,
+      None => self
+        .value_reader
+        .insert(Box::new(Reader::open(&self.path)?)),
+    };
+    reader.clear_mask_all();
+    reader.set_mask(handle);
+    reader.set_time_range_limit(start, time);
+    let mut found = false;
+    reader.for_each_block(|t, _, value, _| {
+      if t <= time {
+        buf.clear();
+        buf.extend_from_slice(value);
+        found = true;
+      }
+    })?;
+    if found {
+      Ok(())
+    } else {
+      Err(Error::InvalidOperation)
+    }
+  }
+
+  /// Returns the length in bits and the type of the variable
+  /// of the given handle.
+  fn var_info(&mut self, handle: Handle) -> Option<(u32, VarType)> {
+    if self.var_infos.is_none() {
+      let max_handle = self.max_handle().map_or(0, u32::from) as usize;
+      let mut infos = vec![(0, 0); max_handle].into_boxed_slice();
+      for hier in self.hiers() {
+        if let Hier::Var(v) = hier
+          && let Some(info) = infos.get_mut(u32::from(v.handle()) as usize - 1)
+        {
+          *info = (v.length(), v.ty());
+        }
+      }
+      self.var_infos = Some(infos);
+    }
+    let infos = self.var_infos.as_ref().unwrap();
+    infos.get(u32::from(handle) as usize - 1).copied()
   }
 
   /// Returns an iterator over the hierarchies of the waveform.
   pub fn hiers(&mut self) -> Hiers<'_> {
-    unsafe { capi::fstReaderIterateHierRewind(self.ctx) };
     Hiers {
-      ctx: self.ctx,
-      phantom: PhantomData,
+      hiers: self.backend.hiers(),
+      supplemental: None,
     }
   }
 
   /// Returns an iterator over the variables of the waveform.
   pub fn vars(&mut self) -> Vars<'_> {
-    unsafe { capi::fstReaderIterateHierRewind(self.ctx) };
     Vars {
       hiers: self.hiers(),
       scopes: Vec::new(),
@@ -173,53 +390,141 @@ impl Reader {
   ///   // ...
   /// }
   /// ```
+  ///
+  /// Blocks are decoded as a whole, so if there is a time range limit, value
+  /// changes before the start and after the end of the range may be delivered.
   pub fn for_each_block<F>(&mut self, mut callback: F) -> Result<()>
   where
     F: FnMut(u64, Handle, &[u8], bool),
   {
-    extern "C" fn c_callback<F>(
-      data: *mut raw::c_void,
-      time: u64,
-      handle: capi::fstHandle,
-      value: *const raw::c_uchar,
-      len: u32,
-    ) where
-      F: FnMut(u64, Handle, &[u8], bool),
-    {
-      let data: &mut F = unsafe { &mut *(data as *mut F) };
-      let handle = unsafe { Handle(NonZeroU32::new_unchecked(handle)) };
-      let value = unsafe { slice::from_raw_parts(value, len as usize) };
-      data(time, handle, value, false);
-    }
+    self
+      .backend
+      .iter_blocks(&mut |time, handle, value, var_len| {
+        callback(time, handle, value, var_len);
+        ControlFlow::Continue(())
+      })
+  }
 
-    extern "C" fn c_callback_var_len<F>(
-      data: *mut raw::c_void,
-      time: u64,
-      handle: capi::fstHandle,
-      value: *const raw::c_uchar,
-      len: u32,
-    ) where
-      F: FnMut(u64, Handle, &[u8], bool),
-    {
-      let data: &mut F = unsafe { &mut *(data as *mut F) };
-      let handle = unsafe { Handle(NonZeroU32::new_unchecked(handle)) };
-      let value = unsafe { slice::from_raw_parts(value, len as usize) };
-      data(time, handle, value, true);
+  /// Runs the given callback on each block of the waveform,
+  /// stops iterating when the callback returns [`ControlFlow::Break`].
+  ///
+  /// The callback is defined as:
+  ///
+  /// ```
+  /// use std::ops::ControlFlow;
+  ///
+  /// fn callback(time: u64, handle: fstapi::Handle, value: &[u8], var_len: bool) -> ControlFlow<()> {
+  ///   // ...
+  ///   ControlFlow::Continue(())
+  /// }
+  /// ```
+  ///
+  /// Returns the break value of the callback, or [`ControlFlow::Continue`]
+  /// if all blocks have been iterated.
+  pub fn try_for_each_block<F, B>(&mut self, mut callback: F) -> Result<ControlFlow<B>>
+  where
+    F: FnMut(u64, Handle, &[u8], bool) -> ControlFlow<B>,
+  {
+    let mut result = ControlFlow::Continue(());
+    self
+      .backend
+      .iter_blocks(&mut |time, handle, value, var_len| {
+        result = callback(time, handle, value, var_len);
+        match result {
+          ControlFlow::Continue(_) => ControlFlow::Continue(()),
+          ControlFlow::Break(_) => ControlFlow::Break(()),
+        }
+      })
+      .map(|_| result)
+  }
+
+  /// Returns an iterator over the value changes of the waveform.
+  ///
+  /// Value changes are yielded in time order, and only variables with
+  /// process mask set are considered. If there is a time range limit,
+  /// value changes after the end of the range are skipped, and value changes
+  /// before the start of the range may be yielded, the same as
+  /// [`for_each_block`](Reader::for_each_block).
+  ///
+  /// The waveform is decoded one value change block at a time, so only
+  /// value changes of the current block are held in memory.
+  pub fn changes(&mut self) -> Changes<'_> {
+    let windows = self.time_windows().into();
+    Changes {
+      reader: self,
+      windows,
+      changes: VecDeque::new(),
     }
+  }
 
-    let ret = unsafe {
-      capi::fstReaderIterBlocks2(
-        self.ctx,
-        Some(c_callback::<F>),
-        Some(c_callback_var_len::<F>),
-        (&mut callback) as *mut _ as *mut raw::c_void,
-        ptr::null_mut(),
-      )
+  /// Returns time ranges of all value change blocks, in file order.
+  ///
+  /// Returns an empty slice if the blocks are unknown,
+  /// e.g. the waveform is gzip-wrapped.
+  fn vc_ranges(&mut self) -> &[(u64, u64)] {
+    self.vc_ranges.get_or_insert_with(|| {
+      read_blocks(&self.path)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|b| b.time_range)
+        .collect()
+    })
+  }
+
+  /// Returns time windows covering the time range limit,
+  /// or the whole waveform if there is no limit.
+  ///
+  /// Windows are split at the start times of value change blocks, so each
+  /// window covers one block. There is only one window if the blocks are
+  /// unknown.
+  pub(crate) fn time_windows(&mut self) -> Vec<TimeWindow> {
+    let (start, end) = match self.time_range {
+      Some((start, end)) => (start.max(self.start_time()), end.min(self.end_time())),
+      None => (self.start_time(), self.end_time()),
     };
-    match ret {
-      0 => Err(Error::InvalidOperation),
-      _ => Ok(()),
+    if start > end {
+      return Vec::new();
     }
+    let mut windows: Vec<TimeWindow> = Vec::new();
+    let block_starts = self.vc_ranges().iter().map(|(s, _)| *s);
+    let block_starts = block_starts.filter(|s| start < *s && *s <= end);
+    for window_start in iter::once(start).chain(block_starts) {
+      if let Some(last) = windows.last_mut() {
+        if window_start <= last.start {
+          continue;
+        }
+        last.end = window_start - 1;
+      }
+      windows.push(TimeWindow {
+        start: window_start,
+        end,
+        first: windows.is_empty(),
+      });
+    }
+    windows
+  }
+
+  /// Runs the given callback on each value change in the given time window.
+  ///
+  /// Value changes before the start of the first window are included
+  /// as well, the same as [`for_each_block`](Reader::for_each_block).
+  pub(crate) fn for_each_in_window<F>(&mut self, window: TimeWindow, mut callback: F) -> Result<()>
+  where
+    F: FnMut(u64, Handle, &[u8], bool),
+  {
+    let TimeWindow { start, end, first } = window;
+    self.apply_time_range_limit(Some((start, end)));
+    let ret = self.try_for_each_block(|time, handle, value, var_len| {
+      if time > end {
+        return ControlFlow::Break(());
+      }
+      if first || time >= start {
+        callback(time, handle, value, var_len);
+      }
+      ControlFlow::Continue(())
+    });
+    self.apply_time_range_limit(self.time_range);
+    ret.map(|_| ())
   }
 
   /// Dumps the content of waveform as VCD format to the given file
@@ -228,22 +533,33 @@ impl Reader {
   where
     P: AsRef<Path>,
   {
-    let ret = if let Some(path) = path {
-      let path = path.to_str()?.into_cstring()?;
-      unsafe { capi::fstReaderDumpToVcdFile(self.ctx, path.as_ptr()) }
-    } else {
-      unsafe { capi::fstReaderDumpToVcdFile(self.ctx, ptr::null()) }
-    };
-    match ret {
-      0 => Ok(()),
-      _ => Err(Error::InvalidOperation),
+    let path = path.as_ref().map(AsRef::as_ref);
+    if let Some(path) = path
+      && let Err(e) = File::create(path)
+    {
+      return Err(Error::Create(path.into(), e.kind()));
     }
+    self.backend.dump_to_vcd(path)
   }
 }
 
-impl Drop for Reader {
-  fn drop(&mut self) {
-    unsafe { capi::fstReaderClose(self.ctx) }
+/// Converts the given C string into <code>&[str]</code>.
+fn to_str(s: &CStr) -> Result<&str> {
+  s.to_str().map_err(|e| Error::InvalidUtf8Str(Some(e)))
+}
+
+/// Adds the missing `r` prefix to the given value if the variable type is real,
+/// since the backends omit it for values read from the initial values of a block.
+fn fix_real_value(ty: VarType, value: &mut Vec<u8>) {
+  let is_real = matches!(
+    ty,
+    var_type::VCD_REAL
+      | var_type::VCD_REAL_PARAMETER
+      | var_type::VCD_REALTIME
+      | var_type::SV_SHORTREAL
+  );
+  if is_real && value.first().is_some_and(|b| *b != b'r') {
+    value.insert(0, b'r');
   }
 }
 
@@ -253,15 +569,27 @@ impl Drop for Reader {
 /// method on [`Reader`].
 #[derive(Debug)]
 pub struct Hiers<'a> {
-  ctx: *mut raw::c_void,
-  phantom: PhantomData<&'a ()>,
+  hiers: <ReaderBackend as Backend>::Hiers<'a>,
+  /// Supplemental type information for the next variable.
+  supplemental: Option<Supplemental>,
 }
 
 impl<'a> Iterator for Hiers<'a> {
   type Item = Hier<'a>;
 
   fn next(&mut self) -> Option<Self::Item> {
-    unsafe { capi::fstReaderIterateHier(self.ctx).as_ref() }.map(Hier::new)
+    let mut hier = self.hiers.next().map(Hier::new)?;
+    match &mut hier {
+      Hier::AttrBegin(a) => {
+        if let Ok(Some(s)) = a.supplemental() {
+          self.supplemental = Some(s);
+        }
+      }
+      Hier::Var(v) => v.1 = self.supplemental.take(),
+      Hier::Scope(_) | Hier::Upscope => self.supplemental = None,
+      Hier::AttrEnd => {}
+    }
+    Some(hier)
   }
 }
 
@@ -281,58 +609,57 @@ pub enum Hier<'a> {
 }
 
 impl<'a> Hier<'a> {
-  /// Creates a new hierarchy.
-  fn new(hier: &'a capi::fstHier) -> Self {
-    match hier.htyp as capi::fstHierType {
-      capi::fstHierType_FST_HT_SCOPE => Self::Scope(Scope(unsafe { &hier.u.scope })),
-      capi::fstHierType_FST_HT_UPSCOPE => Self::Upscope,
-      capi::fstHierType_FST_HT_VAR => Self::Var(Var(unsafe { &hier.u.var })),
-      capi::fstHierType_FST_HT_ATTRBEGIN => Self::AttrBegin(Attr(unsafe { &hier.u.attr })),
-      capi::fstHierType_FST_HT_ATTREND => Self::AttrEnd,
-      _ => unreachable!(),
+  /// Creates a new hierarchy from the one returned by the backend.
+  fn new(hier: RawHier<'a>) -> Self {
+    match hier {
+      RawHier::Scope(s) => Self::Scope(Scope(s)),
+      RawHier::Upscope => Self::Upscope,
+      RawHier::Var(v) => Self::Var(Var(v, None)),
+      RawHier::AttrBegin(a) => Self::AttrBegin(Attr(a)),
+      RawHier::AttrEnd => Self::AttrEnd,
     }
   }
 }
 
 /// A scope in FST hierarchy.
 #[derive(Debug)]
-pub struct Scope<'a>(&'a capi::fstHier__bindgen_ty_1_fstHierScope);
+pub struct Scope<'a>(RawScope<'a>);
 
 impl<'a> Scope<'a> {
   /// Returns scope type.
   pub fn ty(&self) -> ScopeType {
-    self.0.typ as ScopeType
+    self.0.ty as ScopeType
   }
 
   /// Returns scope name.
   pub fn name(&self) -> Result<&str> {
-    unsafe { (self.0.name, self.0.name_length + 1).to_str() }
+    to_str(&self.0.name)
   }
 
   /// Returns scope name as raw C string.
   pub fn name_raw(&self) -> *const raw::c_char {
-    self.0.name
+    self.0.name.as_ptr()
   }
 
   /// Returns scope component.
   pub fn component(&self) -> Result<&str> {
-    unsafe { (self.0.component, self.0.component_length + 1).to_str() }
+    to_str(&self.0.component)
   }
 
   /// Returns scope component as raw C string.
   pub fn component_raw(&self) -> *const raw::c_char {
-    self.0.component
+    self.0.component.as_ptr()
   }
 }
 
 /// A variable in FST hierarchy.
 #[derive(Debug)]
-pub struct Var<'a>(&'a capi::fstHier__bindgen_ty_1_fstHierVar);
+pub struct Var<'a>(RawVar<'a>, Option<Supplemental>);
 
 impl<'a> Var<'a> {
   /// Returns variable type.
   pub fn ty(&self) -> VarType {
-    self.0.typ as VarType
+    self.0.ty as VarType
   }
 
   /// Returns variable direction.
@@ -342,12 +669,12 @@ impl<'a> Var<'a> {
 
   /// Returns variable name.
   pub fn name(&self) -> Result<&str> {
-    unsafe { (self.0.name, self.0.name_length + 1).to_str() }
+    to_str(&self.0.name)
   }
 
   /// Returns variable name as raw C string.
   pub fn name_raw(&self) -> *const raw::c_char {
-    self.0.name
+    self.0.name.as_ptr()
   }
 
   /// Returns variable length in bits.
@@ -357,23 +684,37 @@ impl<'a> Var<'a> {
 
   /// Returns variable handle.
   pub fn handle(&self) -> Handle {
-    unsafe { Handle(NonZeroU32::new_unchecked(self.0.handle)) }
+    self.0.handle
   }
 
   /// Returns `true` if variable is an alias.
   pub fn is_alias(&self) -> bool {
-    self.0.is_alias() != 0
+    self.0.is_alias
+  }
+
+  /// Returns supplemental type information of the variable,
+  /// which is stored in the preceding
+  /// [`misc_type::SUPVAR`](crate::consts::misc_type::SUPVAR) attribute.
+  pub fn supplemental(&self) -> Option<&Supplemental> {
+    self.1.as_ref()
+  }
+
+  /// Decodes the given value of the current variable.
+  ///
+  /// See [`Value::new`] for details.
+  pub fn decode_value(&self, value: &[u8]) -> Result<Value> {
+    Value::new(self.ty(), value)
   }
 }
 
 /// An attribute in FST hierarchy.
 #[derive(Debug)]
-pub struct Attr<'a>(&'a capi::fstHier__bindgen_ty_1_fstHierAttr);
+pub struct Attr<'a>(RawAttr<'a>);
 
 impl<'a> Attr<'a> {
   /// Returns attribute type.
   pub fn ty(&self) -> AttrType {
-    self.0.typ as AttrType
+    self.0.ty as AttrType
   }
 
   /// Returns attribute subtype.
@@ -388,12 +729,12 @@ impl<'a> Attr<'a> {
 
   /// Returns attribute name.
   pub fn name(&self) -> Result<&str> {
-    unsafe { (self.0.name, self.0.name_length + 1).to_str() }
+    to_str(&self.0.name)
   }
 
   /// Returns attribute name as raw C string.
   pub fn name_raw(&self) -> *const raw::c_char {
-    self.0.name
+    self.0.name.as_ptr()
   }
 
   /// Returns attribute argument.
@@ -414,6 +755,42 @@ impl<'a> Attr<'a> {
   pub fn arg_from_name(&self) -> u64 {
     self.0.arg_from_name
   }
+
+  /// Returns the enumeration table handle if the attribute is
+  /// an enumeration table definition or reference.
+  pub fn enum_handle(&self) -> Option<EnumHandle> {
+    if self.ty() == attr_type::MISC && self.subtype() == misc_type::ENUMTABLE {
+      EnumHandle::new(self.arg() as u32)
+    } else {
+      None
+    }
+  }
+
+  /// Returns supplemental type information if the attribute is
+  /// a [`misc_type::SUPVAR`](crate::consts::misc_type::SUPVAR) attribute.
+  pub fn supplemental(&self) -> Result<Option<Supplemental>> {
+    if self.ty() != attr_type::MISC || self.subtype() != misc_type::SUPVAR {
+      return Ok(None);
+    }
+    let shift = supplemental_data_type::SVT_SHIFT_COUNT;
+    let data_type = self.arg() & supplemental_data_type::ABS_MAX as u64;
+    Ok(Some(Supplemental::new(
+      self.name()?,
+      (self.arg() >> shift) as _,
+      data_type as _,
+    )))
+  }
+
+  /// Parses the enumeration table if the attribute is
+  /// an enumeration table definition.
+  ///
+  /// Returns [`None`] if the attribute is not a definition.
+  pub fn enum_table(&self) -> Result<Option<EnumTable>> {
+    if self.enum_handle().is_none() || self.0.name.is_empty() {
+      return Ok(None);
+    }
+    EnumTable::from_attr_name(self.name()?).map(Some)
+  }
 }
 
 /// An iterator over the variables of an FST waveform.
@@ -466,3 +843,97 @@ impl<'a> Iterator for Vars<'a> {
     None
   }
 }
+
+/// An iterator over the dump activity changes of an FST waveform.
+///
+/// This struct is created by the
+/// [`dump_activity_changes`](Reader::dump_activity_changes)
+/// method on [`Reader`].
+#[derive(Debug)]
+pub struct DumpActivityChanges<'a> {
+  backend: &'a ReaderBackend,
+  index: u32,
+  len: u32,
+}
+
+impl Iterator for DumpActivityChanges<'_> {
+  type Item = (u64, bool);
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.index >= self.len {
+      return None;
+    }
+    let change = self.backend.dump_activity_change(self.index);
+    self.index += 1;
+    Some(change)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    let len = (self.len - self.index) as usize;
+    (len, Some(len))
+  }
+}
+
+impl ExactSizeIterator for DumpActivityChanges<'_> {}
+
+/// A value change of a variable in FST waveform.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValueChange {
+  /// Time of the value change.
+  pub time: u64,
+  /// Handle of the variable.
+  pub handle: Handle,
+  /// The new value.
+  pub value: Box<[u8]>,
+  /// `true` if the variable is a variable-length one.
+  pub var_len: bool,
+}
+
+/// A time window of value changes, in which the value change blocks
+/// can be decoded independently.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TimeWindow {
+  pub start: u64,
+  pub end: u64,
+  /// `true` if this is the first window.
+  pub first: bool,
+}
+
+/// An iterator over the value changes of an FST waveform.
+///
+/// This struct is created by the [`changes`](Reader::changes)
+/// method on [`Reader`].
+#[derive(Debug)]
+pub struct Changes<'a> {
+  reader: &'a mut Reader,
+  windows: VecDeque<TimeWindow>,
+  changes: VecDeque<ValueChange>,
+}
+
+impl Iterator for Changes<'_> {
+  type Item = Result<ValueChange>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Some(change) = self.changes.pop_front() {
+        return Some(Ok(change));
+      }
+      let window = self.windows.pop_front()?;
+      let changes = &mut self.changes;
+      let ret = self
+        .reader
+        .for_each_in_window(window, |time, handle, value, var_len| {
+          changes.push_back(ValueChange {
+            time,
+            handle,
+            value: value.into(),
+            var_len,
+          })
+        });
+      if let Err(e) = ret {
+        self.windows.clear();
+        return Some(Err(e));
+      }
+    }
+  }
+}
@@ -1,3 +1,5 @@
+use crate::consts::{SupplementalDataType, SupplementalVarType};
+#[cfg(feature = "capi")]
 use crate::utils::IntoCHandle;
 use std::fmt;
 use std::num::NonZeroU32;
@@ -27,6 +29,71 @@ impl fmt::Display for Handle {
   }
 }
 
+/// Enumeration table handle, which is actually a 32-bit non-zero unsigned integer.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EnumHandle(pub(crate) NonZeroU32);
+
+impl EnumHandle {
+  /// Creates a new enumeration table handle.
+  pub(crate) fn new(n: u32) -> Option<Self> {
+    NonZeroU32::new(n).map(Self)
+  }
+}
+
+impl From<EnumHandle> for u32 {
+  /// Creates a non-zero unsigned integer from the enumeration table handle.
+  fn from(value: EnumHandle) -> Self {
+    value.0.into()
+  }
+}
+
+impl fmt::Display for EnumHandle {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    self.0.fmt(f)
+  }
+}
+
+/// Supplemental type information of a variable,
+/// like VHDL signal kinds and data types.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Supplemental {
+  type_name: String,
+  var_type: SupplementalVarType,
+  data_type: SupplementalDataType,
+}
+
+impl Supplemental {
+  /// Creates a new supplemental type information.
+  pub fn new(
+    type_name: &str,
+    var_type: SupplementalVarType,
+    data_type: SupplementalDataType,
+  ) -> Self {
+    Self {
+      type_name: type_name.into(),
+      var_type,
+      data_type,
+    }
+  }
+
+  /// Returns type name, like `std_logic_vector`, may be empty.
+  pub fn type_name(&self) -> &str {
+    &self.type_name
+  }
+
+  /// Returns supplemental variable type.
+  pub fn var_type(&self) -> SupplementalVarType {
+    self.var_type
+  }
+
+  /// Returns supplemental data type.
+  pub fn data_type(&self) -> SupplementalDataType {
+    self.data_type
+  }
+}
+
+#[cfg(feature = "capi")]
 impl IntoCHandle for Option<Handle> {
   fn into_handle(self) -> u32 {
     match self {
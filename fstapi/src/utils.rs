@@ -1,14 +1,16 @@
 use crate::{Error, Result};
-use std::ffi::{CStr, CString, c_char};
+use std::ffi::CString;
+#[cfg(feature = "capi")]
 use std::path::Path;
-use std::slice;
 
 /// Trait for converting [`Path`] into string.
+#[cfg(feature = "capi")]
 pub(crate) trait PathToStr<'a> {
   /// Converts to <code>&[str]</code>.
   fn to_str(&'a self) -> Result<&'a str>;
 }
 
+#[cfg(feature = "capi")]
 impl<'a, P> PathToStr<'a> for P
 where
   P: AsRef<Path>,
@@ -18,30 +20,6 @@ where
   }
 }
 
-/// Trait for converting raw C string into string.
-pub(crate) trait RawToStr {
-  /// Converts to <code>&[str]</code>.
-  unsafe fn to_str<'a>(self) -> Result<&'a str>;
-}
-
-impl RawToStr for *const c_char {
-  unsafe fn to_str<'a>(self) -> Result<&'a str> {
-    unsafe { CStr::from_ptr(self) }
-      .to_str()
-      .map_err(|e| Error::InvalidUtf8Str(Some(e)))
-  }
-}
-
-impl RawToStr for (*const c_char, u32) {
-  unsafe fn to_str<'a>(self) -> Result<&'a str> {
-    let bytes = unsafe { slice::from_raw_parts(self.0 as *const u8, self.1 as usize) };
-    CStr::from_bytes_with_nul(bytes)
-      .map_err(Error::CStrConv)?
-      .to_str()
-      .map_err(|e| Error::InvalidUtf8Str(Some(e)))
-  }
-}
-
 /// Trait for converting bytes into [`CString`].
 pub(crate) trait IntoCString {
   /// Converts into [`CString`].
@@ -58,6 +36,7 @@ where
 }
 
 /// Trait for converting `Option<Handle>` into handle for C API.
+#[cfg(feature = "capi")]
 pub(crate) trait IntoCHandle {
   /// Converts into C handle.
   fn into_handle(self) -> u32;
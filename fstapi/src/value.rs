@@ -0,0 +1,375 @@
+use crate::consts::{VarType, var_type};
+use crate::{Error, Result};
+use std::fmt;
+
+/// A single bit of logic value, supports all values of VHDL `std_logic`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Logic {
+  /// Logic zero, `0`.
+  Zero,
+  /// Logic one, `1`.
+  One,
+  /// Unknown, `x`.
+  X,
+  /// High impedance, `z`.
+  Z,
+  /// Uninitialized, `u`.
+  U,
+  /// Weak unknown, `w`.
+  W,
+  /// Weak zero, `l`.
+  L,
+  /// Weak one, `h`.
+  H,
+  /// Don't care, `-`.
+  DontCare,
+}
+
+impl Logic {
+  /// Creates a new logic bit from the given character.
+  ///
+  /// Returns [`None`] if the character is not a valid logic value.
+  pub fn from_char(c: u8) -> Option<Self> {
+    match c.to_ascii_lowercase() {
+      b'0' => Some(Self::Zero),
+      b'1' => Some(Self::One),
+      b'x' => Some(Self::X),
+      b'z' => Some(Self::Z),
+      b'u' => Some(Self::U),
+      b'w' => Some(Self::W),
+      b'l' => Some(Self::L),
+      b'h' => Some(Self::H),
+      b'-' => Some(Self::DontCare),
+      _ => None,
+    }
+  }
+
+  /// Returns the character of the current logic bit.
+  pub fn to_char(self) -> u8 {
+    match self {
+      Self::Zero => b'0',
+      Self::One => b'1',
+      Self::X => b'x',
+      Self::Z => b'z',
+      Self::U => b'u',
+      Self::W => b'w',
+      Self::L => b'l',
+      Self::H => b'h',
+      Self::DontCare => b'-',
+    }
+  }
+
+  /// Returns the boolean value of the current logic bit.
+  ///
+  /// Weak zero and weak one are treated as `false` and `true`.
+  /// Returns [`None`] if the value is unknown.
+  pub fn to_bool(self) -> Option<bool> {
+    match self {
+      Self::Zero | Self::L => Some(false),
+      Self::One | Self::H => Some(true),
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for Logic {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.to_char() as char)
+  }
+}
+
+/// A vector of logic bits, the first bit is the most significant bit.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LogicVec(Box<[Logic]>);
+
+impl LogicVec {
+  /// Creates a new logic vector from the given value,
+  /// like the values passed to the callback of
+  /// [`for_each_block`](crate::Reader::for_each_block).
+  pub fn new(value: &[u8]) -> Result<Self> {
+    value
+      .iter()
+      .map(|c| Logic::from_char(*c))
+      .collect::<Option<_>>()
+      .map(Self)
+      .ok_or(Error::InvalidValue)
+  }
+
+  /// Returns the number of bits.
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Returns `true` if there is no bit in the vector.
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// Returns all bits, the first bit is the most significant bit.
+  pub fn bits(&self) -> &[Logic] {
+    &self.0
+  }
+
+  /// Returns the bit at the given index, index 0 is the least significant bit.
+  pub fn bit(&self, index: usize) -> Option<Logic> {
+    self.0.iter().rev().nth(index).copied()
+  }
+
+  /// Returns `true` if all bits are known.
+  pub fn is_known(&self) -> bool {
+    self.0.iter().all(|b| b.to_bool().is_some())
+  }
+
+  /// Returns `true` if there is any unknown (`x`, `u` or `w`) bit.
+  pub fn has_x(&self) -> bool {
+    self
+      .0
+      .iter()
+      .any(|b| matches!(b, Logic::X | Logic::U | Logic::W))
+  }
+
+  /// Returns `true` if there is any high impedance bit.
+  pub fn has_z(&self) -> bool {
+    self.0.contains(&Logic::Z)
+  }
+
+  /// Converts to an unsigned integer.
+  ///
+  /// Returns [`None`] if there are unknown bits,
+  /// or the value does not fit in [`u64`].
+  pub fn to_u64(&self) -> Option<u64> {
+    self.to_u128().and_then(|v| v.try_into().ok())
+  }
+
+  /// Converts to an unsigned integer.
+  ///
+  /// Returns [`None`] if there are unknown bits,
+  /// or the value does not fit in [`u128`].
+  pub fn to_u128(&self) -> Option<u128> {
+    let bits = self.significant_bits(false)?;
+    if bits.len() > u128::BITS as usize {
+      return None;
+    }
+    Some(bits.iter().fold(0, |v, b| (v << 1) | (*b as u128)))
+  }
+
+  /// Converts to a signed integer, the most significant bit is the sign bit.
+  ///
+  /// Returns [`None`] if there are unknown bits,
+  /// or the value does not fit in [`i64`].
+  pub fn to_i64(&self) -> Option<i64> {
+    let sign = self.0.first()?.to_bool()?;
+    let bits = self.significant_bits(sign)?;
+    if bits.len() > i64::BITS as usize {
+      return None;
+    }
+    let init = if sign { -1 } else { 0 };
+    Some(bits.iter().fold(init, |v, b| (v << 1) | (*b as i64)))
+  }
+
+  /// Converts to an unsigned integer with arbitrary width,
+  /// in big-endian byte order.
+  ///
+  /// Returns [`None`] if there are unknown bits.
+  pub fn to_bytes_be(&self) -> Option<Vec<u8>> {
+    let bits = self.significant_bits(false)?;
+    let mut bytes = vec![0; bits.len().div_ceil(8)];
+    for (i, b) in bits.iter().rev().enumerate() {
+      let len = bytes.len();
+      bytes[len - 1 - i / 8] |= (*b as u8) << (i % 8);
+    }
+    Some(bytes)
+  }
+
+  /// Converts to a big unsigned integer.
+  ///
+  /// Returns [`None`] if there are unknown bits.
+  #[cfg(feature = "num-bigint")]
+  pub fn to_biguint(&self) -> Option<num_bigint::BigUint> {
+    self
+      .to_bytes_be()
+      .map(|b| num_bigint::BigUint::from_bytes_be(&b))
+  }
+
+  /// Converts to a lowercase hexadecimal string.
+  ///
+  /// A hexadecimal digit is `z` if all of its bits are high impedance,
+  /// or `x` if any of its bits is not known.
+  pub fn to_hex_string(&self) -> String {
+    self
+      .0
+      .rchunks(4)
+      .rev()
+      .map(|ds| {
+        if ds.iter().all(|d| *d == Logic::Z) {
+          return 'z';
+        }
+        ds.iter()
+          .try_fold(0, |ans, d| d.to_bool().map(|b| (ans << 1) | b as u32))
+          .map_or('x', |d| char::from_digit(d, 16).unwrap())
+      })
+      .collect()
+  }
+
+  /// Returns known bits with the leading bits that equal to `lead` removed.
+  ///
+  /// Returns [`None`] if there are unknown bits.
+  fn significant_bits(&self, lead: bool) -> Option<Vec<bool>> {
+    let bits = self
+      .0
+      .iter()
+      .map(|b| b.to_bool())
+      .collect::<Option<Vec<_>>>()?;
+    let start = bits.iter().position(|b| *b != lead).unwrap_or(bits.len());
+    Some(bits[start..].to_vec())
+  }
+}
+
+impl fmt::Display for LogicVec {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for b in self.0.iter() {
+      write!(f, "{b}")?;
+    }
+    Ok(())
+  }
+}
+
+/// Decoded value of a variable.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+  /// Unsigned bit vector.
+  Bits(LogicVec),
+  /// Signed integer, in two's complement.
+  Int(LogicVec),
+  /// Real number.
+  Real(f64),
+  /// String.
+  String(String),
+}
+
+impl Value {
+  /// Decodes the given value of a variable of the given type.
+  ///
+  /// The value should be in the same format as the values passed to
+  /// the callback of [`for_each_block`](crate::Reader::for_each_block)
+  /// or returned by [`value_at`](crate::Reader::value_at).
+  /// Native doubles are not supported, use
+  /// [`from_native_double`](Value::from_native_double) instead.
+  pub fn new(ty: VarType, value: &[u8]) -> Result<Self> {
+    match ty {
+      var_type::VCD_REAL
+      | var_type::VCD_REAL_PARAMETER
+      | var_type::VCD_REALTIME
+      | var_type::SV_SHORTREAL => {
+        let value = value.strip_prefix(b"r").unwrap_or(value);
+        std::str::from_utf8(value)
+          .map_err(|e| Error::InvalidUtf8Str(Some(e)))?
+          .parse()
+          .map(Self::Real)
+          .map_err(|_| Error::InvalidValue)
+      }
+      var_type::GEN_STRING => std::str::from_utf8(value)
+        .map(|s| Self::String(s.into()))
+        .map_err(|e| Error::InvalidUtf8Str(Some(e))),
+      var_type::VCD_INTEGER
+      | var_type::SV_INT
+      | var_type::SV_SHORTINT
+      | var_type::SV_LONGINT
+      | var_type::SV_BYTE => LogicVec::new(value).map(Self::Int),
+      _ => LogicVec::new(value).map(Self::Bits),
+    }
+  }
+
+  /// Decodes the given native double value, which is passed to the callback of
+  /// [`for_each_block`](crate::Reader::for_each_block) when
+  /// [`set_native_doubles_on_callback`](crate::Reader::set_native_doubles_on_callback)
+  /// is enabled.
+  pub fn from_native_double(value: &[u8]) -> Result<Self> {
+    value
+      .try_into()
+      .map(|b| Self::Real(f64::from_ne_bytes(b)))
+      .map_err(|_| Error::InvalidValue)
+  }
+
+  /// Returns the logic vector if the value is a bit vector or an integer.
+  pub fn as_logic_vec(&self) -> Option<&LogicVec> {
+    match self {
+      Self::Bits(v) | Self::Int(v) => Some(v),
+      _ => None,
+    }
+  }
+
+  /// Returns the string if the value is a string.
+  pub fn as_str(&self) -> Option<&str> {
+    match self {
+      Self::String(s) => Some(s),
+      _ => None,
+    }
+  }
+
+  /// Converts to an unsigned integer.
+  ///
+  /// Returns [`None`] if the value is not a bit vector or a non-negative
+  /// integer, or can not be represented in [`u64`].
+  pub fn to_u64(&self) -> Option<u64> {
+    self.to_u128().and_then(|v| v.try_into().ok())
+  }
+
+  /// Converts to a signed integer.
+  ///
+  /// Returns [`None`] if the value is not a bit vector or an integer,
+  /// or can not be represented in [`i64`].
+  pub fn to_i64(&self) -> Option<i64> {
+    match self {
+      Self::Bits(v) => v.to_u64().and_then(|v| v.try_into().ok()),
+      Self::Int(v) => v.to_i64(),
+      _ => None,
+    }
+  }
+
+  /// Converts to an unsigned integer.
+  ///
+  /// Returns [`None`] if the value is not a bit vector or a non-negative
+  /// integer, or can not be represented in [`u128`].
+  pub fn to_u128(&self) -> Option<u128> {
+    match self {
+      Self::Bits(v) => v.to_u128(),
+      Self::Int(v) => v.to_i64().and_then(|v| v.try_into().ok()),
+      _ => None,
+    }
+  }
+
+  /// Converts to a big unsigned integer.
+  ///
+  /// Returns [`None`] if the value is not a bit vector,
+  /// or there are unknown bits.
+  #[cfg(feature = "num-bigint")]
+  pub fn to_biguint(&self) -> Option<num_bigint::BigUint> {
+    match self {
+      Self::Bits(v) => v.to_biguint(),
+      _ => None,
+    }
+  }
+
+  /// Converts to a real number.
+  ///
+  /// Returns [`None`] if the value is a string, or there are unknown bits.
+  pub fn to_f64(&self) -> Option<f64> {
+    match self {
+      Self::Bits(v) => v.to_u128().map(|v| v as f64),
+      Self::Int(v) => v.to_i64().map(|v| v as f64),
+      Self::Real(r) => Some(*r),
+      Self::String(_) => None,
+    }
+  }
+}
+
+impl fmt::Display for Value {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::Bits(v) | Self::Int(v) => write!(f, "{v}"),
+      Self::Real(r) => write!(f, "{r}"),
+      Self::String(s) => write!(f, "{s}"),
+    }
+  }
+}
@@ -1,7 +1,8 @@
-use crate::consts::{AttrType, FileType, ScopeType, VarDir, VarType, WriterPackType};
-use crate::types::Handle;
+use crate::consts::{AttrType, FileType, ScopeType, VarDir, VarType, WriterPackType, var_type};
+use crate::types::{EnumHandle, Handle, Supplemental};
 use crate::utils::*;
 use crate::{Error, Result, capi};
+use std::fs::File;
 use std::os::raw;
 use std::path::Path;
 
@@ -10,6 +11,11 @@ use std::path::Path;
 pub struct Writer {
   /// Non-null context pointer.
   ctx: *mut raw::c_void,
+  /// The last emitted time.
+  time: Option<u64>,
+  /// Value lengths of variables, indexed by handle minus one,
+  /// zero for variable-length variables.
+  var_lens: Vec<u32>,
 }
 
 impl Writer {
@@ -18,12 +24,21 @@ impl Writer {
   where
     P: AsRef<Path>,
   {
+    // Check if the file can be created first,
+    // since the C API does not report the reason of failure.
+    if let Err(e) = File::create(&path) {
+      return Err(Error::Create(path.as_ref().into(), e.kind()));
+    }
     let path = path.to_str()?.into_cstring()?;
     let ctx = unsafe { capi::fstWriterCreate(path.as_ptr(), use_compressed_hier as raw::c_int) };
     if ctx.is_null() {
       Err(Error::ContextCreate)
     } else {
-      Ok(Self { ctx })
+      Ok(Self {
+        ctx,
+        time: None,
+        var_lens: Vec::new(),
+      })
     }
   }
 
@@ -76,6 +91,16 @@ impl Writer {
     self
   }
 
+  /// Sets dump size limit in bytes.
+  ///
+  /// Once the limit is reached, value changes will no longer be written,
+  /// and [`emit_time_change`](Writer::emit_time_change) will return
+  /// [`Error::DumpSizeLimitReached`].
+  pub fn dump_size_limit(self, limit: u64) -> Self {
+    unsafe { capi::fstWriterSetDumpSizeLimit(self.ctx, limit) };
+    self
+  }
+
   /// Sets timescale.
   pub fn timescale(self, timescale: i32) -> Self {
     unsafe { capi::fstWriterSetTimescale(self.ctx, timescale) };
@@ -125,6 +150,90 @@ impl Writer {
     unsafe { capi::fstWriterSetAttrEnd(self.ctx) }
   }
 
+  /// Sets environment variable attribute.
+  pub fn set_env_var(&mut self, env_var: &str) -> Result<()> {
+    let env_var = env_var.into_cstring()?;
+    unsafe { capi::fstWriterSetEnvVar(self.ctx, env_var.as_ptr()) };
+    Ok(())
+  }
+
+  /// Sets source stem attribute, which is the source location
+  /// of the next scope or variable.
+  ///
+  /// The path will be resolved to the real path if `use_realpath` is `true`.
+  pub fn set_source_stem(&mut self, path: &str, line: u32, use_realpath: bool) -> Result<()> {
+    let path = path.into_cstring()?;
+    unsafe {
+      capi::fstWriterSetSourceStem(self.ctx, path.as_ptr(), line, use_realpath as raw::c_uint)
+    };
+    Ok(())
+  }
+
+  /// Sets source instantiation stem attribute, which is the location
+  /// where the next scope is instantiated.
+  ///
+  /// The path will be resolved to the real path if `use_realpath` is `true`.
+  pub fn set_source_instantiation_stem(
+    &mut self,
+    path: &str,
+    line: u32,
+    use_realpath: bool,
+  ) -> Result<()> {
+    let path = path.into_cstring()?;
+    unsafe {
+      capi::fstWriterSetSourceInstantiationStem(
+        self.ctx,
+        path.as_ptr(),
+        line,
+        use_realpath as raw::c_uint,
+      )
+    };
+    Ok(())
+  }
+
+  /// Creates a new enumeration table with the given name and
+  /// elements in `(literal, value)` form.
+  ///
+  /// Values are binary strings, like `"0110"`, and will be padded with
+  /// leading zeros to at least `min_val_bits` bits.
+  ///
+  /// Returns [`Error::InvalidOperation`] if there is no element.
+  pub fn create_enum_table(
+    &mut self,
+    name: &str,
+    min_val_bits: u32,
+    elems: &[(&str, &str)],
+  ) -> Result<EnumHandle> {
+    let name = name.into_cstring()?;
+    let literals = elems
+      .iter()
+      .map(|(l, _)| l.into_cstring())
+      .collect::<Result<Vec<_>>>()?;
+    let values = elems
+      .iter()
+      .map(|(_, v)| v.into_cstring())
+      .collect::<Result<Vec<_>>>()?;
+    let mut literal_ptrs: Vec<_> = literals.iter().map(|l| l.as_ptr()).collect();
+    let mut value_ptrs: Vec<_> = values.iter().map(|v| v.as_ptr()).collect();
+    EnumHandle::new(unsafe {
+      capi::fstWriterCreateEnumTable(
+        self.ctx,
+        name.as_ptr(),
+        elems.len() as u32,
+        min_val_bits,
+        literal_ptrs.as_mut_ptr(),
+        value_ptrs.as_mut_ptr(),
+      )
+    })
+    .ok_or(Error::InvalidOperation)
+  }
+
+  /// Emits reference to the given enumeration table,
+  /// which will be applied to the next variable.
+  pub fn emit_enum_table_ref(&mut self, handle: EnumHandle) {
+    unsafe { capi::fstWriterEmitEnumTableRef(self.ctx, handle.into()) }
+  }
+
   /// Sets scope.
   pub fn set_scope(&mut self, ty: ScopeType, name: &str, component: &str) -> Result<()> {
     let name = name.into_cstring()?;
@@ -148,14 +257,86 @@ impl Writer {
     alias: Option<Handle>,
   ) -> Result<Handle> {
     let name = name.into_cstring()?;
-    Handle::new(unsafe {
+    let handle = unsafe {
       capi::fstWriterCreateVar(self.ctx, ty, dir, len, name.as_ptr(), alias.into_handle())
-    })
-    .ok_or(Error::InvalidOperation)
+    };
+    self.add_var(ty, len, handle)
+  }
+
+  /// Creates a new variable with supplemental type information.
+  ///
+  /// The information can be read back by [`Var::supplemental`](crate::Var::supplemental).
+  pub fn create_var2(
+    &mut self,
+    ty: VarType,
+    dir: VarDir,
+    len: u32,
+    name: &str,
+    alias: Option<Handle>,
+    supplemental: &Supplemental,
+  ) -> Result<Handle> {
+    let name = name.into_cstring()?;
+    let type_name = supplemental.type_name().into_cstring()?;
+    let handle = unsafe {
+      capi::fstWriterCreateVar2(
+        self.ctx,
+        ty,
+        dir,
+        len,
+        name.as_ptr(),
+        alias.into_handle(),
+        type_name.as_ptr(),
+        supplemental.var_type(),
+        supplemental.data_type(),
+      )
+    };
+    self.add_var(ty, len, handle)
+  }
+
+  /// Records the value length of the variable of the given handle
+  /// returned by the C API, if it is not an alias.
+  fn add_var(&mut self, ty: VarType, len: u32, handle: u32) -> Result<Handle> {
+    let handle = Handle::new(handle).ok_or(Error::InvalidOperation)?;
+    if u32::from(handle) as usize > self.var_lens.len() {
+      // Same as the C API, reals are stored as doubles.
+      let len = match ty {
+        var_type::VCD_REAL
+        | var_type::VCD_REAL_PARAMETER
+        | var_type::VCD_REALTIME
+        | var_type::SV_SHORTREAL => 8,
+        var_type::GEN_STRING => 0,
+        _ => len,
+      };
+      self.var_lens.push(len);
+    }
+    Ok(handle)
+  }
+
+  /// Returns the value length of the variable of the given handle.
+  fn var_len(&self, handle: Handle) -> Result<u32> {
+    self
+      .var_lens
+      .get(u32::from(handle) as usize - 1)
+      .copied()
+      .ok_or(Error::InvalidHandle(handle))
   }
 
   /// Emits value change for the given handle.
+  ///
+  /// Returns [`Error::InvalidLength`] if the length of the value does not
+  /// match the length of the variable, or [`Error::InvalidOperation`] if
+  /// the variable is variable-length.
   pub fn emit_value_change(&mut self, handle: Handle, value: &[u8]) -> Result<()> {
+    match self.var_len(handle)? {
+      0 => return Err(Error::InvalidOperation),
+      len if len as usize != value.len() => {
+        return Err(Error::InvalidLength {
+          expected: len,
+          actual: value.len(),
+        });
+      }
+      _ => {}
+    }
     let ret = unsafe {
       capi::fstWriterEmitValueChange(
         self.ctx,
@@ -171,7 +352,13 @@ impl Writer {
   }
 
   /// Emits vairable-length value change for the given handle.
+  ///
+  /// Returns [`Error::InvalidOperation`] if the variable is not
+  /// variable-length.
   pub fn emit_var_len_value_change(&mut self, handle: Handle, value: &[u8]) -> Result<()> {
+    if self.var_len(handle)? != 0 {
+      return Err(Error::InvalidOperation);
+    }
     let ret = unsafe {
       capi::fstWriterEmitVariableLengthValueChange(
         self.ctx,
@@ -186,12 +373,36 @@ impl Writer {
     }
   }
 
+  /// Emits dump activity change at the current time,
+  /// like `$dumpon` and `$dumpoff` in VCD.
+  pub fn emit_dump_active(&mut self, enable: bool) {
+    unsafe { capi::fstWriterEmitDumpActive(self.ctx, enable as raw::c_int) }
+  }
+
   /// Emits time change.
+  ///
+  /// Returns [`Error::InvalidTime`] if the time is less than the previous time.
   pub fn emit_time_change(&mut self, time: u64) -> Result<()> {
-    match unsafe { capi::fstWriterEmitTimeChange(self.ctx, time) } {
-      0 => Ok(()),
-      _ => Err(Error::InvalidOperation),
+    if self.time.is_some_and(|t| time < t) {
+      return Err(Error::InvalidTime(time));
+    }
+    if unsafe { capi::fstWriterEmitTimeChange(self.ctx, time) } != 0 {
+      return Err(if self.is_dump_size_limit_reached() {
+        Error::DumpSizeLimitReached
+      } else {
+        Error::InvalidOperation
+      });
     }
+    if unsafe { capi::fstWriterGetFseekFailed(self.ctx) } != 0 {
+      return Err(Error::SeekFailed);
+    }
+    self.time = Some(time);
+    Ok(())
+  }
+
+  /// Returns `true` if the dump size limit has been reached.
+  pub fn is_dump_size_limit_reached(&self) -> bool {
+    unsafe { capi::fstWriterGetDumpSizeLimitReached(self.ctx) != 0 }
   }
 
   /// Flushes the content of the current writer to file.
@@ -0,0 +1,139 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Block, Reader, Writer, WriterPackType, block_type, writer_pack_type};
+use fstapi::{scope_type, var_dir, var_type};
+use std::fs;
+
+/// Returns metadata of all blocks of the given file.
+fn blocks(file: &TempFile) -> Vec<Block> {
+  Reader::open(file.path()).unwrap().blocks().unwrap()
+}
+
+/// Returns value change blocks of the waveform written by [`write_blocks`]
+/// with the given pack type.
+fn value_change_blocks(name: &str, pack_type: WriterPackType) -> Vec<Block> {
+  let file = TempFile::new(name);
+  write_blocks_to(
+    Writer::create(file.path(), true)
+      .unwrap()
+      .pack_type(pack_type),
+  );
+  blocks(&file)
+    .into_iter()
+    .filter(Block::is_value_change)
+    .collect()
+}
+
+#[test]
+fn blocks_of_waveform() {
+  let file = TempFile::new("blocks-of-waveform");
+  write_blocks(file.path());
+  let reader = Reader::open(file.path()).unwrap();
+  let blocks = reader.blocks().unwrap();
+  let types: Vec<_> = blocks.iter().map(|b| b.ty).collect();
+  let vc = block_type::VCDATA_DYN_ALIAS2;
+  let expected = [
+    block_type::HDR,
+    vc,
+    vc,
+    vc,
+    block_type::GEOM,
+    block_type::HIER,
+  ];
+  assert_eq!(types, expected);
+
+  // Blocks cover the whole file.
+  let mut offset = 0;
+  for block in &blocks {
+    assert_eq!(block.offset, offset);
+    assert!(block.uncompressed_length > 0);
+    offset += block.length;
+  }
+  assert_eq!(offset, fs::metadata(file.path()).unwrap().len());
+
+  // Time ranges of value change blocks.
+  let vc_blocks: Vec<_> = blocks.iter().filter(|b| b.is_value_change()).collect();
+  assert_eq!(vc_blocks.len() as u64, reader.value_change_block_count());
+  let mut start = 0;
+  for (block, end) in vc_blocks.iter().zip(BLOCK_ENDS) {
+    assert_eq!(block.time_range, Some((start, end)));
+    start = end;
+  }
+  for block in blocks.iter().filter(|b| !b.is_value_change()) {
+    assert_eq!(block.time_range, None);
+    assert_eq!(block.pack_type, None);
+  }
+}
+
+#[test]
+fn pack_types() {
+  for (name, pack_type) in [
+    ("blocks-pack-type-zlib", writer_pack_type::ZLIB),
+    ("blocks-pack-type-fastlz", writer_pack_type::FASTLZ),
+    ("blocks-pack-type-lz4", writer_pack_type::LZ4),
+  ] {
+    let blocks = value_change_blocks(name, pack_type);
+    assert_eq!(blocks.len(), BLOCK_ENDS.len(), "{name}");
+    for block in blocks {
+      assert_eq!(block.pack_type, Some(pack_type), "{name}");
+    }
+  }
+}
+
+#[test]
+fn hierarchy_blocks() {
+  // The hierarchy is compressed by LZ4 if value changes are.
+  let file = TempFile::new("blocks-hierarchy-lz4");
+  write_blocks_to(
+    Writer::create(file.path(), true)
+      .unwrap()
+      .pack_type(writer_pack_type::LZ4),
+  );
+  let ty = blocks(&file).last().unwrap().ty;
+  assert!(matches!(ty, block_type::HIER_LZ4 | block_type::HIER_LZ4DUO));
+
+  // The uncompressed hierarchy is written to a separate file.
+  let file = TempFile::new("blocks-hierarchy-uncompressed");
+  write_blocks_to(Writer::create(file.path(), false).unwrap());
+  let types: Vec<_> = blocks(&file).into_iter().map(|b| b.ty).collect();
+  assert_eq!(types.last(), Some(&block_type::GEOM));
+  assert!(!types.contains(&block_type::HIER));
+}
+
+#[test]
+fn gzip_wrapped() {
+  let file = TempFile::new("blocks-gzip-wrapped");
+  write_blocks_to(
+    Writer::create(file.path(), true)
+      .unwrap()
+      .repack_on_close(true),
+  );
+  let blocks = blocks(&file);
+  assert_eq!(blocks.len(), 1);
+  assert_eq!(blocks[0].ty, block_type::ZWRAPPER);
+  assert_eq!(blocks[0].length, fs::metadata(file.path()).unwrap().len());
+  // The wrapped waveform is larger than the wrapper.
+  assert!(blocks[0].uncompressed_length > blocks[0].length);
+}
+
+#[test]
+fn blackout_block() {
+  let file = TempFile::new("blocks-blackout-block");
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  writer.set_upscope();
+  writer.emit_time_change(0).unwrap();
+  writer.emit_value_change(clk, b"0").unwrap();
+  writer.emit_time_change(10).unwrap();
+  writer.emit_dump_active(false);
+  writer.emit_value_change(clk, b"1").unwrap();
+  drop(writer);
+  let types: Vec<_> = blocks(&file).into_iter().map(|b| b.ty).collect();
+  assert!(types.contains(&block_type::BLACKOUT), "{types:?}");
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Reader, ValueChange, Writer};
+
+/// Returns value changes read by the block iterator.
+fn for_each_block(reader: &mut Reader) -> Vec<ValueChange> {
+  let mut changes = Vec::new();
+  reader
+    .for_each_block(|time, handle, value, var_len| {
+      changes.push(ValueChange {
+        time,
+        handle,
+        value: value.into(),
+        var_len,
+      })
+    })
+    .unwrap();
+  changes
+}
+
+#[test]
+fn changes_all() {
+  let file = TempFile::new("changes-all");
+  let vars = write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader.set_mask_all();
+  let changes: Vec<_> = reader.changes().collect::<Result<_, _>>().unwrap();
+  assert_eq!(changes, for_each_block(&mut reader));
+  for change in &changes {
+    let expected = match change.handle {
+      h if h == vars.counter => counter_at(change.time),
+      h if h == vars.toggle => toggle_at(change.time),
+      h if h == vars.string => string_at(change.time),
+      _ => continue,
+    };
+    assert_eq!(*change.value, *expected.as_bytes());
+    assert_eq!(change.var_len, change.handle == vars.string);
+  }
+  let times = changes.iter().filter(|c| c.handle == vars.counter);
+  assert_eq!(times.count() as u64, BLOCK_ENDS[2] / STEP + 1);
+}
+
+#[test]
+fn changes_masked() {
+  let file = TempFile::new("changes-masked");
+  let vars = write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader.clear_mask_all();
+  reader.set_mask(vars.toggle);
+  let changes: Vec<_> = reader.changes().collect::<Result<_, _>>().unwrap();
+  assert_eq!(changes.len() as u64, BLOCK_ENDS[2] / STEP + 1);
+  for change in changes {
+    assert_eq!(change.handle, vars.toggle);
+    assert_eq!(*change.value, *toggle_at(change.time).as_bytes());
+  }
+}
+
+#[test]
+fn changes_time_range() {
+  let file = TempFile::new("changes-time-range");
+  write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader.set_mask_all();
+  let (start, end) = (200, 400);
+  reader.set_time_range_limit(start, end);
+  let changes: Vec<_> = reader.changes().collect::<Result<_, _>>().unwrap();
+  // Value changes before the start may be yielded, and no value change
+  // after the end is yielded.
+  assert!(changes.first().unwrap().time <= start);
+  assert_eq!(changes.last().unwrap().time, end / STEP * STEP);
+  let expected: Vec<_> = for_each_block(&mut reader)
+    .into_iter()
+    .filter(|c| c.time <= end)
+    .collect();
+  assert_eq!(changes, expected);
+  assert_eq!(reader.time_range_limit(), Some((start, end)));
+}
+
+#[test]
+fn changes_gzip_wrapped() {
+  let file = TempFile::new("changes-gzip-wrapped");
+  let writer = Writer::create(file.path(), true).unwrap();
+  let vars = write_blocks_to(writer.repack_on_close(true));
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader.set_mask_all();
+  // Blocks are unknown, so the waveform is decoded as a whole.
+  let changes: Vec<_> = reader.changes().collect::<Result<_, _>>().unwrap();
+  assert_eq!(changes, for_each_block(&mut reader));
+  let times = changes.iter().filter(|c| c.handle == vars.counter);
+  assert_eq!(times.count() as u64, BLOCK_ENDS[2] / STEP + 1);
+}
+
+#[test]
+fn changes_partially_consumed() {
+  let file = TempFile::new("changes-partially-consumed");
+  write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader.set_mask_all();
+  let first: Vec<_> = reader.changes().take(3).collect::<Result<_, _>>().unwrap();
+  assert_eq!(first.len(), 3);
+  // The reader can still be used after dropping the iterator.
+  assert_eq!(reader.time_range_limit(), None);
+  let all = for_each_block(&mut reader);
+  assert_eq!(first, all[..3]);
+  assert_eq!(all.last().unwrap().time, BLOCK_ENDS[2]);
+}
@@ -0,0 +1,132 @@
+//! Helpers shared by integration tests.
+
+#![allow(dead_code)]
+
+use fstapi::{Handle, Writer, var_dir, var_type};
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
+
+/// A temporary waveform file, which is removed when dropped.
+pub struct TempFile(PathBuf);
+
+impl TempFile {
+  /// Creates a new temporary file path with the given name.
+  pub fn new(name: &str) -> Self {
+    let file_name = format!("fstapi-test-{}-{name}.fst", process::id());
+    Self(env::temp_dir().join(file_name))
+  }
+
+  /// Returns the path of the file.
+  pub fn path(&self) -> &Path {
+    &self.0
+  }
+}
+
+impl Drop for TempFile {
+  fn drop(&mut self) {
+    let _ = fs::remove_file(&self.0);
+    // The hierarchy is written to a separate file if it is not compressed.
+    let mut hier = self.0.clone().into_os_string();
+    hier.push(".hier");
+    let _ = fs::remove_file(hier);
+  }
+}
+
+/// End times of value change blocks of the waveform written by [`write_blocks`].
+pub const BLOCK_ENDS: [u64; 3] = [165, 330, 495];
+
+/// End time of the waveform written by [`write_blocks`].
+pub const END_TIME: u64 = 600;
+
+/// Interval between two value changes in the waveform
+/// written by [`write_blocks`].
+pub const STEP: u64 = 15;
+
+/// Handles of variables in the waveform written by [`write_blocks`].
+pub struct BlocksVars {
+  /// 8-bit counter.
+  pub counter: Handle,
+  /// 1-bit toggle.
+  pub toggle: Handle,
+  /// Real number.
+  pub real: Handle,
+  /// Variable-length string.
+  pub string: Handle,
+}
+
+/// Writes a waveform with three value change blocks to the given path.
+///
+/// All variables change every [`STEP`] time units until the end of the last
+/// block, and the values are returned by [`counter_at`], [`toggle_at`],
+/// [`real_at`] and [`string_at`].
+pub fn write_blocks(path: &Path) -> BlocksVars {
+  write_blocks_to(Writer::create(path, true).unwrap())
+}
+
+/// Writes a waveform with three value change blocks by the given writer.
+///
+/// See [`write_blocks`] for details.
+pub fn write_blocks_to(mut writer: Writer) -> BlocksVars {
+  writer
+    .set_scope(fstapi::scope_type::VCD_MODULE, "top", "")
+    .unwrap();
+  let vars = BlocksVars {
+    counter: writer
+      .create_var(var_type::VCD_REG, var_dir::OUTPUT, 8, "counter", None)
+      .unwrap(),
+    toggle: writer
+      .create_var(var_type::VCD_WIRE, var_dir::OUTPUT, 1, "toggle", None)
+      .unwrap(),
+    real: writer
+      .create_var(var_type::VCD_REAL, var_dir::OUTPUT, 64, "real", None)
+      .unwrap(),
+    string: writer
+      .create_var(var_type::GEN_STRING, var_dir::OUTPUT, 0, "string", None)
+      .unwrap(),
+  };
+  writer.set_upscope();
+  for time in (0..=BLOCK_ENDS[2]).step_by(STEP as usize) {
+    writer.emit_time_change(time).unwrap();
+    writer
+      .emit_value_change(vars.counter, counter_at(time).as_bytes())
+      .unwrap();
+    writer
+      .emit_value_change(vars.toggle, toggle_at(time).as_bytes())
+      .unwrap();
+    let real = (step_at(time) as f64 / 2.0).to_ne_bytes();
+    writer.emit_value_change(vars.real, &real).unwrap();
+    writer
+      .emit_var_len_value_change(vars.string, string_at(time).as_bytes())
+      .unwrap();
+    if BLOCK_ENDS.contains(&time) {
+      writer.flush();
+    }
+  }
+  writer.emit_time_change(END_TIME).unwrap();
+  vars
+}
+
+/// Returns the index of the last value change at or before the given time.
+fn step_at(time: u64) -> u64 {
+  time.min(BLOCK_ENDS[2]) / STEP
+}
+
+/// Returns the value of the counter at the given time.
+pub fn counter_at(time: u64) -> String {
+  format!("{:08b}", step_at(time))
+}
+
+/// Returns the value of the toggle at the given time.
+pub fn toggle_at(time: u64) -> String {
+  (step_at(time) % 2).to_string()
+}
+
+/// Returns the value of the real number at the given time.
+pub fn real_at(time: u64) -> String {
+  format!("r{}", step_at(time) as f64 / 2.0)
+}
+
+/// Returns the value of the string at the given time.
+pub fn string_at(time: u64) -> String {
+  format!("s{}", step_at(time))
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::TempFile;
+use fstapi::{Reader, Writer, scope_type, var_dir, var_type};
+
+/// Writes a waveform with the given dump activity changes.
+fn write_dump_activity(file: &TempFile, changes: &[(u64, bool)]) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  writer.set_upscope();
+  let mut changes = changes.iter().peekable();
+  for time in (0..=100).step_by(10) {
+    writer.emit_time_change(time).unwrap();
+    while let Some((_, active)) = changes.next_if(|(t, _)| *t == time) {
+      writer.emit_dump_active(*active);
+    }
+    let value = if time % 20 == 0 { b"0" } else { b"1" };
+    writer.emit_value_change(clk, value).unwrap();
+  }
+}
+
+#[test]
+fn no_dump_activity() {
+  let file = TempFile::new("dump-activity-none");
+  write_dump_activity(&file, &[]);
+  let reader = Reader::open(file.path()).unwrap();
+  assert_eq!(reader.dump_activity_changes().len(), 0);
+  assert_eq!(reader.dump_activity_changes().next(), None);
+}
+
+#[test]
+fn read_back() {
+  let file = TempFile::new("dump-activity-read-back");
+  let changes = [(20, false), (50, true), (70, false), (90, true)];
+  write_dump_activity(&file, &changes);
+  let reader = Reader::open(file.path()).unwrap();
+  let mut iter = reader.dump_activity_changes();
+  assert_eq!(iter.len(), changes.len());
+  iter.next();
+  assert_eq!(iter.size_hint(), (3, Some(3)));
+  let read: Vec<_> = reader.dump_activity_changes().collect();
+  assert_eq!(read, changes);
+}
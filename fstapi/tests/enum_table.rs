@@ -0,0 +1,44 @@
+#![cfg(feature = "capi")]
+
+use fstapi::{EnumTable, Error};
+
+#[test]
+fn parse_attr_name() {
+  let table = EnumTable::from_attr_name("state 3 IDLE RUN DONE 00 01 10").unwrap();
+  assert_eq!(table.name(), "state");
+  let elems: Vec<_> = table
+    .elems()
+    .iter()
+    .map(|(l, v)| (l.as_str(), v.as_str()))
+    .collect();
+  assert_eq!(elems, [("IDLE", "00"), ("RUN", "01"), ("DONE", "10")]);
+}
+
+#[test]
+fn lookup_literals_and_values() {
+  let table = EnumTable::from_attr_name("state 3 IDLE RUN DONE 00 01 10").unwrap();
+  assert_eq!(table.literal(b"01"), Some("RUN"));
+  // Leading zeros are ignored.
+  assert_eq!(table.literal(b"1"), Some("RUN"));
+  assert_eq!(table.literal(b"0010"), Some("DONE"));
+  assert_eq!(table.literal(b"0"), Some("IDLE"));
+  assert_eq!(table.literal(b""), Some("IDLE"));
+  assert_eq!(table.literal(b"11"), None);
+  assert_eq!(table.literal(b"x1"), None);
+  assert_eq!(table.value("DONE"), Some("10"));
+  assert_eq!(table.value("done"), None);
+}
+
+#[test]
+fn invalid_attr_name() {
+  for name in ["", "state", "state3"] {
+    assert!(
+      matches!(EnumTable::from_attr_name(name), Err(Error::InvalidValue)),
+      "{name:?}"
+    );
+  }
+  assert!(matches!(
+    EnumTable::from_attr_name("sta\0te 1 A 0"),
+    Err(Error::CStringConv(_))
+  ));
+}
@@ -0,0 +1,196 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Error, Reader, Writer, scope_type, var_dir, var_type};
+use std::{fs, io};
+
+#[test]
+fn open_missing_file() {
+  let file = TempFile::new("errors-open-missing-file");
+  let err = Reader::open(file.path()).unwrap_err();
+  assert_eq!(
+    err,
+    Error::Open(file.path().into(), io::ErrorKind::NotFound)
+  );
+  let message = format!(
+    "failed to open `{}`, entity not found",
+    file.path().display()
+  );
+  assert_eq!(err.to_string(), message);
+}
+
+#[test]
+fn open_non_fst_file() {
+  let file = TempFile::new("errors-open-non-fst-file");
+  for content in ["", "$date today $end\n"] {
+    fs::write(file.path(), content).unwrap();
+    let err = Reader::open(file.path()).unwrap_err();
+    assert_eq!(err, Error::NotFst(file.path().into()));
+    let message = format!("`{}` is not a valid FST waveform", file.path().display());
+    assert_eq!(err.to_string(), message);
+  }
+}
+
+#[test]
+fn create_in_missing_dir() {
+  let dir = TempFile::new("errors-create-in-missing-dir");
+  let path = dir.path().join("out.fst");
+  let err = Writer::create(&path, true).unwrap_err();
+  assert_eq!(err, Error::Create(path.clone(), io::ErrorKind::NotFound));
+  let message = format!("failed to create `{}`, entity not found", path.display());
+  assert_eq!(err.to_string(), message);
+
+  // Dumping as VCD reports the same error.
+  let file = TempFile::new("errors-create-in-missing-dir-vcd");
+  write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  let err = reader.dump_as_vcd(Some(&path)).unwrap_err();
+  assert_eq!(err, Error::Create(path, io::ErrorKind::NotFound));
+}
+
+#[test]
+fn time_goes_backwards() {
+  let file = TempFile::new("errors-time-goes-backwards");
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  writer.emit_time_change(10).unwrap();
+  writer.emit_value_change(clk, b"0").unwrap();
+  // The same time is allowed.
+  writer.emit_time_change(10).unwrap();
+  let err = writer.emit_time_change(5).unwrap_err();
+  assert_eq!(err, Error::InvalidTime(5));
+  assert_eq!(err.to_string(), "invalid time 5, time can not go backwards");
+  // The writer can still be used.
+  writer.emit_time_change(20).unwrap();
+  writer.emit_value_change(clk, b"1").unwrap();
+}
+
+#[test]
+fn invalid_handle() {
+  let file = TempFile::new("errors-invalid-handle");
+  let vars = write_blocks(file.path());
+
+  // Handles of a waveform with fewer variables.
+  let other = TempFile::new("errors-invalid-handle-other");
+  let mut writer = Writer::create(other.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  writer.set_upscope();
+  writer.emit_time_change(0).unwrap();
+  writer.emit_value_change(clk, b"0").unwrap();
+  let err = writer.emit_value_change(vars.string, b"0").unwrap_err();
+  assert_eq!(err, Error::InvalidHandle(vars.string));
+  assert_eq!(err.to_string(), format!("invalid handle {}", vars.string));
+  let err = writer
+    .emit_var_len_value_change(vars.string, b"s")
+    .unwrap_err();
+  assert_eq!(err, Error::InvalidHandle(vars.string));
+  drop(writer);
+
+  let mut reader = Reader::open(other.path()).unwrap();
+  let err = reader.value_at(vars.string, 0).unwrap_err();
+  assert_eq!(err, Error::InvalidHandle(vars.string));
+}
+
+#[test]
+fn invalid_length() {
+  let file = TempFile::new("errors-invalid-length");
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let data = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 4, "data", None)
+    .unwrap();
+  let temp = writer
+    .create_var(var_type::VCD_REAL, var_dir::OUTPUT, 64, "temp", None)
+    .unwrap();
+  let name = writer
+    .create_var(var_type::GEN_STRING, var_dir::OUTPUT, 0, "name", None)
+    .unwrap();
+  writer.set_upscope();
+  writer.emit_time_change(0).unwrap();
+  let err = writer.emit_value_change(data, b"01").unwrap_err();
+  assert_eq!(
+    err,
+    Error::InvalidLength {
+      expected: 4,
+      actual: 2
+    }
+  );
+  assert_eq!(err.to_string(), "invalid value length 2, expected 4");
+  // Reals are stored as doubles.
+  let err = writer.emit_value_change(temp, b"1.5").unwrap_err();
+  assert_eq!(
+    err,
+    Error::InvalidLength {
+      expected: 8,
+      actual: 3
+    }
+  );
+  // Fixed-length and variable-length value changes can not be mixed.
+  let err = writer.emit_value_change(name, b"abc").unwrap_err();
+  assert_eq!(err, Error::InvalidOperation);
+  let err = writer.emit_var_len_value_change(data, b"0101").unwrap_err();
+  assert_eq!(err, Error::InvalidOperation);
+  // The writer can still be used.
+  writer.emit_value_change(data, b"0101").unwrap();
+  writer
+    .emit_value_change(temp, &1.5f64.to_ne_bytes())
+    .unwrap();
+  writer.emit_var_len_value_change(name, b"abc").unwrap();
+  writer.emit_time_change(10).unwrap();
+  drop(writer);
+
+  let mut reader = Reader::open(file.path()).unwrap();
+  assert_eq!(reader.value_at(data, 0).unwrap(), b"0101");
+}
+
+#[test]
+fn dump_size_limit_reached() {
+  let file = TempFile::new("errors-dump-size-limit-reached");
+  let mut writer = Writer::create(file.path(), true)
+    .unwrap()
+    .dump_size_limit(1);
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  assert!(!writer.is_dump_size_limit_reached());
+  // The limit is checked when a block is flushed.
+  let mut time = 0;
+  let err = loop {
+    if let Err(e) = writer.emit_time_change(time) {
+      break e;
+    }
+    writer.emit_value_change(clk, b"0").unwrap();
+    writer.flush();
+    time += 10;
+  };
+  assert!(time > 0);
+  assert!(writer.is_dump_size_limit_reached());
+  assert_eq!(err, Error::DumpSizeLimitReached);
+  assert_eq!(err.to_string(), "dump size limit reached");
+  drop(writer);
+
+  // Value changes before the limit is reached are kept.
+  let mut reader = Reader::open(file.path()).unwrap();
+  assert_eq!(reader.value_at(clk, 0).unwrap(), b"0");
+}
+
+#[test]
+fn error_sources() {
+  use std::error::Error as _;
+  let file = TempFile::new("errors-error-sources");
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  let err = writer
+    .set_scope(scope_type::VCD_MODULE, "a\0b", "")
+    .unwrap_err();
+  assert!(matches!(err, Error::CStringConv(_)));
+  assert!(err.source().is_some());
+  assert!(err.to_string().starts_with("CString conversion error, "));
+  assert!(Error::InvalidValue.source().is_none());
+}
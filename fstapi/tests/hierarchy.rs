@@ -0,0 +1,138 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::TempFile;
+use fstapi::{Hierarchy, Reader, Writer, attr_type, misc_type, scope_type, var_dir, var_type};
+
+/// Writes a waveform with nested scopes, aliases and enumeration tables.
+fn write_hierarchy(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  let table = writer
+    .create_enum_table("state", 2, &[("IDLE", "0"), ("RUN", "1"), ("DONE", "10")])
+    .unwrap();
+  // A plain variable declared right after the table definition.
+  writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 2, "plain", None)
+    .unwrap();
+  writer
+    .set_scope(scope_type::VCD_MODULE, "top", "Top")
+    .unwrap();
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  writer
+    .set_attr_begin(attr_type::MISC, misc_type::COMMENT, "core", 0)
+    .unwrap();
+  writer.set_attr_end();
+  writer
+    .set_scope(scope_type::VCD_MODULE, "core", "Core")
+    .unwrap();
+  writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", Some(clk))
+    .unwrap();
+  writer.emit_enum_table_ref(table);
+  writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 2, "state", None)
+    .unwrap();
+  writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 2, "next", None)
+    .unwrap();
+  writer.set_upscope();
+  writer.set_upscope();
+  writer.emit_time_change(0).unwrap();
+  writer.emit_value_change(clk, b"0").unwrap();
+  writer.emit_time_change(1).unwrap();
+}
+
+/// Opens the given file and builds the hierarchy.
+fn hierarchy(file: &TempFile) -> Hierarchy {
+  Hierarchy::new(&mut Reader::open(file.path()).unwrap()).unwrap()
+}
+
+#[test]
+fn lookup_by_path() {
+  let file = TempFile::new("hierarchy-lookup-by-path");
+  write_hierarchy(&file);
+  let hier = hierarchy(&file);
+  assert_eq!(hier.num_scopes(), 2);
+  assert_eq!(hier.num_vars(), 5);
+
+  let top = hier.scope_by_path("top").unwrap();
+  let core = hier.scope_by_path("top.core").unwrap();
+  assert_eq!(hier.root_scopes(), [top]);
+  assert_eq!(hier.scope(top).component(), "Top");
+  assert_eq!(hier.scope(core).parent(), Some(top));
+  assert_eq!(hier.scope(top).scopes(), [core]);
+  assert_eq!(hier.scope_path(core), "top.core");
+  assert!(hier.scope_by_path("core").is_none());
+
+  let plain = hier.var_by_path("plain").unwrap();
+  assert_eq!(hier.root_vars(), [plain]);
+  let state = hier.var_by_path("top.core.state").unwrap();
+  assert_eq!(hier.var(state).scope(), Some(core));
+  assert_eq!(hier.var(state).length(), 2);
+  assert_eq!(hier.var_path(state), "top.core.state");
+  assert!(hier.var_by_path("top.state").is_none());
+}
+
+#[test]
+fn lookup_by_handle() {
+  let file = TempFile::new("hierarchy-lookup-by-handle");
+  write_hierarchy(&file);
+  let hier = hierarchy(&file);
+  let clk = hier.var_by_path("top.clk").unwrap();
+  let alias = hier.var_by_path("top.core.clk").unwrap();
+  assert!(!hier.var(clk).is_alias());
+  assert!(hier.var(alias).is_alias());
+  let handle = hier.var(alias).handle();
+  assert_eq!(hier.var(clk).handle(), handle);
+  assert_eq!(hier.var_by_handle(handle), Some(clk));
+  assert_eq!(hier.handle_path(handle).unwrap(), "top.clk");
+}
+
+#[test]
+fn vars_under_scope() {
+  let file = TempFile::new("hierarchy-vars-under-scope");
+  write_hierarchy(&file);
+  let hier = hierarchy(&file);
+  let top = hier.scope_by_path("top").unwrap();
+  let names: Vec<_> = hier.vars_under(top).map(|id| hier.var_path(id)).collect();
+  assert_eq!(
+    names,
+    ["top.clk", "top.core.clk", "top.core.state", "top.core.next"]
+  );
+}
+
+#[test]
+fn attrs_of_scopes() {
+  let file = TempFile::new("hierarchy-attrs-of-scopes");
+  write_hierarchy(&file);
+  let hier = hierarchy(&file);
+  let core = hier.scope_by_path("top.core").unwrap();
+  let attrs = hier.scope(core).attrs();
+  assert_eq!(attrs.len(), 1);
+  assert_eq!(attrs[0].subtype(), misc_type::COMMENT);
+  assert_eq!(attrs[0].name(), "core");
+  assert!(hier.attrs().is_empty());
+}
+
+#[test]
+fn enum_tables_of_vars() {
+  let file = TempFile::new("hierarchy-enum-tables-of-vars");
+  write_hierarchy(&file);
+  let hier = hierarchy(&file);
+  let state = hier.var_by_path("top.core.state").unwrap();
+  let table = hier.var_enum_table(state).unwrap();
+  assert_eq!(table.name(), "state");
+  assert_eq!(hier.enum_literal(state, b"10"), Some("DONE"));
+  assert_eq!(hier.enum_literal(state, b"01"), Some("RUN"));
+  assert_eq!(hier.enum_literal(state, b"11"), None);
+  // Only the variable after the reference uses the table,
+  // the definition is not a reference.
+  for path in ["plain", "top.clk", "top.core.clk", "top.core.next"] {
+    let id = hier.var_by_path(path).unwrap();
+    assert!(hier.var(id).enum_table().is_none(), "{path}");
+    assert!(hier.var(id).attrs().is_empty(), "{path}");
+  }
+}
@@ -0,0 +1,205 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Error, Handle, Reader, ReaderPool};
+use std::ops::ControlFlow;
+use std::{fs, io, thread};
+
+type Change = (u64, Handle, Vec<u8>, bool);
+
+/// Returns value changes of all variables read sequentially
+/// with the given time range limit.
+fn sequential(file: &TempFile, range: Option<(u64, u64)>) -> Vec<Change> {
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader.set_mask_all();
+  if let Some((start, end)) = range {
+    reader.set_time_range_limit(start, end);
+  }
+  let mut changes = Vec::new();
+  reader
+    .for_each_block(|t, h, v, l| changes.push((t, h, v.into(), l)))
+    .unwrap();
+  changes
+}
+
+/// Returns value changes of all variables read by a pool with the given
+/// number of threads and time range limit.
+fn pooled(file: &TempFile, threads: usize, range: Option<(u64, u64)>) -> Vec<Change> {
+  let pool = ReaderPool::open(file.path()).unwrap().threads(threads);
+  let mut changes = Vec::new();
+  pool
+    .for_each_block(
+      |reader| {
+        reader.set_mask_all();
+        if let Some((start, end)) = range {
+          reader.set_time_range_limit(start, end);
+        }
+      },
+      |t, h, v, l| changes.push((t, h, v.into(), l)),
+    )
+    .unwrap();
+  changes
+}
+
+#[test]
+fn pool_same_as_reader() {
+  let file = TempFile::new("pool-same-as-reader");
+  write_blocks(file.path());
+  let expected = sequential(&file, None);
+  assert_eq!(expected.last().unwrap().0, BLOCK_ENDS[2]);
+  for threads in [1, 2, 4] {
+    assert_eq!(pooled(&file, threads, None), expected, "threads: {threads}");
+  }
+}
+
+#[test]
+fn pool_time_range() {
+  let file = TempFile::new("pool-time-range");
+  write_blocks(file.path());
+  for range in [(100, 200), (100, BLOCK_ENDS[0]), (170, 340), (0, END_TIME)] {
+    // The sequential reader may deliver value changes after the end.
+    let expected: Vec<_> = sequential(&file, Some(range))
+      .into_iter()
+      .filter(|(t, _, _, _)| *t <= range.1)
+      .collect();
+    assert!(expected.first().unwrap().0 <= range.0);
+    for threads in [1, 3] {
+      let changes = pooled(&file, threads, Some(range));
+      assert_eq!(changes, expected, "range: {range:?}, threads: {threads}");
+    }
+  }
+}
+
+#[test]
+fn pool_try_for_each_block() {
+  let file = TempFile::new("pool-try-for-each-block");
+  let vars = write_blocks(file.path());
+  let pool = ReaderPool::open(file.path()).unwrap().threads(2);
+  let mut times = Vec::new();
+  let ret = pool
+    .try_for_each_block(
+      |reader| reader.set_mask(vars.counter),
+      |time, _, value, _| {
+        times.push(time);
+        if value == counter_at(200).as_bytes() {
+          ControlFlow::Break(time)
+        } else {
+          ControlFlow::Continue(())
+        }
+      },
+    )
+    .unwrap();
+  assert_eq!(ret, ControlFlow::Break(195));
+  assert_eq!(times, (0..=195).step_by(STEP as usize).collect::<Vec<_>>());
+}
+
+#[test]
+fn pool_fold_blocks() {
+  let file = TempFile::new("pool-fold-blocks");
+  let vars = write_blocks(file.path());
+  let pool = ReaderPool::open(file.path()).unwrap().threads(3);
+  let ranges: Vec<_> = pool
+    .get()
+    .unwrap()
+    .blocks()
+    .unwrap()
+    .into_iter()
+    .filter_map(|b| b.time_range)
+    .collect();
+  let parts = pool
+    .fold_blocks(
+      |reader| reader.set_mask(vars.toggle),
+      Vec::new,
+      |times, time, _, _, _| times.push(time),
+    )
+    .unwrap();
+  // Each part covers one value change block.
+  assert_eq!(parts.len(), ranges.len());
+  for (times, (start, end)) in parts.iter().zip(&ranges) {
+    assert!(!times.is_empty());
+    assert!(times.iter().all(|t| start <= t && t <= end), "{times:?}");
+  }
+  let count = parts.iter().map(Vec::len).sum::<usize>() as u64;
+  assert_eq!(count, BLOCK_ENDS[2] / STEP + 1);
+}
+
+#[test]
+fn pool_open_errors() {
+  let file = TempFile::new("pool-open-errors");
+  assert!(matches!(
+    ReaderPool::open(file.path()),
+    Err(Error::Open(_, io::ErrorKind::NotFound))
+  ));
+  fs::write(file.path(), "not an FST waveform").unwrap();
+  assert!(matches!(
+    ReaderPool::open(file.path()),
+    Err(Error::NotFst(_))
+  ));
+}
+
+#[test]
+fn pool_get_resets_readers() {
+  let file = TempFile::new("pool-get-resets-readers");
+  let vars = write_blocks(file.path());
+  let pool = ReaderPool::open(file.path()).unwrap();
+  assert_eq!(pool.path(), file.path());
+  {
+    let mut reader = pool.get().unwrap();
+    assert!(!reader.mask(vars.counter));
+    assert_eq!(reader.time_range_limit(), None);
+    reader.set_mask_all();
+    reader.set_time_range_limit(100, 200);
+    reader.set_native_doubles_on_callback(true);
+  }
+  // The reader is put back to the pool and reset.
+  let mut reader = pool.get().unwrap();
+  assert!(!reader.mask(vars.counter));
+  assert_eq!(reader.time_range_limit(), None);
+  let mut count = 0;
+  reader.for_each_block(|_, _, _, _| count += 1).unwrap();
+  assert_eq!(count, 0);
+  let real_values = |reader: &mut Reader| {
+    let mut values = Vec::new();
+    reader.set_mask(vars.real);
+    reader
+      .for_each_block(|_, _, v, _| values.push(v.to_vec()))
+      .unwrap();
+    reader.clear_mask_all();
+    values
+  };
+  let mut new_reader = Reader::open(file.path()).unwrap();
+  assert_eq!(real_values(&mut reader), real_values(&mut new_reader));
+  reader.set_mask(vars.toggle);
+  reader.for_each_block(|_, _, _, _| count += 1).unwrap();
+  assert_eq!(count, BLOCK_ENDS[2] / STEP + 1);
+
+  // A detached reader keeps its state.
+  let reader = reader.detach();
+  assert!(reader.mask(vars.toggle));
+}
+
+#[test]
+fn pool_get_from_threads() {
+  let file = TempFile::new("pool-get-from-threads");
+  let vars = write_blocks(file.path());
+  let pool = ReaderPool::open(file.path()).unwrap();
+  thread::scope(|s| {
+    let handles: Vec<_> = (0..4u64)
+      .map(|i| {
+        let pool = &pool;
+        s.spawn(move || {
+          // Hold several readers at the same time.
+          let mut readers: Vec<_> = (0..3).map(|_| pool.get().unwrap()).collect();
+          for (j, reader) in readers.iter_mut().enumerate() {
+            let time = (i * 3 + j as u64) * 40;
+            let value = reader.value_at(vars.counter, time).unwrap();
+            assert_eq!(value, counter_at(time).as_bytes());
+          }
+        })
+      })
+      .collect();
+    handles.into_iter().for_each(|h| h.join().unwrap());
+  });
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::TempFile;
+use fstapi::{Hier, Hierarchy, Reader, Supplemental, Writer};
+use fstapi::{scope_type, supplemental_data_type as sdt, supplemental_var_type as svt, var_dir};
+use fstapi::{var_type, writer_pack_type};
+
+/// Writes a waveform with VHDL variables.
+fn write_vhdl(file: &TempFile, compressed_hier: bool) {
+  let mut writer = Writer::create(file.path(), compressed_hier)
+    .unwrap()
+    .pack_type(writer_pack_type::ZLIB);
+  writer
+    .set_scope(scope_type::VHDL_ARCHITECTURE, "top", "rtl")
+    .unwrap();
+  let [(_, Some(data_s)), (_, Some(count_s)), (_, Some(bits_s)), _] = expected() else {
+    unreachable!()
+  };
+  let data = writer
+    .create_var2(var_type::VCD_REG, var_dir::INPUT, 8, "data", None, &data_s)
+    .unwrap();
+  writer
+    .create_var2(
+      var_type::VCD_INTEGER,
+      var_dir::IMPLICIT,
+      32,
+      "count",
+      None,
+      &count_s,
+    )
+    .unwrap();
+  // An alias with its own supplemental type.
+  writer
+    .create_var2(
+      var_type::VCD_REG,
+      var_dir::INPUT,
+      8,
+      "bits",
+      Some(data),
+      &bits_s,
+    )
+    .unwrap();
+  // A plain variable without supplemental type.
+  writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  writer.set_upscope();
+  writer.emit_time_change(0).unwrap();
+  writer.emit_value_change(data, b"00000000").unwrap();
+  writer.emit_time_change(1).unwrap();
+}
+
+/// Returns paths and supplemental types of variables
+/// written by [`write_vhdl`].
+fn expected() -> [(&'static str, Option<Supplemental>); 4] {
+  [
+    (
+      "top.data",
+      Some(Supplemental::new(
+        "std_logic_vector",
+        svt::VHDL_SIGNAL,
+        sdt::VHDL_STD_LOGIC_VECTOR,
+      )),
+    ),
+    (
+      "top.count",
+      Some(Supplemental::new("", svt::VHDL_VARIABLE, sdt::VHDL_INTEGER)),
+    ),
+    (
+      "top.bits",
+      Some(Supplemental::new(
+        "bit_vector",
+        svt::VHDL_SIGNAL,
+        sdt::VHDL_BIT_VECTOR,
+      )),
+    ),
+    ("top.clk", None),
+  ]
+}
+
+#[test]
+fn read_back_by_vars() {
+  for compressed_hier in [false, true] {
+    let file = TempFile::new(&format!("supplemental-vars-{compressed_hier}"));
+    write_vhdl(&file, compressed_hier);
+    let mut reader = Reader::open(file.path()).unwrap();
+    let vars: Vec<_> = reader
+      .vars()
+      .map(|v| {
+        let (name, var) = v.unwrap();
+        (name, var.supplemental().cloned())
+      })
+      .collect();
+    let expected: Vec<_> = expected()
+      .into_iter()
+      .map(|(n, s)| (n.to_string(), s))
+      .collect();
+    assert_eq!(vars, expected);
+  }
+}
+
+#[test]
+fn read_back_by_hierarchy() {
+  let file = TempFile::new("supplemental-hierarchy");
+  write_vhdl(&file, true);
+  let hier = Hierarchy::new(&mut Reader::open(file.path()).unwrap()).unwrap();
+  for (path, supplemental) in expected() {
+    let id = hier.var_by_path(path).unwrap();
+    assert_eq!(hier.var(id).supplemental(), supplemental.as_ref(), "{path}");
+  }
+}
+
+#[test]
+fn supvar_attrs() {
+  let file = TempFile::new("supplemental-supvar-attrs");
+  write_vhdl(&file, true);
+  let mut reader = Reader::open(file.path()).unwrap();
+  let attrs: Vec<_> = reader
+    .hiers()
+    .filter_map(|hier| match hier {
+      Hier::AttrBegin(attr) => Some(attr.supplemental().unwrap().unwrap()),
+      _ => None,
+    })
+    .collect();
+  let expected: Vec<_> = expected().into_iter().filter_map(|(_, s)| s).collect();
+  assert_eq!(attrs, expected);
+}
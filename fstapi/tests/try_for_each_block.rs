@@ -0,0 +1,93 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::Reader;
+use std::ops::ControlFlow;
+
+#[test]
+fn break_value() {
+  let file = TempFile::new("try-break-value");
+  let vars = write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader.clear_mask_all();
+  reader.set_mask(vars.counter);
+  let target = counter_at(200);
+  let mut times = Vec::new();
+  let ret = reader
+    .try_for_each_block(|time, _, value, _| {
+      times.push(time);
+      if value == target.as_bytes() {
+        ControlFlow::Break(time)
+      } else {
+        ControlFlow::Continue(())
+      }
+    })
+    .unwrap();
+  assert_eq!(ret, ControlFlow::Break(195));
+  // The callback is not called after it breaks.
+  assert_eq!(times.last(), Some(&195));
+  assert_eq!(times.len() as u64, 195 / STEP + 1);
+}
+
+#[test]
+fn continue_to_end() {
+  let file = TempFile::new("try-continue-to-end");
+  let vars = write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader.clear_mask_all();
+  reader.set_mask(vars.toggle);
+  let mut times = Vec::new();
+  let ret = reader
+    .try_for_each_block(|time, _, value, _| {
+      assert_eq!(value, toggle_at(time).as_bytes());
+      times.push(time);
+      ControlFlow::<()>::Continue(())
+    })
+    .unwrap();
+  assert_eq!(ret, ControlFlow::Continue(()));
+  let expected: Vec<_> = (0..=BLOCK_ENDS[2]).step_by(STEP as usize).collect();
+  assert_eq!(times, expected);
+}
+
+#[test]
+fn reusable_after_break() {
+  let file = TempFile::new("try-reusable-after-break");
+  let vars = write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader.clear_mask_all();
+  reader.set_mask(vars.counter);
+
+  // Without a time range limit.
+  let ret = reader
+    .try_for_each_block(|time, _, _, _| ControlFlow::Break(time))
+    .unwrap();
+  assert_eq!(ret, ControlFlow::Break(0));
+  assert_eq!(reader.time_range_limit(), None);
+  let mut last = None;
+  reader
+    .for_each_block(|time, _, _, _| last = Some(time))
+    .unwrap();
+  assert_eq!(last, Some(BLOCK_ENDS[2]));
+
+  // With a time range limit, which is restored after breaking.
+  reader.set_time_range_limit(200, 400);
+  let ret = reader
+    .try_for_each_block(|time, _, _, _| {
+      if time >= 200 {
+        ControlFlow::Break(time)
+      } else {
+        ControlFlow::Continue(())
+      }
+    })
+    .unwrap();
+  assert_eq!(ret, ControlFlow::Break(210));
+  assert_eq!(reader.time_range_limit(), Some((200, 400)));
+  let mut times = Vec::new();
+  reader
+    .for_each_block(|time, _, _, _| times.push(time))
+    .unwrap();
+  assert!(times.contains(&390));
+  assert!(times.iter().all(|t| *t <= BLOCK_ENDS[2]));
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Error, Logic, LogicVec, Reader, Value, var_type};
+
+/// Creates a logic vector from the given string.
+fn lv(s: &str) -> LogicVec {
+  LogicVec::new(s.as_bytes()).unwrap()
+}
+
+#[test]
+fn logic_chars() {
+  for c in b"01xzuwlh-" {
+    let logic = Logic::from_char(*c).unwrap();
+    assert_eq!(logic.to_char(), *c);
+    assert_eq!(Logic::from_char(c.to_ascii_uppercase()), Some(logic));
+  }
+  assert_eq!(Logic::from_char(b'2'), None);
+  assert_eq!(Logic::One.to_string(), "1");
+  assert_eq!(Logic::L.to_bool(), Some(false));
+  assert_eq!(Logic::H.to_bool(), Some(true));
+  assert_eq!(Logic::X.to_bool(), None);
+  assert_eq!(Logic::DontCare.to_bool(), None);
+}
+
+#[test]
+fn logic_vec_bits() {
+  let v = lv("10xz");
+  assert_eq!(v.len(), 4);
+  assert!(!v.is_empty());
+  assert_eq!(v.bits(), [Logic::One, Logic::Zero, Logic::X, Logic::Z]);
+  assert_eq!(v.bit(0), Some(Logic::Z));
+  assert_eq!(v.bit(3), Some(Logic::One));
+  assert_eq!(v.bit(4), None);
+  assert!(!v.is_known());
+  assert!(v.has_x());
+  assert!(v.has_z());
+  assert_eq!(v.to_string(), "10xz");
+  assert!(lv("0101").is_known());
+  assert!(!lv("01u").is_known() && lv("01u").has_x() && !lv("01u").has_z());
+  assert!(lv("hl").is_known());
+  assert!(matches!(LogicVec::new(b"012"), Err(Error::InvalidValue)));
+}
+
+#[test]
+fn logic_vec_integers() {
+  assert_eq!(lv("00001010").to_u64(), Some(10));
+  assert_eq!(lv("hl").to_u64(), Some(2));
+  assert_eq!(lv("").to_u64(), Some(0));
+  assert_eq!(lv("1x").to_u64(), None);
+  assert_eq!(lv("1111").to_i64(), Some(-1));
+  assert_eq!(lv("1000").to_i64(), Some(-8));
+  assert_eq!(lv("0111").to_i64(), Some(7));
+  assert_eq!(lv("").to_i64(), None);
+
+  // Leading zeros or sign bits do not count towards the width.
+  let wide = format!("{}1", "0".repeat(100));
+  assert_eq!(lv(&wide).to_u64(), Some(1));
+  let neg = format!("{}0", "1".repeat(100));
+  assert_eq!(lv(&neg).to_i64(), Some(-2));
+  let big = format!("1{}", "0".repeat(64));
+  assert_eq!(lv(&big).to_u64(), None);
+  assert_eq!(lv(&big).to_u128(), Some(1 << 64));
+  let too_big = format!("1{}", "0".repeat(128));
+  assert_eq!(lv(&too_big).to_u128(), None);
+  let mut bytes = vec![1];
+  bytes.extend([0; 16]);
+  assert_eq!(lv(&too_big).to_bytes_be(), Some(bytes));
+  assert_eq!(lv("100000001").to_bytes_be(), Some(vec![1, 1]));
+  assert_eq!(lv("0x").to_bytes_be(), None);
+}
+
+#[test]
+fn logic_vec_hex_string() {
+  assert_eq!(lv("11111010").to_hex_string(), "fa");
+  assert_eq!(lv("101111").to_hex_string(), "2f");
+  assert_eq!(lv("zzzz0001").to_hex_string(), "z1");
+  assert_eq!(lv("zz0z1x01").to_hex_string(), "xx");
+}
+
+#[test]
+fn value_new() {
+  let bits = Value::new(var_type::VCD_REG, b"1111").unwrap();
+  assert_eq!(bits, Value::Bits(lv("1111")));
+  assert_eq!(bits.to_u64(), Some(15));
+  assert_eq!(bits.to_i64(), Some(15));
+  assert_eq!(bits.to_f64(), Some(15.0));
+  assert_eq!(bits.as_logic_vec(), Some(&lv("1111")));
+  assert_eq!(bits.as_str(), None);
+
+  let int = Value::new(var_type::SV_INT, b"1111").unwrap();
+  assert_eq!(int, Value::Int(lv("1111")));
+  assert_eq!(int.to_i64(), Some(-1));
+  assert_eq!(int.to_u64(), None);
+  assert_eq!(int.to_f64(), Some(-1.0));
+  assert_eq!(int.to_string(), "1111");
+
+  let real = Value::new(var_type::VCD_REAL, b"r2.5").unwrap();
+  assert_eq!(real, Value::Real(2.5));
+  assert_eq!(
+    Value::new(var_type::VCD_REAL, b"-1").unwrap(),
+    Value::Real(-1.0)
+  );
+  assert_eq!(real.to_f64(), Some(2.5));
+  assert_eq!(real.to_u64(), None);
+  assert_eq!(real.as_logic_vec(), None);
+  assert_eq!(real.to_string(), "2.5");
+  let native = Value::from_native_double(&2.5f64.to_ne_bytes()).unwrap();
+  assert_eq!(native, real);
+  assert!(matches!(
+    Value::from_native_double(b"r2.5"),
+    Err(Error::InvalidValue)
+  ));
+
+  let string = Value::new(var_type::GEN_STRING, b"hello").unwrap();
+  assert_eq!(string.as_str(), Some("hello"));
+  assert_eq!(string.to_f64(), None);
+  assert_eq!(string.to_string(), "hello");
+
+  assert!(matches!(
+    Value::new(var_type::VCD_REAL, b"r?"),
+    Err(Error::InvalidValue)
+  ));
+  assert!(matches!(
+    Value::new(var_type::GEN_STRING, b"\xff"),
+    Err(Error::InvalidUtf8Str(Some(_)))
+  ));
+  assert!(matches!(
+    Value::new(var_type::VCD_WIRE, b"2"),
+    Err(Error::InvalidValue)
+  ));
+}
+
+#[cfg(feature = "num-bigint")]
+#[test]
+fn value_biguint() {
+  let big = format!("1{}", "0".repeat(128));
+  let value = Value::new(var_type::VCD_REG, big.as_bytes()).unwrap();
+  let expected = num_bigint::BigUint::from(1u8) << 128u32;
+  assert_eq!(value.to_biguint(), Some(expected));
+  assert_eq!(
+    Value::new(var_type::SV_INT, b"1").unwrap().to_biguint(),
+    None
+  );
+}
+
+#[test]
+fn var_decode_value() {
+  let file = TempFile::new("value-var-decode-value");
+  let vars = write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader.set_mask_all();
+  let changes: Vec<_> = reader.changes().collect::<Result<_, _>>().unwrap();
+  let mut checked = 0;
+  for var in reader.vars() {
+    let (_, var) = var.unwrap();
+    for change in changes.iter().filter(|c| c.handle == var.handle()) {
+      let value = var.decode_value(&change.value).unwrap();
+      let step = change.time / STEP;
+      match var.handle() {
+        h if h == vars.counter => assert_eq!(value.to_u64(), Some(step)),
+        h if h == vars.toggle => assert_eq!(value.to_u64(), Some(step % 2)),
+        h if h == vars.real => assert_eq!(value, Value::Real(step as f64 / 2.0)),
+        h if h == vars.string => assert_eq!(value.as_str(), Some(&*string_at(change.time))),
+        _ => unreachable!(),
+      }
+      checked += 1;
+    }
+  }
+  assert_eq!(checked, changes.len());
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Error, Reader, Writer, block_type};
+
+/// Returns the value of the given handle at the given time as a string.
+fn value_at(reader: &mut Reader, handle: fstapi::Handle, time: u64) -> String {
+  String::from_utf8(reader.value_at(handle, time).unwrap()).unwrap()
+}
+
+#[test]
+fn value_at_all_times() {
+  let file = TempFile::new("value-at-all-times");
+  let vars = write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  assert_eq!(reader.end_time(), END_TIME);
+  for time in 0..=END_TIME {
+    assert_eq!(value_at(&mut reader, vars.counter, time), counter_at(time));
+    assert_eq!(value_at(&mut reader, vars.toggle, time), toggle_at(time));
+    assert_eq!(value_at(&mut reader, vars.real, time), real_at(time));
+  }
+}
+
+#[test]
+fn value_at_block_boundaries() {
+  let file = TempFile::new("value-at-block-boundaries");
+  let vars = write_blocks(file.path());
+  let times = BLOCK_ENDS.iter().flat_map(|t| [*t - 1, *t, *t + 1]);
+  for time in times.chain([END_TIME]) {
+    // Use a new reader for each time, so no block is cached.
+    let mut reader = Reader::open(file.path()).unwrap();
+    assert_eq!(value_at(&mut reader, vars.counter, time), counter_at(time));
+    let mut reader = Reader::open(file.path()).unwrap();
+    assert_eq!(value_at(&mut reader, vars.toggle, time), toggle_at(time));
+  }
+}
+
+#[test]
+fn value_at_backwards() {
+  let file = TempFile::new("value-at-backwards");
+  let vars = write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  for time in (0..=END_TIME).rev().step_by(7) {
+    assert_eq!(value_at(&mut reader, vars.counter, time), counter_at(time));
+  }
+}
+
+#[test]
+fn value_at_after_end() {
+  let file = TempFile::new("value-at-after-end");
+  let vars = write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  for time in [END_TIME + 1, END_TIME * 2, u64::MAX] {
+    assert_eq!(
+      value_at(&mut reader, vars.counter, time),
+      counter_at(END_TIME)
+    );
+    assert_eq!(value_at(&mut reader, vars.real, time), real_at(END_TIME));
+  }
+}
+
+#[test]
+fn value_at_var_len() {
+  let file = TempFile::new("value-at-var-len");
+  let vars = write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  assert_eq!(
+    reader.value_at(vars.string, 0),
+    Err(Error::InvalidHandle(vars.string))
+  );
+}
+
+#[test]
+fn value_at_ignores_masks_and_time_range() {
+  let file = TempFile::new("value-at-ignores-masks");
+  let vars = write_blocks(file.path());
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader.clear_mask_all();
+  reader.set_time_range_limit(0, 10);
+  assert_eq!(value_at(&mut reader, vars.counter, 500), counter_at(500));
+}
+
+#[test]
+fn value_at_gzip_wrapped() {
+  let file = TempFile::new("value-at-gzip-wrapped");
+  let writer = Writer::create(file.path(), true).unwrap();
+  let vars = write_blocks_to(writer.repack_on_close(true));
+  let mut reader = Reader::open(file.path()).unwrap();
+  assert_eq!(reader.blocks().unwrap()[0].ty, block_type::ZWRAPPER);
+  for time in BLOCK_ENDS.into_iter().chain([0, 100, 550, END_TIME]) {
+    assert_eq!(value_at(&mut reader, vars.counter, time), counter_at(time));
+  }
+}
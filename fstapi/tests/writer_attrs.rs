@@ -0,0 +1,129 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::TempFile;
+use fstapi::{Error, Hier, Reader, Writer, attr_type, misc_type, scope_type, var_dir, var_type};
+
+/// An attribute read back from the hierarchy, in
+/// `(subtype, name, arg, arg_from_name)` form.
+type AttrInfo = (u32, String, u64, u64);
+
+/// Writes a waveform with environment variable, source stems
+/// and enumeration table attributes.
+fn write_attrs(file: &TempFile, compressed_hier: bool) {
+  let mut writer = Writer::create(file.path(), compressed_hier).unwrap();
+  writer.set_env_var("HOME=/home/fst").unwrap();
+  writer.set_source_stem("top.v", 3, false).unwrap();
+  writer
+    .set_source_instantiation_stem("tb.v", 10, false)
+    .unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  // The same path is only defined once.
+  writer.set_source_stem("top.v", 5, false).unwrap();
+  let table = writer
+    .create_enum_table("color", 2, &[("RED", "0"), ("GREEN", "1"), ("BLUE", "10")])
+    .unwrap();
+  writer.emit_enum_table_ref(table);
+  let color = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 2, "color", None)
+    .unwrap();
+  writer.set_upscope();
+  writer.emit_time_change(0).unwrap();
+  writer.emit_value_change(color, b"10").unwrap();
+  writer.emit_time_change(1).unwrap();
+}
+
+/// Returns all attributes in the hierarchy of the given file.
+fn read_attrs(file: &TempFile) -> Vec<AttrInfo> {
+  let mut reader = Reader::open(file.path()).unwrap();
+  reader
+    .hiers()
+    .filter_map(|hier| match hier {
+      Hier::AttrBegin(attr) => {
+        assert_eq!(attr.ty(), attr_type::MISC);
+        let arg_from_name = match attr.subtype() {
+          misc_type::SOURCESTEM | misc_type::SOURCEISTEM => attr.arg_from_name(),
+          _ => 0,
+        };
+        Some((
+          attr.subtype(),
+          attr.name().unwrap().into(),
+          attr.arg(),
+          arg_from_name,
+        ))
+      }
+      _ => None,
+    })
+    .collect()
+}
+
+#[test]
+fn env_var_and_source_stems() {
+  for compressed_hier in [false, true] {
+    let file = TempFile::new(&format!("writer-attrs-stems-{compressed_hier}"));
+    write_attrs(&file, compressed_hier);
+    let attrs = read_attrs(&file);
+    let expected: &[(u32, &str, u64, u64)] = &[
+      (misc_type::ENVVAR, "HOME=/home/fst", 0, 0),
+      (misc_type::PATHNAME, "top.v", 1, 0),
+      (misc_type::SOURCESTEM, "", 3, 1),
+      (misc_type::PATHNAME, "tb.v", 2, 0),
+      (misc_type::SOURCEISTEM, "", 10, 2),
+      (misc_type::SOURCESTEM, "", 5, 1),
+    ];
+    let stems: Vec<_> = attrs
+      .iter()
+      .filter(|(ty, ..)| *ty != misc_type::ENUMTABLE)
+      .map(|(ty, name, arg, from_name)| {
+        // Names of source stems are the encoded path indices.
+        let name = if from_name == &0 { name.as_str() } else { "" };
+        (*ty, name, *arg, *from_name)
+      })
+      .collect();
+    assert_eq!(stems, expected);
+  }
+}
+
+#[test]
+fn enum_table_definition_and_reference() {
+  let file = TempFile::new("writer-attrs-enum-table");
+  write_attrs(&file, true);
+  let mut reader = Reader::open(file.path()).unwrap();
+  let mut tables = Vec::new();
+  let mut refs = Vec::new();
+  for hier in reader.hiers() {
+    if let Hier::AttrBegin(attr) = hier
+      && let Some(handle) = attr.enum_handle()
+    {
+      match attr.enum_table().unwrap() {
+        Some(table) => tables.push((handle, table)),
+        None => refs.push(handle),
+      }
+    }
+  }
+  assert_eq!(tables.len(), 1);
+  let (handle, table) = &tables[0];
+  assert_eq!(refs, [*handle]);
+  assert_eq!(table.name(), "color");
+  let elems: Vec<_> = table
+    .elems()
+    .iter()
+    .map(|(l, v)| (l.as_str(), v.as_str()))
+    .collect();
+  assert_eq!(elems, [("RED", "00"), ("GREEN", "01"), ("BLUE", "10")]);
+  assert_eq!(table.literal(b"10"), Some("BLUE"));
+  assert_eq!(table.literal(b"1"), Some("GREEN"));
+  assert_eq!(table.value("RED"), Some("00"));
+  assert_eq!(table.value("BLACK"), None);
+}
+
+#[test]
+fn empty_enum_table() {
+  let file = TempFile::new("writer-attrs-empty-enum-table");
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  assert!(matches!(
+    writer.create_enum_table("empty", 1, &[]),
+    Err(Error::InvalidOperation)
+  ));
+}
@@ -2,12 +2,14 @@ use crate::section::{Item, Section};
 use fstapi::{ArrayType, EnumValueType, MiscType, PackType};
 use fstapi::{Attr, Hier, Reader, Result};
 use fstapi::{array_type, attr_type, enum_value_type, misc_type, pack_type};
+use serde::Serialize;
 use tabled::Tabled;
 
 /// Attribute information.
-#[derive(Tabled)]
+#[derive(Tabled, Serialize)]
 pub struct AttrInfo {
   #[tabled(rename = "Type")]
+  #[serde(rename = "type")]
   ty: &'static str,
   #[tabled(rename = "Subtype")]
   subtype: &'static str,
@@ -0,0 +1,80 @@
+use crate::section::{self, Item, Section};
+use fstapi::{Block, Reader, Result, block_type, writer_pack_type};
+use serde::Serialize;
+use tabled::Tabled;
+
+/// Block information.
+#[derive(Tabled, Serialize)]
+pub struct BlockInfo {
+  #[tabled(rename = "Type")]
+  #[serde(rename = "type")]
+  ty: &'static str,
+  #[tabled(rename = "Offset")]
+  offset: u64,
+  #[tabled(rename = "Length")]
+  length: u64,
+  #[tabled(rename = "Uncompressed length")]
+  uncompressed_length: u64,
+  #[tabled(rename = "Start time", display_with = "section::display_option")]
+  start_time: Option<u64>,
+  #[tabled(rename = "End time", display_with = "section::display_option")]
+  end_time: Option<u64>,
+  #[tabled(rename = "Pack type")]
+  pack_type: &'static str,
+}
+
+impl BlockInfo {
+  fn new(block: Block) -> Self {
+    Self {
+      ty: match block.ty {
+        block_type::HDR => "Header",
+        block_type::VCDATA => "VcData",
+        block_type::BLACKOUT => "Blackout",
+        block_type::GEOM => "Geometry",
+        block_type::HIER => "Hierarchy",
+        block_type::VCDATA_DYN_ALIAS => "VcDataDynAlias",
+        block_type::HIER_LZ4 => "HierarchyLz4",
+        block_type::HIER_LZ4DUO => "HierarchyLz4Duo",
+        block_type::VCDATA_DYN_ALIAS2 => "VcDataDynAlias2",
+        block_type::ZWRAPPER => "ZWrapper",
+        block_type::SKIP => "Skip",
+        _ => "Unknown",
+      },
+      offset: block.offset,
+      length: block.length,
+      uncompressed_length: block.uncompressed_length,
+      start_time: block.time_range.map(|(start, _)| start),
+      end_time: block.time_range.map(|(_, end)| end),
+      pack_type: match block.pack_type {
+        Some(writer_pack_type::ZLIB) => "Zlib",
+        Some(writer_pack_type::FASTLZ) => "FastLZ",
+        Some(writer_pack_type::LZ4) => "LZ4",
+        _ => "",
+      },
+    }
+  }
+}
+
+/// Blocks information.
+pub struct Blocks {
+  blocks: Vec<BlockInfo>,
+}
+
+impl Blocks {
+  pub fn new(reader: &Reader) -> Result<Self> {
+    let blocks = reader.blocks()?.into_iter().map(BlockInfo::new).collect();
+    Ok(Self { blocks })
+  }
+}
+
+impl Section for Blocks {
+  type Item = BlockInfo;
+
+  fn name() -> &'static str {
+    "Blocks"
+  }
+
+  fn item(&self) -> Item<'_, Self::Item> {
+    Item::Many(&self.blocks)
+  }
+}
@@ -0,0 +1,138 @@
+use crate::metadata::Metadata;
+use crate::section::{Item, Section};
+use fstapi::{Reader, Result, block_type, writer_pack_type};
+use serde::Serialize;
+use tabled::Tabled;
+
+/// Header information, including metadata.
+#[derive(Tabled, Serialize)]
+pub struct Header {
+  #[tabled(inline)]
+  #[serde(flatten)]
+  metadata: Metadata,
+  #[tabled(rename = "Max handle")]
+  max_handle: u32,
+  #[tabled(rename = "Number of value change blocks")]
+  num_vc_blocks: u64,
+  #[tabled(rename = "Double endian match")]
+  double_endian_match: bool,
+  #[tabled(rename = "Memory used by writer")]
+  mem_used: u64,
+  #[tabled(rename = "Pack type")]
+  pack_type: String,
+  #[tabled(rename = "Hierarchy compression")]
+  hier_compression: &'static str,
+  #[tabled(rename = "Gzip wrapped")]
+  gzip_wrapped: bool,
+}
+
+impl Header {
+  pub fn new(reader: &Reader) -> Result<Self> {
+    // Compression is recorded in blocks rather than the header.
+    let blocks = reader.blocks()?;
+    let mut pack_types = Vec::new();
+    let mut hier_compression = "Unknown";
+    let mut gzip_wrapped = false;
+    for block in &blocks {
+      match block.ty {
+        block_type::HIER => hier_compression = "Gzip",
+        block_type::HIER_LZ4 => hier_compression = "LZ4",
+        block_type::HIER_LZ4DUO => hier_compression = "LZ4Duo",
+        block_type::ZWRAPPER => gzip_wrapped = true,
+        _ => {}
+      }
+      let pack_type = match block.pack_type {
+        Some(writer_pack_type::ZLIB) => "Zlib",
+        Some(writer_pack_type::FASTLZ) => "FastLZ",
+        Some(writer_pack_type::LZ4) => "LZ4",
+        _ => continue,
+      };
+      if !pack_types.contains(&pack_type) {
+        pack_types.push(pack_type);
+      }
+    }
+    Ok(Self {
+      metadata: Metadata::new(reader)?,
+      max_handle: reader.max_handle().map_or(0, u32::from),
+      num_vc_blocks: reader.value_change_block_count(),
+      double_endian_match: reader.double_endian_match(),
+      mem_used: reader.memory_used_by_writer(),
+      pack_type: if pack_types.is_empty() {
+        "Unknown".into()
+      } else {
+        pack_types.join(", ")
+      },
+      hier_compression,
+      gzip_wrapped,
+    })
+  }
+}
+
+impl Section for Header {
+  type Item = Self;
+
+  fn name() -> &'static str {
+    "Header"
+  }
+
+  fn item(&self) -> Item<'_, Self::Item> {
+    Item::One(self)
+  }
+}
+
+/// Dump activity period information.
+#[derive(Tabled, Serialize)]
+pub struct DumpPeriod {
+  #[tabled(rename = "Start time")]
+  start_time: u64,
+  #[tabled(rename = "End time")]
+  end_time: u64,
+  #[tabled(rename = "Active")]
+  active: bool,
+}
+
+/// Dump activity periods information.
+pub struct DumpActivity {
+  periods: Vec<DumpPeriod>,
+}
+
+impl DumpActivity {
+  pub fn new(reader: &Reader) -> Self {
+    let mut periods = Vec::new();
+    // Dumping is active at the beginning of the waveform.
+    let mut active = true;
+    let mut start_time = reader.start_time();
+    for (time, a) in reader.dump_activity_changes() {
+      if a == active {
+        continue;
+      }
+      if time > start_time {
+        periods.push(DumpPeriod {
+          start_time,
+          end_time: time,
+          active,
+        });
+      }
+      active = a;
+      start_time = time;
+    }
+    periods.push(DumpPeriod {
+      start_time,
+      end_time: reader.end_time().max(start_time),
+      active,
+    });
+    Self { periods }
+  }
+}
+
+impl Section for DumpActivity {
+  type Item = DumpPeriod;
+
+  fn name() -> &'static str {
+    "Dump activity"
+  }
+
+  fn item(&self) -> Item<'_, Self::Item> {
+    Item::Many(&self.periods)
+  }
+}
@@ -1,14 +1,22 @@
 mod attrs;
+mod blocks;
+mod header;
 mod metadata;
 mod scopes;
 mod section;
+mod stats;
+mod tree;
+mod values;
 mod vars;
 
 use clap::Parser;
-use fstapi::{Reader, Result};
-use section::Print;
+use fstapi::{ReaderPool, Result, VarDir, VarType};
+use regex::Regex;
+use section::{Format, Print};
+use stats::SortKey;
 use std::process;
-use vars::VarSection;
+use values::Radix;
+use vars::{VarFilter, VarSection};
 
 #[derive(Parser)]
 #[command(
@@ -36,6 +44,11 @@ struct Cli {
   #[arg(short, long)]
   metadata: bool,
 
+  /// Display all information recorded in the header and the blackout
+  /// block, including dump activity periods.
+  #[arg(short = 'H', long)]
+  header: bool,
+
   /// Display all variables.
   #[arg(short, long)]
   vars: bool,
@@ -48,6 +61,26 @@ struct Cli {
   #[arg(long)]
   names_only: bool,
 
+  /// Display only variables whose full names match the given regex.
+  #[arg(long, value_name = "REGEX", value_parser = Regex::new)]
+  filter: Option<Regex>,
+
+  /// Display only variables of the given types, like `reg,wire`.
+  #[arg(long = "type", value_name = "TYPES", value_delimiter = ',', value_parser = vars::parse_var_type)]
+  types: Vec<VarType>,
+
+  /// Display only variables of the given directions, like `input,output`.
+  #[arg(long = "dir", value_name = "DIRS", value_delimiter = ',', value_parser = vars::parse_var_dir)]
+  dirs: Vec<VarDir>,
+
+  /// Display only variables with at least the given length in bits.
+  #[arg(long, value_name = "BITS")]
+  min_width: Option<u32>,
+
+  /// Display only variables with at most the given length in bits.
+  #[arg(long, value_name = "BITS")]
+  max_width: Option<u32>,
+
   /// Display all scopes.
   #[arg(short, long)]
   scopes: bool,
@@ -55,6 +88,64 @@ struct Cli {
   /// Display all attributes.
   #[arg(short = 'A', long)]
   attrs: bool,
+
+  /// Display all blocks, including their offsets, lengths and time ranges.
+  #[arg(short, long)]
+  blocks: bool,
+
+  /// Display scopes and variables as a tree.
+  #[arg(short, long)]
+  tree: bool,
+
+  /// Maximum depth of the tree, top-level scopes are at depth 0.
+  #[arg(long, requires = "tree")]
+  depth: Option<usize>,
+
+  /// Display the tree from the given scope, like `top.cpu`.
+  #[arg(long, requires = "tree")]
+  root: Option<String>,
+
+  /// Display statistics of value changes of each variable.
+  #[arg(short = 'S', long)]
+  stats: bool,
+
+  /// Sort statistics by the given key.
+  #[arg(long, value_enum, default_value_t = SortKey::Changes, requires = "stats")]
+  sort: SortKey,
+
+  /// Display statistics of the first N variables only.
+  #[arg(long, value_name = "N", requires = "stats")]
+  top: Option<usize>,
+
+  /// Display values of variables whose full names match the given regex,
+  /// at times given by `--at`, or all value changes by default.
+  #[arg(long, value_name = "REGEX", value_parser = Regex::new)]
+  values: Option<Regex>,
+
+  /// Display values at the given times, like `100,200`.
+  /// Values that are not available are displayed as `?`.
+  #[arg(long, value_name = "TIMES", value_delimiter = ',', requires = "values")]
+  at: Vec<u64>,
+
+  /// Display value changes from the given time.
+  #[arg(long, value_name = "TIME", requires = "values", conflicts_with = "at")]
+  from: Option<u64>,
+
+  /// Display value changes until the given time.
+  #[arg(long, value_name = "TIME", requires = "values", conflicts_with = "at")]
+  to: Option<u64>,
+
+  /// Radix of displayed values.
+  #[arg(long, value_enum, default_value_t = Radix::Bin, requires = "values")]
+  radix: Radix,
+
+  /// Number of threads for decoding the waveform in parallel.
+  #[arg(short, long, default_value_t = 1)]
+  jobs: usize,
+
+  /// Output format.
+  #[arg(short, long, value_enum, default_value_t = Format::Table)]
+  format: Format,
 }
 
 fn main() {
@@ -75,25 +166,47 @@ fn try_main() -> Result<()> {
   }
 
   // Validate command line arguments.
-  if !cli.metadata && !cli.vars && !cli.scopes && !cli.attrs {
+  if !cli.metadata
+    && !cli.header
+    && !cli.vars
+    && !cli.scopes
+    && !cli.attrs
+    && !cli.blocks
+    && !cli.tree
+    && !cli.stats
+    && cli.values.is_none()
+  {
     eprintln!("Invalid command line arguments, try `-h`.");
     process::exit(1);
   }
 
   // Open the given FST file.
-  let mut reader = Reader::open(cli.file)?;
+  let pool = ReaderPool::open(cli.file)?.threads(cli.jobs);
+  let mut reader = pool.get()?;
 
   // Generate sections.
   let mut secs: Vec<Box<dyn Print>> = Vec::new();
   if cli.metadata {
     secs.push(Box::new(metadata::Metadata::new(&reader)?));
   }
+  if cli.header {
+    secs.push(Box::new(header::Header::new(&reader)?));
+    secs.push(Box::new(header::DumpActivity::new(&reader)));
+  }
   if cli.vars {
+    let filter = VarFilter {
+      name: cli.filter,
+      types: cli.types,
+      dirs: cli.dirs,
+      min_width: cli.min_width,
+      max_width: cli.max_width,
+    };
+    let reader = &mut reader;
     secs.push(match (cli.no_aliases, cli.names_only) {
-      (false, false) => Box::new(vars::Variables::new(&mut reader)?),
-      (true, false) => Box::new(vars::NoAliasesVars::new(&mut reader)?),
-      (false, true) => Box::new(vars::NameOnlyVars::new(&mut reader)?),
-      (true, true) => Box::new(vars::NameOnlyNoAliasesVars::new(&mut reader)?),
+      (false, false) => Box::new(vars::Variables::new(reader, &filter)?),
+      (true, false) => Box::new(vars::NoAliasesVars::new(reader, &filter)?),
+      (false, true) => Box::new(vars::NameOnlyVars::new(reader, &filter)?),
+      (true, true) => Box::new(vars::NameOnlyNoAliasesVars::new(reader, &filter)?),
     });
   }
   if cli.scopes {
@@ -102,8 +215,38 @@ fn try_main() -> Result<()> {
   if cli.attrs {
     secs.push(Box::new(attrs::Attrs::new(&mut reader)?));
   }
+  if cli.blocks {
+    secs.push(Box::new(blocks::Blocks::new(&reader)?));
+  }
+  if cli.tree {
+    match tree::Tree::new(&mut reader, cli.root.as_deref(), cli.depth)? {
+      Some(tree) => secs.push(Box::new(tree)),
+      None => {
+        eprintln!("Scope `{}` not found.", cli.root.unwrap());
+        process::exit(1);
+      }
+    }
+  }
+  if cli.stats {
+    let mut stats = stats::Stats::new(&mut reader, &pool, cli.jobs)?;
+    stats.sort(cli.sort);
+    if let Some(top) = cli.top {
+      stats.truncate(top);
+    }
+    secs.push(Box::new(stats));
+  }
+  if let Some(re) = &cli.values {
+    let values = if cli.at.is_empty() {
+      let from = cli.from.unwrap_or(reader.start_time());
+      let to = cli.to.unwrap_or(reader.end_time());
+      values::Values::between(&mut reader, re, from, to, cli.radix)?
+    } else {
+      values::Values::at(&mut reader, re, &cli.at, cli.radix)?
+    };
+    secs.push(Box::new(values));
+  }
 
   // Print sections.
-  secs.print();
+  section::print(&secs, cli.format);
   Ok(())
 }
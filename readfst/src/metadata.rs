@@ -1,9 +1,10 @@
 use crate::section::{Item, Section};
 use fstapi::{Reader, Result, file_type};
+use serde::Serialize;
 use tabled::Tabled;
 
 /// Metadata information.
-#[derive(Tabled)]
+#[derive(Tabled, Serialize)]
 pub struct Metadata {
   #[tabled(rename = "Date")]
   date: String,
@@ -1,11 +1,14 @@
 use crate::section::{Item, Section};
-use fstapi::{Hier, Reader, Result, Scope, scope_type};
+use fstapi::{Hier, Reader, Result, Scope, ScopeType, scope_type};
+use serde::Serialize;
+use serde_json::Value;
 use tabled::Tabled;
 
 /// Scope information.
-#[derive(Tabled)]
+#[derive(Tabled, Serialize)]
 pub struct ScopeInfo {
   #[tabled(rename = "Type")]
+  #[serde(rename = "type")]
   ty: &'static str,
   #[tabled(rename = "Name")]
   name: String,
@@ -16,51 +19,91 @@ pub struct ScopeInfo {
 impl ScopeInfo {
   fn new(scope: Scope) -> Result<Self> {
     Ok(Self {
-      ty: match scope.ty() {
-        scope_type::VCD_MODULE => "VcdModule",
-        scope_type::VCD_TASK => "VcdTask",
-        scope_type::VCD_FUNCTION => "VcdFunction",
-        scope_type::VCD_BEGIN => "VcdBegin",
-        scope_type::VCD_FORK => "VcdFork",
-        scope_type::VCD_GENERATE => "VcdGenerate",
-        scope_type::VCD_STRUCT => "VcdStruct",
-        scope_type::VCD_UNION => "VcdUnion",
-        scope_type::VCD_CLASS => "VcdClass",
-        scope_type::VCD_INTERFACE => "VcdInterface",
-        scope_type::VCD_PACKAGE => "VcdPackage",
-        scope_type::VCD_PROGRAM => "VcdProgram",
-        scope_type::VHDL_ARCHITECTURE => "VhdlArchitecture",
-        scope_type::VHDL_PROCEDURE => "VhdlProcedure",
-        scope_type::VHDL_FUNCTION => "VhdlFunction",
-        scope_type::VHDL_RECORD => "VhdlRecord",
-        scope_type::VHDL_PROCESS => "VhdlProcess",
-        scope_type::VHDL_BLOCK => "VhdlBlock",
-        scope_type::VHDL_FOR_GENERATE => "VhdlForGenerate",
-        scope_type::VHDL_IF_GENERATE => "VhdlIfGenerate",
-        scope_type::VHDL_GENERATE => "VhdlGenerate",
-        scope_type::VHDL_PACKAGE => "VhdlPackage",
-        _ => unreachable!(),
-      },
+      ty: scope_type_name(scope.ty()),
       name: scope.name()?.into(),
       component: scope.component()?.into(),
     })
   }
 }
 
+/// Returns the name of the given scope type.
+pub fn scope_type_name(ty: ScopeType) -> &'static str {
+  match ty {
+    scope_type::VCD_MODULE => "VcdModule",
+    scope_type::VCD_TASK => "VcdTask",
+    scope_type::VCD_FUNCTION => "VcdFunction",
+    scope_type::VCD_BEGIN => "VcdBegin",
+    scope_type::VCD_FORK => "VcdFork",
+    scope_type::VCD_GENERATE => "VcdGenerate",
+    scope_type::VCD_STRUCT => "VcdStruct",
+    scope_type::VCD_UNION => "VcdUnion",
+    scope_type::VCD_CLASS => "VcdClass",
+    scope_type::VCD_INTERFACE => "VcdInterface",
+    scope_type::VCD_PACKAGE => "VcdPackage",
+    scope_type::VCD_PROGRAM => "VcdProgram",
+    scope_type::VHDL_ARCHITECTURE => "VhdlArchitecture",
+    scope_type::VHDL_PROCEDURE => "VhdlProcedure",
+    scope_type::VHDL_FUNCTION => "VhdlFunction",
+    scope_type::VHDL_RECORD => "VhdlRecord",
+    scope_type::VHDL_PROCESS => "VhdlProcess",
+    scope_type::VHDL_BLOCK => "VhdlBlock",
+    scope_type::VHDL_FOR_GENERATE => "VhdlForGenerate",
+    scope_type::VHDL_IF_GENERATE => "VhdlIfGenerate",
+    scope_type::VHDL_GENERATE => "VhdlGenerate",
+    scope_type::VHDL_PACKAGE => "VhdlPackage",
+    _ => unreachable!(),
+  }
+}
+
 /// Scopes information.
 pub struct Scopes {
   scopes: Vec<ScopeInfo>,
+  /// Indices of child scopes of each scope.
+  children: Vec<Vec<usize>>,
+  /// Indices of top-level scopes.
+  roots: Vec<usize>,
 }
 
 impl Scopes {
   pub fn new(reader: &mut Reader) -> Result<Self> {
     let mut scopes = Vec::new();
+    let mut children = Vec::new();
+    let mut roots = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
     for hier in reader.hiers() {
-      if let Hier::Scope(scope) = hier {
-        scopes.push(ScopeInfo::new(scope)?);
+      match hier {
+        Hier::Scope(scope) => {
+          let index = scopes.len();
+          scopes.push(ScopeInfo::new(scope)?);
+          children.push(Vec::new());
+          match stack.last() {
+            Some(&parent) => children[parent].push(index),
+            None => roots.push(index),
+          }
+          stack.push(index);
+        }
+        Hier::Upscope => {
+          stack.pop();
+        }
+        _ => {}
       }
     }
-    Ok(Self { scopes })
+    Ok(Self {
+      scopes,
+      children,
+      roots,
+    })
+  }
+
+  /// Returns the JSON value of the given scope and all its child scopes.
+  fn scope_to_json(&self, index: usize) -> Value {
+    let mut value = serde_json::to_value(&self.scopes[index]).unwrap();
+    let children = self.children[index]
+      .iter()
+      .map(|&i| self.scope_to_json(i))
+      .collect();
+    value["scopes"] = Value::Array(children);
+    value
   }
 }
 
@@ -74,4 +117,8 @@ impl Section for Scopes {
   fn item(&self) -> Item<'_, Self::Item> {
     Item::Many(&self.scopes)
   }
+
+  fn to_json(&self) -> Value {
+    self.roots.iter().map(|&i| self.scope_to_json(i)).collect()
+  }
 }
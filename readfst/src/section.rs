@@ -1,13 +1,38 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
 use tabled::builder::Builder;
 use tabled::object::FirstRow;
 use tabled::{Alignment, Modify, Panel, Style, Table, Tabled};
 
+/// Output format.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+  /// Human readable tables.
+  Table,
+  /// A JSON object, with one key for each section.
+  Json,
+  /// Comma-separated values, sections are separated by empty lines.
+  Csv,
+}
+
 /// Item that represents the content of a [`Section`].
 pub enum Item<'a, T> {
   One(&'a T),
   Many(&'a [T]),
 }
 
+impl<T> Item<'_, T> {
+  /// Returns all rows of the item.
+  pub fn rows(&self) -> &[T] {
+    match self {
+      Item::One(i) => std::slice::from_ref(*i),
+      Item::Many(is) => is,
+    }
+  }
+}
+
 /// Section of waveform information.
 pub trait Section {
   /// Type of the item in the current section.
@@ -18,6 +43,18 @@ pub trait Section {
 
   /// Returns the item in the current section.
   fn item(&self) -> Item<'_, Self::Item>;
+
+  /// Returns the content of the current section as JSON value.
+  fn to_json(&self) -> Value
+  where
+    Self::Item: Serialize,
+  {
+    let value = match self.item() {
+      Item::One(i) => serde_json::to_value(i),
+      Item::Many(is) => serde_json::to_value(is),
+    };
+    value.unwrap()
+  }
 }
 
 /// Trait for converting to [`Table`].
@@ -63,27 +100,86 @@ where
 
 /// Trait for printing to stdout.
 pub trait Print {
-  /// Prints to stdout.
-  fn print(&self);
+  /// Prints to stdout as table.
+  fn print_table(&self);
+
+  /// Prints to stdout as CSV.
+  fn print_csv(&self);
+
+  /// Returns the key and the value in JSON output.
+  fn to_json(&self) -> (String, Value);
 }
 
-impl<T> Print for T
+impl<S, I> Print for S
 where
-  T: ToTable,
+  S: Section<Item = I>,
+  I: Tabled + Serialize,
 {
-  fn print(&self) {
-    let mut table = self.to_table();
-    println!("{}", table.with(Style::rounded().off_vertical()));
+  fn print_table(&self) {
+    print_table(self.to_table());
+  }
+
+  fn print_csv(&self) {
+    print_csv_record(I::headers());
+    for row in self.item().rows() {
+      print_csv_record(row.fields());
+    }
+  }
+
+  fn to_json(&self) -> (String, Value) {
+    let key = Self::name().to_lowercase().replace(' ', "_");
+    (key, Section::to_json(self))
   }
 }
 
-impl Print for Vec<Box<dyn Print>> {
-  fn print(&self) {
-    for (i, s) in self.iter().enumerate() {
-      if i != 0 {
-        println!()
-      }
-      s.print()
+/// Prints the given sections to stdout in the given format.
+pub fn print(secs: &[Box<dyn Print>], format: Format) {
+  if format == Format::Json {
+    let map: Map<_, _> = secs.iter().map(|s| s.to_json()).collect();
+    println!("{}", serde_json::to_string_pretty(&map).unwrap());
+    return;
+  }
+  for (i, s) in secs.iter().enumerate() {
+    if i != 0 {
+      println!()
+    }
+    match format {
+      Format::Table => s.print_table(),
+      Format::Csv => s.print_csv(),
+      Format::Json => unreachable!(),
     }
   }
 }
+
+/// Displays the given optional value, or nothing if there is no value.
+pub fn display_option<T>(value: &Option<T>) -> String
+where
+  T: fmt::Display,
+{
+  value.as_ref().map(T::to_string).unwrap_or_default()
+}
+
+/// Prints the given table to stdout.
+pub fn print_table(mut table: Table) {
+  println!("{}", table.with(Style::rounded().off_vertical()));
+}
+
+/// Prints a CSV record to stdout, fields are quoted if necessary.
+pub fn print_csv_record<I, S>(fields: I)
+where
+  I: IntoIterator<Item = S>,
+  S: AsRef<str>,
+{
+  let fields: Vec<_> = fields
+    .into_iter()
+    .map(|f| {
+      let f = f.as_ref();
+      if f.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", f.replace('"', "\"\""))
+      } else {
+        f.into()
+      }
+    })
+    .collect();
+  println!("{}", fields.join(","));
+}
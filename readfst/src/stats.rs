@@ -0,0 +1,239 @@
+use crate::section::{self, Item, Section};
+use crate::vars::serialize_handle;
+use clap::ValueEnum;
+use fstapi::{Handle, Reader, ReaderPool, Result, Value, VarType, var_type};
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::fmt;
+use tabled::Tabled;
+
+/// Key for sorting statistics.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SortKey {
+  /// Number of value changes, in descending order.
+  Changes,
+  /// Number of bit toggles, in descending order.
+  Toggles,
+  /// Time spent in X/Z, in descending order.
+  XzTime,
+  /// Variable name.
+  Name,
+  /// Variable handle.
+  Handle,
+}
+
+/// Numeric value of a variable.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Serialize)]
+#[serde(untagged)]
+enum Number {
+  Unsigned(u128),
+  Signed(i64),
+  Real(f64),
+}
+
+impl Number {
+  fn new(value: &Value) -> Option<Self> {
+    match value {
+      Value::Bits(v) => v.to_u128().map(Self::Unsigned),
+      Value::Int(v) => v.to_i64().map(Self::Signed),
+      Value::Real(r) => Some(Self::Real(*r)),
+      Value::String(_) => None,
+    }
+  }
+}
+
+impl fmt::Display for Number {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Self::Unsigned(v) => write!(f, "{v}"),
+      Self::Signed(v) => write!(f, "{v}"),
+      Self::Real(v) => write!(f, "{v}"),
+    }
+  }
+}
+
+/// Statistics of a variable.
+#[derive(Tabled, Serialize)]
+pub struct VarStats {
+  #[tabled(rename = "Handle")]
+  #[serde(serialize_with = "serialize_handle")]
+  handle: Handle,
+  #[tabled(rename = "Name")]
+  name: String,
+  #[tabled(rename = "Changes")]
+  changes: u64,
+  #[tabled(rename = "Toggles", display_with = "section::display_option")]
+  toggles: Option<u64>,
+  #[tabled(rename = "First change", display_with = "section::display_option")]
+  first_change: Option<u64>,
+  #[tabled(rename = "Last change", display_with = "section::display_option")]
+  last_change: Option<u64>,
+  #[tabled(rename = "X/Z time", display_with = "section::display_option")]
+  xz_time: Option<u64>,
+  #[tabled(rename = "Min", display_with = "section::display_option")]
+  min: Option<Number>,
+  #[tabled(rename = "Max", display_with = "section::display_option")]
+  max: Option<Number>,
+  /// Type of the variable.
+  #[tabled(skip)]
+  #[serde(skip)]
+  ty: VarType,
+  /// The last value of the variable.
+  #[tabled(skip)]
+  #[serde(skip)]
+  value: Vec<u8>,
+  /// Time since when the variable has unknown bits.
+  #[tabled(skip)]
+  #[serde(skip)]
+  xz_since: Option<u64>,
+}
+
+impl VarStats {
+  fn new(handle: Handle, name: String, ty: VarType) -> Self {
+    let is_bits = is_bits(ty);
+    Self {
+      handle,
+      name,
+      changes: 0,
+      toggles: is_bits.then_some(0),
+      first_change: None,
+      last_change: None,
+      xz_time: is_bits.then_some(0),
+      min: None,
+      max: None,
+      ty,
+      value: Vec::new(),
+      xz_since: None,
+    }
+  }
+
+  /// Updates the statistics by the given value change.
+  fn update(&mut self, time: u64, value: &[u8]) {
+    if let Some(toggles) = &mut self.toggles {
+      // Initial values are not toggles.
+      if self.first_change.is_some() {
+        let diff = self.value.iter().zip(value).filter(|(a, b)| a != b);
+        *toggles += (diff.count() + self.value.len().abs_diff(value.len())) as u64;
+      }
+      self.end_xz(time);
+      if value.iter().any(|b| !matches!(b, b'0' | b'1')) {
+        self.xz_since = Some(time);
+      }
+      self.value.clear();
+      self.value.extend_from_slice(value);
+    }
+    self.changes += 1;
+    self.first_change.get_or_insert(time);
+    self.last_change = Some(time);
+    if let Some(num) = Value::new(self.ty, value)
+      .ok()
+      .as_ref()
+      .and_then(Number::new)
+    {
+      if self.min.is_none_or(|min| num < min) {
+        self.min = Some(num);
+      }
+      if self.max.is_none_or(|max| num > max) {
+        self.max = Some(num);
+      }
+    }
+  }
+
+  /// Ends the period in which the variable has unknown bits at the given time.
+  fn end_xz(&mut self, time: u64) {
+    if let Some(since) = self.xz_since.take()
+      && let Some(xz_time) = &mut self.xz_time
+    {
+      *xz_time += time.saturating_sub(since);
+    }
+  }
+}
+
+/// Returns `true` if values of the given variable type are bit vectors.
+fn is_bits(ty: VarType) -> bool {
+  !matches!(
+    ty,
+    var_type::VCD_REAL
+      | var_type::VCD_REAL_PARAMETER
+      | var_type::VCD_REALTIME
+      | var_type::SV_SHORTREAL
+      | var_type::GEN_STRING
+  )
+}
+
+/// Statistics of value changes.
+pub struct Stats {
+  stats: Vec<VarStats>,
+}
+
+impl Stats {
+  /// Collects statistics of all variables except aliases.
+  ///
+  /// Value change blocks are decoded by the given pool if `jobs` is
+  /// greater than 1, otherwise by the given reader.
+  pub fn new(reader: &mut Reader, pool: &ReaderPool, jobs: usize) -> Result<Self> {
+    // Indices of statistics of each handle.
+    let mut indices = Vec::new();
+    let mut stats = Vec::new();
+    for var in reader.vars() {
+      let (name, var) = var?;
+      if var.is_alias() {
+        continue;
+      }
+      let index = u32::from(var.handle()) as usize;
+      if indices.len() <= index {
+        indices.resize(index + 1, None);
+      }
+      indices[index] = Some(stats.len());
+      stats.push(VarStats::new(var.handle(), name, var.ty()));
+    }
+
+    // Update statistics by value changes.
+    let callback = |time, handle: Handle, value: &[u8], _| {
+      if let Some(&Some(i)) = indices.get(u32::from(handle) as usize) {
+        stats[i].update(time, value);
+      }
+    };
+    if jobs > 1 {
+      pool.for_each_block(|r| r.set_mask_all(), callback)?;
+    } else {
+      reader.set_mask_all();
+      let ret = reader.for_each_block(callback);
+      reader.clear_mask_all();
+      ret?;
+    }
+    let end_time = reader.end_time();
+    for s in &mut stats {
+      s.end_xz(end_time);
+    }
+    Ok(Self { stats })
+  }
+
+  /// Sorts statistics by the given key.
+  pub fn sort(&mut self, key: SortKey) {
+    match key {
+      SortKey::Changes => self.stats.sort_by_key(|s| Reverse(s.changes)),
+      SortKey::Toggles => self.stats.sort_by_key(|s| Reverse(s.toggles)),
+      SortKey::XzTime => self.stats.sort_by_key(|s| Reverse(s.xz_time)),
+      SortKey::Name => self.stats.sort_by(|a, b| a.name.cmp(&b.name)),
+      SortKey::Handle => self.stats.sort_by_key(|s| s.handle),
+    }
+  }
+
+  /// Keeps only the first `n` variables.
+  pub fn truncate(&mut self, n: usize) {
+    self.stats.truncate(n);
+  }
+}
+
+impl Section for Stats {
+  type Item = VarStats;
+
+  fn name() -> &'static str {
+    "Statistics"
+  }
+
+  fn item(&self) -> Item<'_, Self::Item> {
+    Item::Many(&self.stats)
+  }
+}
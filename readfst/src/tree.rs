@@ -0,0 +1,266 @@
+use crate::scopes::scope_type_name;
+use crate::section::{self, Print};
+use crate::vars::{serialize_handle, var_dir_name, var_type_name};
+use fstapi::{Handle, Hierarchy, Reader, Result, ScopeId, VarId};
+use serde::Serialize;
+use serde_json::Value;
+
+/// Scope node in the tree.
+#[derive(Serialize)]
+struct ScopeNode {
+  #[serde(rename = "type")]
+  ty: &'static str,
+  name: String,
+  component: String,
+  num_vars: usize,
+  scopes: Vec<ScopeNode>,
+  vars: Vec<VarNode>,
+}
+
+impl ScopeNode {
+  fn new(hier: &Hierarchy, id: ScopeId, name: String, depth: usize) -> Self {
+    let scope = hier.scope(id);
+    let (scopes, vars) = if depth > 0 {
+      (
+        scope
+          .scopes()
+          .iter()
+          .map(|&s| Self::new(hier, s, hier.scope(s).name().into(), depth - 1))
+          .collect(),
+        scope
+          .vars()
+          .iter()
+          .map(|&v| VarNode::new(hier, v))
+          .collect(),
+      )
+    } else {
+      (Vec::new(), Vec::new())
+    };
+    Self {
+      ty: scope_type_name(scope.ty()),
+      name,
+      component: scope.component().into(),
+      num_vars: scope.vars().len(),
+      scopes,
+      vars,
+    }
+  }
+
+  /// Returns the label of the scope in the tree.
+  fn label(&self) -> String {
+    let component = if self.component.is_empty() {
+      String::new()
+    } else {
+      format!(", {}", self.component)
+    };
+    let s = if self.num_vars == 1 { "" } else { "s" };
+    format!(
+      "{} ({}{component}, {} var{s})",
+      self.name, self.ty, self.num_vars
+    )
+  }
+
+  /// Prints the children of the scope, with the given line prefix.
+  fn print_children(&self, prefix: &str) {
+    print_nodes(&self.scopes, &self.vars, prefix);
+  }
+
+  /// Prints the scope and its children as CSV records.
+  fn print_csv(&self, path: &str, depth: usize) {
+    let path = join_path(path, &self.name);
+    section::print_csv_record([
+      &depth.to_string(),
+      "Scope",
+      &path,
+      self.ty,
+      "",
+      &self.component,
+      &self.num_vars.to_string(),
+      "",
+      "",
+    ]);
+    for scope in &self.scopes {
+      scope.print_csv(&path, depth + 1);
+    }
+    for var in &self.vars {
+      var.print_csv(&path, depth + 1);
+    }
+  }
+}
+
+/// Variable node in the tree.
+#[derive(Serialize)]
+struct VarNode {
+  #[serde(serialize_with = "serialize_handle")]
+  handle: Handle,
+  #[serde(rename = "type")]
+  ty: &'static str,
+  direction: &'static str,
+  name: String,
+  length: u32,
+  alias_of: String,
+}
+
+impl VarNode {
+  fn new(hier: &Hierarchy, id: VarId) -> Self {
+    let var = hier.var(id);
+    let alias_of = if var.is_alias() {
+      hier.handle_path(var.handle()).unwrap_or_default()
+    } else {
+      String::new()
+    };
+    Self {
+      handle: var.handle(),
+      ty: var_type_name(var.ty()),
+      direction: var_dir_name(var.direction()),
+      name: var.name().into(),
+      length: var.length(),
+      alias_of,
+    }
+  }
+
+  /// Returns the label of the variable in the tree.
+  fn label(&self) -> String {
+    let mut label = format!("{}: {} [{}]", self.name, self.ty, self.length);
+    if !self.alias_of.is_empty() {
+      label += &format!(" -> {}", self.alias_of);
+    }
+    label
+  }
+
+  /// Prints the variable as a CSV record.
+  fn print_csv(&self, path: &str, depth: usize) {
+    section::print_csv_record([
+      &depth.to_string(),
+      "Var",
+      &join_path(path, &self.name),
+      self.ty,
+      self.direction,
+      "",
+      "",
+      &self.length.to_string(),
+      &self.alias_of,
+    ]);
+  }
+}
+
+/// Prints the given scopes and variables as children in the tree,
+/// with the given line prefix.
+fn print_nodes(scopes: &[ScopeNode], vars: &[VarNode], prefix: &str) {
+  let count = scopes.len() + vars.len();
+  let mut index = 0;
+  let mut branch = |label: String| {
+    index += 1;
+    let (branch, indent) = if index == count {
+      ("└── ", "    ")
+    } else {
+      ("├── ", "│   ")
+    };
+    println!("{prefix}{branch}{label}");
+    format!("{prefix}{indent}")
+  };
+  for scope in scopes {
+    let prefix = branch(scope.label());
+    scope.print_children(&prefix);
+  }
+  for var in vars {
+    branch(var.label());
+  }
+}
+
+/// Returns the full name of the given name in the given scope.
+fn join_path(scope: &str, name: &str) -> String {
+  if scope.is_empty() {
+    name.into()
+  } else {
+    format!("{scope}.{name}")
+  }
+}
+
+/// Hierarchy tree of scopes and variables.
+pub struct Tree {
+  scopes: Vec<ScopeNode>,
+  vars: Vec<VarNode>,
+}
+
+impl Tree {
+  /// Creates a new tree from the given reader.
+  ///
+  /// The tree starts at the scope of the given full name if `root` is
+  /// provided, and displays items no more than `depth` levels below the
+  /// top-level scopes (or the root scope).
+  ///
+  /// Returns [`None`] if the root scope does not exist.
+  pub fn new(
+    reader: &mut Reader,
+    root: Option<&str>,
+    depth: Option<usize>,
+  ) -> Result<Option<Self>> {
+    let hier = Hierarchy::new(reader)?;
+    let depth = depth.unwrap_or(usize::MAX);
+    let tree = match root {
+      Some(root) => {
+        let Some(id) = hier.scope_by_path(root) else {
+          return Ok(None);
+        };
+        Self {
+          scopes: vec![ScopeNode::new(&hier, id, root.into(), depth)],
+          vars: Vec::new(),
+        }
+      }
+      None => Self {
+        scopes: (hier.root_scopes().iter())
+          .map(|&s| ScopeNode::new(&hier, s, hier.scope(s).name().into(), depth))
+          .collect(),
+        vars: (hier.root_vars().iter())
+          .map(|&v| VarNode::new(&hier, v))
+          .collect(),
+      },
+    };
+    Ok(Some(tree))
+  }
+}
+
+impl Print for Tree {
+  fn print_table(&self) {
+    if self.scopes.is_empty() && self.vars.is_empty() {
+      println!("None");
+      return;
+    }
+    for scope in &self.scopes {
+      println!("{}", scope.label());
+      scope.print_children("");
+    }
+    for var in &self.vars {
+      println!("{}", var.label());
+    }
+  }
+
+  fn print_csv(&self) {
+    section::print_csv_record([
+      "Depth",
+      "Kind",
+      "Name",
+      "Type",
+      "Direction",
+      "Component",
+      "Number of variables",
+      "Length in Bits",
+      "Alias Of",
+    ]);
+    for scope in &self.scopes {
+      scope.print_csv("", 0);
+    }
+    for var in &self.vars {
+      var.print_csv("", 0);
+    }
+  }
+
+  fn to_json(&self) -> (String, Value) {
+    let value = serde_json::json!({
+      "scopes": self.scopes,
+      "vars": self.vars,
+    });
+    ("tree".into(), value)
+  }
+}
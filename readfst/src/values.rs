@@ -0,0 +1,211 @@
+use crate::section::{Item, Section};
+use clap::ValueEnum;
+use fstapi::{Handle, Reader, Result, Value, VarType};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use tabled::Tabled;
+
+/// Radix of displayed values.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Radix {
+  /// Binary, the same as values stored in the waveform.
+  Bin,
+  /// Hexadecimal, a digit is `x` if any of its bits is unknown,
+  /// or `z` if all of its bits are high impedance.
+  Hex,
+  /// Decimal, falls back to binary if there are unknown bits,
+  /// or the value is too large.
+  Dec,
+}
+
+impl Radix {
+  /// Formats the given value of a variable of the given type.
+  fn format(self, ty: VarType, value: &[u8]) -> String {
+    let Ok(value) = Value::new(ty, value) else {
+      return String::from_utf8_lossy(value).into();
+    };
+    match (self, &value) {
+      (Self::Hex, Value::Bits(v) | Value::Int(v)) => Some(v.to_hex_string()),
+      (Self::Dec, Value::Bits(v)) => v.to_u128().map(|v| v.to_string()),
+      (Self::Dec, Value::Int(v)) => v.to_i64().map(|v| v.to_string()),
+      _ => None,
+    }
+    .unwrap_or_else(|| value.to_string())
+  }
+}
+
+/// Value information.
+#[derive(Tabled, Serialize)]
+pub struct ValueInfo {
+  #[tabled(rename = "Time")]
+  time: u64,
+  #[tabled(rename = "Name")]
+  name: String,
+  #[tabled(rename = "Value")]
+  value: String,
+}
+
+/// Value displayed if the value of a variable is not available,
+/// like before the first value change.
+const UNAVAILABLE: &str = "?";
+
+/// Variable whose values are displayed.
+struct VarEntry {
+  name: String,
+  handle: Handle,
+  ty: VarType,
+  /// `true` if the variable is a variable-length one, like strings.
+  var_len: bool,
+}
+
+/// Values information.
+pub struct Values {
+  values: Vec<ValueInfo>,
+}
+
+impl Values {
+  /// Collects values of variables whose full names match the given regex,
+  /// at each of the given times.
+  ///
+  /// Values that are not available are displayed as [`UNAVAILABLE`].
+  pub fn at(reader: &mut Reader, re: &Regex, times: &[u64], radix: Radix) -> Result<Self> {
+    let vars = match_vars(reader, re)?;
+    let var_len_values = var_len_values_at(reader, &vars, times)?;
+    let mut values = Vec::new();
+    let mut buf = Vec::new();
+    for (&time, var_len_values) in times.iter().zip(&var_len_values) {
+      for var in &vars {
+        let value = if var.var_len {
+          var_len_values
+            .get(&var.handle)
+            .map(|v| radix.format(var.ty, v))
+        } else {
+          let ret = reader.value_at_into(var.handle, time, &mut buf);
+          ret.ok().map(|_| radix.format(var.ty, &buf))
+        };
+        values.push(ValueInfo {
+          time,
+          name: var.name.clone(),
+          value: value.unwrap_or_else(|| UNAVAILABLE.into()),
+        });
+      }
+    }
+    Ok(Self { values })
+  }
+
+  /// Collects all value changes of variables whose full names match
+  /// the given regex, between the given start time and end time.
+  pub fn between(
+    reader: &mut Reader,
+    re: &Regex,
+    start: u64,
+    end: u64,
+    radix: Radix,
+  ) -> Result<Self> {
+    let vars = match_vars(reader, re)?;
+    let mut handles: HashMap<_, Vec<_>> = HashMap::new();
+    for var in &vars {
+      handles.entry(var.handle).or_default().push(var);
+    }
+    reader.clear_mask_all();
+    for &handle in handles.keys() {
+      reader.set_mask(handle);
+    }
+    reader.set_time_range_limit(start, end);
+    let mut values = Vec::new();
+    let ret = reader.for_each_block(|time, handle, value, _| {
+      // Value changes before the start time may be reported.
+      if time < start || time > end {
+        return;
+      }
+      for var in &handles[&handle] {
+        values.push(ValueInfo {
+          time,
+          name: var.name.clone(),
+          value: radix.format(var.ty, value),
+        });
+      }
+    });
+    reader.clear_mask_all();
+    reader.reset_time_range_limit();
+    ret?;
+    Ok(Self { values })
+  }
+}
+
+/// Returns variables whose full names match the given regex.
+fn match_vars(reader: &mut Reader, re: &Regex) -> Result<Vec<VarEntry>> {
+  let mut vars = Vec::new();
+  for var in reader.vars() {
+    let (name, var) = var?;
+    if re.is_match(&name) {
+      vars.push(VarEntry {
+        name,
+        handle: var.handle(),
+        ty: var.ty(),
+        var_len: var.length() == 0,
+      });
+    }
+  }
+  Ok(vars)
+}
+
+/// Returns values of the variable-length variables in the given variables
+/// at each of the given times, by iterating over value changes,
+/// since they can not be read by [`Reader::value_at`].
+fn var_len_values_at(
+  reader: &mut Reader,
+  vars: &[VarEntry],
+  times: &[u64],
+) -> Result<Vec<HashMap<Handle, Vec<u8>>>> {
+  let mut values = vec![HashMap::new(); times.len()];
+  // Values do not change after the end time.
+  let last_time = times.iter().max().map(|t| (*t).min(reader.end_time()));
+  let Some(last_time) = last_time.filter(|_| vars.iter().any(|v| v.var_len)) else {
+    return Ok(values);
+  };
+  reader.clear_mask_all();
+  for var in vars.iter().filter(|v| v.var_len) {
+    reader.set_mask(var.handle);
+  }
+  reader.set_time_range_limit(reader.start_time(), last_time);
+  // Visit times in order, and take a snapshot of the current values
+  // before the first value change after each time.
+  let mut order: Vec<_> = (0..times.len()).collect();
+  order.sort_unstable_by_key(|i| times[*i]);
+  let mut order = order.into_iter().peekable();
+  let mut current = HashMap::new();
+  let mut ret = Ok(());
+  for change in reader.changes() {
+    let change = match change {
+      Ok(change) => change,
+      Err(e) => {
+        ret = Err(e);
+        break;
+      }
+    };
+    while let Some(i) = order.next_if(|i| times[*i] < change.time) {
+      values[i] = current.clone();
+    }
+    current.insert(change.handle, change.value.into_vec());
+  }
+  for i in order {
+    values[i] = current.clone();
+  }
+  reader.clear_mask_all();
+  reader.reset_time_range_limit();
+  ret.map(|_| values)
+}
+
+impl Section for Values {
+  type Item = ValueInfo;
+
+  fn name() -> &'static str {
+    "Values"
+  }
+
+  fn item(&self) -> Item<'_, Self::Item> {
+    Item::Many(&self.values)
+  }
+}
@@ -1,5 +1,8 @@
-use crate::section::{Item, Print, Section, ToTable};
-use fstapi::{Handle, Reader, Result, Var, var_dir, var_type};
+use crate::section::{self, Item, Print, Section, ToTable};
+use fstapi::{Handle, Reader, Result, Var, VarDir, VarType, var_dir, var_type};
+use regex::Regex;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::mem;
 use tabled::object::{FirstRow, LastColumn};
@@ -9,11 +12,13 @@ use tabled::{Alignment, Disable, Modify, Panel, Tabled};
 type VarNames = HashMap<Handle, Box<str>>;
 
 /// Variable information.
-#[derive(Tabled)]
+#[derive(Tabled, Serialize)]
 pub struct VarInfo {
   #[tabled(rename = "Handle")]
+  #[serde(serialize_with = "serialize_handle")]
   handle: Handle,
   #[tabled(rename = "Type")]
+  #[serde(rename = "type")]
   ty: &'static str,
   #[tabled(rename = "Direction")]
   direction: &'static str,
@@ -29,48 +34,8 @@ impl VarInfo {
   fn new(name: &str, var: &Var, alias_of: &'static str) -> Self {
     Self {
       handle: var.handle(),
-      ty: match var.ty() {
-        var_type::VCD_EVENT => "VcdEvent",
-        var_type::VCD_INTEGER => "VcdInteger",
-        var_type::VCD_PARAMETER => "VcdParameter",
-        var_type::VCD_REAL => "VcdReal",
-        var_type::VCD_REAL_PARAMETER => "VcdRealParameter",
-        var_type::VCD_REG => "VcdReg",
-        var_type::VCD_SUPPLY0 => "VcdSupply0",
-        var_type::VCD_SUPPLY1 => "VcdSupply1",
-        var_type::VCD_TIME => "VcdTime",
-        var_type::VCD_TRI => "VcdTri",
-        var_type::VCD_TRIAND => "VcdTriand",
-        var_type::VCD_TRIOR => "VcdTrior",
-        var_type::VCD_TRIREG => "VcdTrireg",
-        var_type::VCD_TRI0 => "VcdTri0",
-        var_type::VCD_TRI1 => "VcdTri1",
-        var_type::VCD_WAND => "VcdWand",
-        var_type::VCD_WIRE => "VcdWire",
-        var_type::VCD_WOR => "VcdWor",
-        var_type::VCD_PORT => "VcdPort",
-        var_type::VCD_SPARRAY => "VcdSparray",
-        var_type::VCD_REALTIME => "VcdRealtime",
-        var_type::GEN_STRING => "GenString",
-        var_type::SV_BIT => "SvBit",
-        var_type::SV_LOGIC => "SvLogic",
-        var_type::SV_INT => "SvInt",
-        var_type::SV_SHORTINT => "SvShortint",
-        var_type::SV_LONGINT => "SvLongint",
-        var_type::SV_BYTE => "SvByte",
-        var_type::SV_ENUM => "SvEnum",
-        var_type::SV_SHORTREAL => "SvShortreal",
-        _ => unreachable!(),
-      },
-      direction: match var.direction() {
-        var_dir::IMPLICIT => "Implicit",
-        var_dir::INPUT => "Input",
-        var_dir::OUTPUT => "Output",
-        var_dir::INOUT => "Inout",
-        var_dir::BUFFER => "Buffer",
-        var_dir::LINKAGE => "Linkage",
-        _ => unreachable!(),
-      },
+      ty: var_type_name(var.ty()),
+      direction: var_dir_name(var.direction()),
       name: name.into(),
       length: var.length(),
       alias_of,
@@ -78,9 +43,117 @@ impl VarInfo {
   }
 }
 
+/// Returns the name of the given variable type.
+pub fn var_type_name(ty: VarType) -> &'static str {
+  match ty {
+    var_type::VCD_EVENT => "VcdEvent",
+    var_type::VCD_INTEGER => "VcdInteger",
+    var_type::VCD_PARAMETER => "VcdParameter",
+    var_type::VCD_REAL => "VcdReal",
+    var_type::VCD_REAL_PARAMETER => "VcdRealParameter",
+    var_type::VCD_REG => "VcdReg",
+    var_type::VCD_SUPPLY0 => "VcdSupply0",
+    var_type::VCD_SUPPLY1 => "VcdSupply1",
+    var_type::VCD_TIME => "VcdTime",
+    var_type::VCD_TRI => "VcdTri",
+    var_type::VCD_TRIAND => "VcdTriand",
+    var_type::VCD_TRIOR => "VcdTrior",
+    var_type::VCD_TRIREG => "VcdTrireg",
+    var_type::VCD_TRI0 => "VcdTri0",
+    var_type::VCD_TRI1 => "VcdTri1",
+    var_type::VCD_WAND => "VcdWand",
+    var_type::VCD_WIRE => "VcdWire",
+    var_type::VCD_WOR => "VcdWor",
+    var_type::VCD_PORT => "VcdPort",
+    var_type::VCD_SPARRAY => "VcdSparray",
+    var_type::VCD_REALTIME => "VcdRealtime",
+    var_type::GEN_STRING => "GenString",
+    var_type::SV_BIT => "SvBit",
+    var_type::SV_LOGIC => "SvLogic",
+    var_type::SV_INT => "SvInt",
+    var_type::SV_SHORTINT => "SvShortint",
+    var_type::SV_LONGINT => "SvLongint",
+    var_type::SV_BYTE => "SvByte",
+    var_type::SV_ENUM => "SvEnum",
+    var_type::SV_SHORTREAL => "SvShortreal",
+    _ => unreachable!(),
+  }
+}
+
+/// Returns the name of the given variable direction.
+pub fn var_dir_name(dir: VarDir) -> &'static str {
+  match dir {
+    var_dir::IMPLICIT => "Implicit",
+    var_dir::INPUT => "Input",
+    var_dir::OUTPUT => "Output",
+    var_dir::INOUT => "Inout",
+    var_dir::BUFFER => "Buffer",
+    var_dir::LINKAGE => "Linkage",
+    _ => unreachable!(),
+  }
+}
+
+/// Serializes the given handle as an integer.
+pub fn serialize_handle<S>(handle: &Handle, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  serializer.serialize_u32((*handle).into())
+}
+
+/// Parses variable type from the given name, like `VcdReg` or `reg`.
+pub fn parse_var_type(s: &str) -> std::result::Result<VarType, String> {
+  (var_type::MIN..=var_type::MAX)
+    .find(|&ty| is_name_of(var_type_name(ty), s))
+    .ok_or_else(|| format!("unknown variable type `{s}`"))
+}
+
+/// Parses variable direction from the given name, like `Input` or `input`.
+pub fn parse_var_dir(s: &str) -> std::result::Result<VarDir, String> {
+  (var_dir::MIN..=var_dir::MAX)
+    .find(|&dir| is_name_of(var_dir_name(dir), s))
+    .ok_or_else(|| format!("unknown variable direction `{s}`"))
+}
+
+/// Returns `true` if the given string is the given name,
+/// or the given name without prefix `Vcd`, `Sv` or `Gen`, ignoring case.
+fn is_name_of(name: &str, s: &str) -> bool {
+  let short = ["Vcd", "Sv", "Gen"]
+    .iter()
+    .find_map(|p| name.strip_prefix(p))
+    .unwrap_or(name);
+  name.eq_ignore_ascii_case(s) || short.eq_ignore_ascii_case(s)
+}
+
+/// Filter of variables.
+#[derive(Default)]
+pub struct VarFilter {
+  /// Regex of full names.
+  pub name: Option<Regex>,
+  /// Allowed types, all types are allowed if empty.
+  pub types: Vec<VarType>,
+  /// Allowed directions, all directions are allowed if empty.
+  pub dirs: Vec<VarDir>,
+  /// Minimum length in bits.
+  pub min_width: Option<u32>,
+  /// Maximum length in bits.
+  pub max_width: Option<u32>,
+}
+
+impl VarFilter {
+  /// Returns `true` if the given variable matches the filter.
+  fn is_match(&self, name: &str, var: &Var) -> bool {
+    self.name.as_ref().is_none_or(|re| re.is_match(name))
+      && (self.types.is_empty() || self.types.contains(&var.ty()))
+      && (self.dirs.is_empty() || self.dirs.contains(&var.direction()))
+      && self.min_width.is_none_or(|w| var.length() >= w)
+      && self.max_width.is_none_or(|w| var.length() <= w)
+  }
+}
+
 /// Trait for variable section.
 pub trait VarSection: Sized {
-  fn new(reader: &mut Reader) -> Result<Self>;
+  fn new(reader: &mut Reader, filter: &VarFilter) -> Result<Self>;
   fn vars(&self) -> &[VarInfo];
 }
 
@@ -91,7 +164,7 @@ pub struct Variables {
 }
 
 impl Variables {
-  fn new(reader: &mut Reader, no_aliases: bool) -> Result<Self> {
+  fn new(reader: &mut Reader, filter: &VarFilter, no_aliases: bool) -> Result<Self> {
     let mut vars = Vec::new();
     let mut names = VarNames::new();
     for var in reader.vars() {
@@ -106,7 +179,9 @@ impl Variables {
       } else {
         ""
       };
-      vars.push(VarInfo::new(&name, &var, alias_of));
+      if filter.is_match(&name, &var) {
+        vars.push(VarInfo::new(&name, &var, alias_of));
+      }
       // Update handle-name map.
       if !var.is_alias() {
         assert!(names.insert(var.handle(), name.into()).is_none());
@@ -120,8 +195,8 @@ impl Variables {
 }
 
 impl VarSection for Variables {
-  fn new(reader: &mut Reader) -> Result<Self> {
-    Self::new(reader, false)
+  fn new(reader: &mut Reader, filter: &VarFilter) -> Result<Self> {
+    Self::new(reader, filter, false)
   }
 
   fn vars(&self) -> &[VarInfo] {
@@ -145,8 +220,8 @@ impl Section for Variables {
 pub struct NoAliasesVars(Variables);
 
 impl VarSection for NoAliasesVars {
-  fn new(reader: &mut Reader) -> Result<Self> {
-    Variables::new(reader, true).map(Self)
+  fn new(reader: &mut Reader, filter: &VarFilter) -> Result<Self> {
+    Variables::new(reader, filter, true).map(Self)
   }
 
   fn vars(&self) -> &[VarInfo] {
@@ -166,6 +241,29 @@ impl ToTable for NoAliasesVars {
   }
 }
 
+impl Print for NoAliasesVars {
+  fn print_table(&self) {
+    section::print_table(self.to_table());
+  }
+
+  fn print_csv(&self) {
+    let headers = VarInfo::headers();
+    section::print_csv_record(&headers[..headers.len() - 1]);
+    for var in self.vars() {
+      let fields = var.fields();
+      section::print_csv_record(&fields[..fields.len() - 1]);
+    }
+  }
+
+  fn to_json(&self) -> (String, Value) {
+    let (key, mut value) = Print::to_json(&self.0);
+    for var in value.as_array_mut().unwrap() {
+      var.as_object_mut().unwrap().remove("alias_of");
+    }
+    (key, value)
+  }
+}
+
 /// Variables information with names only.
 pub struct NameOnly<V>(V);
 
@@ -173,8 +271,8 @@ impl<V> VarSection for NameOnly<V>
 where
   V: VarSection,
 {
-  fn new(reader: &mut Reader) -> Result<Self> {
-    V::new(reader).map(Self)
+  fn new(reader: &mut Reader, filter: &VarFilter) -> Result<Self> {
+    V::new(reader, filter).map(Self)
   }
 
   fn vars(&self) -> &[VarInfo] {
@@ -186,11 +284,23 @@ impl<V> Print for NameOnly<V>
 where
   V: VarSection,
 {
-  fn print(&self) {
+  fn print_table(&self) {
     for var in self.vars() {
       println!("{}", var.name);
     }
   }
+
+  fn print_csv(&self) {
+    section::print_csv_record(["Name"]);
+    for var in self.vars() {
+      section::print_csv_record([&var.name]);
+    }
+  }
+
+  fn to_json(&self) -> (String, Value) {
+    let names = self.vars().iter().map(|v| v.name.clone().into()).collect();
+    (Variables::name().to_lowercase(), Value::Array(names))
+  }
 }
 
 /// Variables information with names only and with aliases.
@@ -0,0 +1,189 @@
+//! Helpers shared by integration tests.
+
+#![allow(dead_code)]
+
+use fstapi::{Handle, Writer, scope_type, var_dir, var_type};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::{env, fs, process};
+
+/// A temporary waveform file, which is removed when dropped.
+pub struct TempFile(PathBuf);
+
+impl TempFile {
+  /// Creates a new temporary file path with the given name.
+  pub fn new(name: &str) -> Self {
+    let file_name = format!("readfst-test-{}-{name}.fst", process::id());
+    Self(env::temp_dir().join(file_name))
+  }
+
+  /// Returns the path of the file.
+  pub fn path(&self) -> &Path {
+    &self.0
+  }
+}
+
+impl Drop for TempFile {
+  fn drop(&mut self) {
+    let _ = fs::remove_file(&self.0);
+    // The hierarchy is written to a separate file if it is not compressed.
+    let mut hier = self.0.clone().into_os_string();
+    hier.push(".hier");
+    let _ = fs::remove_file(hier);
+  }
+}
+
+/// Runs `readfst` on the given file with the given arguments.
+pub fn run_readfst(file: &TempFile, args: &[&str]) -> Output {
+  Command::new(env!("CARGO_BIN_EXE_readfst"))
+    .arg(file.path())
+    .args(args)
+    .output()
+    .unwrap()
+}
+
+/// Runs `readfst` on the given file with the given arguments,
+/// returns the standard output.
+///
+/// Panics if `readfst` fails.
+pub fn readfst(file: &TempFile, args: &[&str]) -> String {
+  let output = run_readfst(file, args);
+  let stderr = String::from_utf8_lossy(&output.stderr);
+  assert!(output.status.success(), "readfst failed: {stderr}");
+  String::from_utf8(output.stdout).unwrap()
+}
+
+/// Runs `readfst` on the given file with the given arguments and `-f json`,
+/// returns the parsed output.
+pub fn readfst_json(file: &TempFile, args: &[&str]) -> serde_json::Value {
+  let args: Vec<_> = args.iter().copied().chain(["-f", "json"]).collect();
+  serde_json::from_str(&readfst(file, &args)).unwrap()
+}
+
+/// End times of value change blocks of the waveform written by [`write_blocks`].
+pub const BLOCK_ENDS: [u64; 3] = [165, 330, 495];
+
+/// End time of the waveform written by [`write_blocks`].
+pub const END_TIME: u64 = 600;
+
+/// Handles of variables in the waveform written by [`write_blocks`].
+pub struct BlocksVars {
+  /// 8-bit counter `top.counter`, increases by 1 every 15 time units.
+  pub counter: Handle,
+  /// 1-bit toggle `top.toggle`, toggles every 15 time units.
+  pub toggle: Handle,
+  /// String `top.string`, which is `s` followed by the counter.
+  pub string: Handle,
+}
+
+/// Writes a waveform with three value change blocks to the given file.
+///
+/// All variables change every 15 time units until the end of the last block.
+pub fn write_blocks(file: &TempFile) -> BlocksVars {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let vars = BlocksVars {
+    counter: writer
+      .create_var(var_type::VCD_REG, var_dir::OUTPUT, 8, "counter", None)
+      .unwrap(),
+    toggle: writer
+      .create_var(var_type::VCD_WIRE, var_dir::OUTPUT, 1, "toggle", None)
+      .unwrap(),
+    string: writer
+      .create_var(var_type::GEN_STRING, var_dir::OUTPUT, 0, "string", None)
+      .unwrap(),
+  };
+  writer.set_upscope();
+  for time in (0..=BLOCK_ENDS[2]).step_by(15) {
+    let count = time / 15;
+    writer.emit_time_change(time).unwrap();
+    writer
+      .emit_value_change(vars.counter, format!("{count:08b}").as_bytes())
+      .unwrap();
+    writer
+      .emit_value_change(vars.toggle, (count % 2).to_string().as_bytes())
+      .unwrap();
+    writer
+      .emit_var_len_value_change(vars.string, format!("s{count}").as_bytes())
+      .unwrap();
+    if BLOCK_ENDS.contains(&time) {
+      writer.flush();
+    }
+  }
+  writer.emit_time_change(END_TIME).unwrap();
+  vars
+}
+
+/// Writes a waveform with nested scopes and variables of
+/// different types, directions and widths to the given file:
+///
+/// ```text
+/// rst: VcdWire, Input [1]
+/// top (VcdModule, Top)
+/// ├── core (VcdModule, Core)
+/// │   ├── clk: VcdWire, Input [1] -> top.clk
+/// │   ├── state: VcdReg, Output [2]
+/// │   └── temp: VcdReal, Output [8]
+/// ├── empty (VcdModule)
+/// ├── clk: VcdWire, Input [1]
+/// ├── data: VcdReg, Inout [16]
+/// └── count: VcdInteger, Output [32]
+/// ```
+///
+/// The length of the real variable `temp` is stored as 8 by the writer.
+pub fn write_hierarchy(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  let rst = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "rst", None)
+    .unwrap();
+  writer
+    .set_scope(scope_type::VCD_MODULE, "top", "Top")
+    .unwrap();
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  let data = writer
+    .create_var(var_type::VCD_REG, var_dir::INOUT, 16, "data", None)
+    .unwrap();
+  let count = writer
+    .create_var(var_type::VCD_INTEGER, var_dir::OUTPUT, 32, "count", None)
+    .unwrap();
+  writer
+    .set_scope(scope_type::VCD_MODULE, "core", "Core")
+    .unwrap();
+  writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", Some(clk))
+    .unwrap();
+  let state = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 2, "state", None)
+    .unwrap();
+  let temp = writer
+    .create_var(var_type::VCD_REAL, var_dir::OUTPUT, 64, "temp", None)
+    .unwrap();
+  writer.set_upscope();
+  writer
+    .set_scope(scope_type::VCD_MODULE, "empty", "")
+    .unwrap();
+  writer.set_upscope();
+  writer.set_upscope();
+  writer.emit_time_change(0).unwrap();
+  writer.emit_value_change(rst, b"1").unwrap();
+  writer.emit_value_change(clk, b"0").unwrap();
+  writer.emit_value_change(data, &[b'0'; 16]).unwrap();
+  writer.emit_value_change(count, &[b'0'; 32]).unwrap();
+  writer.emit_value_change(state, b"00").unwrap();
+  writer
+    .emit_value_change(temp, &0.5f64.to_ne_bytes())
+    .unwrap();
+  for time in (10..=100).step_by(10) {
+    writer.emit_time_change(time).unwrap();
+    writer.emit_value_change(rst, b"0").unwrap();
+    let clk_value = if time % 20 == 0 { b"0" } else { b"1" };
+    writer.emit_value_change(clk, clk_value).unwrap();
+    if time % 40 == 0 {
+      let value = format!("{:02b}", time / 40);
+      writer.emit_value_change(state, value.as_bytes()).unwrap();
+    }
+  }
+  writer.emit_time_change(120).unwrap();
+}
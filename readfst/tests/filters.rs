@@ -0,0 +1,129 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+
+/// Returns names of variables displayed by `readfst -v` with the given
+/// extra arguments.
+fn var_names(file: &TempFile, args: &[&str]) -> Vec<String> {
+  let mut args = args.to_vec();
+  args.extend(["-v", "--names-only", "-f", "csv"]);
+  readfst(file, &args)
+    .lines()
+    .skip(1)
+    .map(str::to_string)
+    .collect()
+}
+
+#[test]
+fn no_filter() {
+  let file = TempFile::new("filters-no-filter");
+  write_hierarchy(&file);
+  let all = [
+    "rst",
+    "top.clk",
+    "top.data",
+    "top.count",
+    "top.core.clk",
+    "top.core.state",
+    "top.core.temp",
+  ];
+  assert_eq!(var_names(&file, &[]), all);
+  // `top.core.clk` is an alias of `top.clk`.
+  let names = var_names(&file, &["--no-aliases"]);
+  assert!(all.iter().filter(|n| **n != "top.core.clk").eq(&names));
+}
+
+#[test]
+fn name_regex() {
+  let file = TempFile::new("filters-name-regex");
+  write_hierarchy(&file);
+  assert_eq!(
+    var_names(&file, &["--filter", "core"]),
+    ["top.core.clk", "top.core.state", "top.core.temp"]
+  );
+  assert_eq!(
+    var_names(&file, &["--filter", r"clk$", "--no-aliases"]),
+    ["top.clk"]
+  );
+  assert!(var_names(&file, &["--filter", "^core"]).is_empty());
+
+  let output = run_readfst(&file, &["-v", "--filter", "("]);
+  assert!(!output.status.success());
+}
+
+#[test]
+fn types_and_dirs() {
+  let file = TempFile::new("filters-types-and-dirs");
+  write_hierarchy(&file);
+  assert_eq!(
+    var_names(&file, &["--type", "reg,integer"]),
+    ["top.data", "top.count", "top.core.state"]
+  );
+  // Full names are accepted, ignoring case.
+  assert_eq!(var_names(&file, &["--type", "VcdReal"]), ["top.core.temp"]);
+  assert_eq!(
+    var_names(&file, &["--dir", "input", "--no-aliases"]),
+    ["rst", "top.clk"]
+  );
+  assert_eq!(
+    var_names(&file, &["--dir", "inout,OUTPUT", "--type", "reg"]),
+    ["top.data", "top.core.state"]
+  );
+
+  let output = run_readfst(&file, &["-v", "--type", "foo"]);
+  assert!(!output.status.success());
+  let stderr = String::from_utf8(output.stderr).unwrap();
+  assert!(stderr.contains("unknown variable type `foo`"), "{stderr}");
+  let output = run_readfst(&file, &["-v", "--dir", "sideways"]);
+  assert!(!output.status.success());
+  let stderr = String::from_utf8(output.stderr).unwrap();
+  assert!(
+    stderr.contains("unknown variable direction `sideways`"),
+    "{stderr}"
+  );
+}
+
+#[test]
+fn widths() {
+  let file = TempFile::new("filters-widths");
+  write_hierarchy(&file);
+  assert_eq!(
+    var_names(&file, &["--min-width", "16"]),
+    ["top.data", "top.count"]
+  );
+  assert_eq!(
+    var_names(&file, &["--max-width", "1", "--no-aliases"]),
+    ["rst", "top.clk"]
+  );
+  // Real variables are stored with length 8.
+  assert_eq!(
+    var_names(&file, &["--min-width", "2", "--max-width", "8"]),
+    ["top.core.state", "top.core.temp"]
+  );
+  assert!(var_names(&file, &["--min-width", "33"]).is_empty());
+}
+
+#[test]
+fn filtered_details() {
+  let file = TempFile::new("filters-filtered-details");
+  write_hierarchy(&file);
+  let json = readfst_json(&file, &["-v", "--filter", "core", "--type", "wire"]);
+  assert_eq!(
+    json["variables"],
+    serde_json::json!([{
+      "handle": 2,
+      "type": "VcdWire",
+      "direction": "Input",
+      "name": "top.core.clk",
+      "length": 1,
+      "alias_of": "top.clk",
+    }])
+  );
+  let json = readfst_json(
+    &file,
+    &["-v", "--filter", "core", "--type", "wire", "--no-aliases"],
+  );
+  assert_eq!(json["variables"], serde_json::json!([]));
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Writer, scope_type, var_dir, var_type};
+use serde_json::json;
+
+/// Writes a waveform with quotes and commas in its date and variable name.
+fn write_special_chars(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true)
+    .unwrap()
+    .date("Mon, 1 \"Jan\"")
+    .unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let var = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "a,b", None)
+    .unwrap();
+  writer.set_upscope();
+  writer.emit_time_change(0).unwrap();
+  writer.emit_value_change(var, b"1").unwrap();
+}
+
+#[test]
+fn json_sections() {
+  let file = TempFile::new("formats-json-sections");
+  write_blocks(&file);
+  let json = readfst_json(&file, &["-m", "-v"]);
+  let keys: Vec<_> = json.as_object().unwrap().keys().collect();
+  assert_eq!(keys, ["metadata", "variables"]);
+  let metadata = &json["metadata"];
+  assert_eq!(metadata["start_time"], 0);
+  assert_eq!(metadata["end_time"], END_TIME);
+  assert_eq!(metadata["num_scopes"], 1);
+  assert_eq!(metadata["num_vars"], 3);
+  assert_eq!(metadata["num_aliases"], 0);
+  assert_eq!(
+    json["variables"][0],
+    json!({
+      "handle": 1,
+      "type": "VcdReg",
+      "direction": "Output",
+      "name": "top.counter",
+      "length": 8,
+      "alias_of": "",
+    })
+  );
+  assert_eq!(json["variables"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn csv_sections() {
+  let file = TempFile::new("formats-csv-sections");
+  write_blocks(&file);
+  let output = readfst(&file, &["-m", "-v", "-f", "csv"]);
+  let sections: Vec<Vec<_>> = output
+    .trim_end()
+    .split("\n\n")
+    .map(|s| s.lines().collect())
+    .collect();
+  assert_eq!(sections.len(), 2);
+  assert_eq!(sections[0].len(), 2);
+  assert!(sections[0][0].starts_with("Date,Version,File type,"));
+  assert!(sections[0][1].ends_with(",0,600,1,3,0"));
+  assert_eq!(
+    sections[1],
+    [
+      "Handle,Type,Direction,Name,Length in Bits,Alias Of",
+      "1,VcdReg,Output,top.counter,8,",
+      "2,VcdWire,Output,top.toggle,1,",
+      "3,GenString,Output,top.string,0,",
+    ]
+  );
+}
+
+#[test]
+fn csv_quoting() {
+  let file = TempFile::new("formats-csv-quoting");
+  write_special_chars(&file);
+  let output = readfst(&file, &["-m", "-f", "csv"]);
+  let row = output.lines().nth(1).unwrap();
+  assert!(row.starts_with(r#""Mon, 1 ""Jan""",fstWriter,"#), "{row}");
+  let output = readfst(&file, &["-v", "--names-only", "-f", "csv"]);
+  assert_eq!(output.lines().nth(1), Some(r#""top.a,b""#));
+  // JSON output is not affected.
+  let json = readfst_json(&file, &["-m"]);
+  assert_eq!(json["metadata"]["date"], "Mon, 1 \"Jan\"");
+}
+
+#[test]
+fn table_sections() {
+  let file = TempFile::new("formats-table-sections");
+  write_blocks(&file);
+  let output = readfst(&file, &["-m", "-v"]);
+  let titles: Vec<_> = output
+    .lines()
+    .map(|l| l.trim_matches(|c: char| c == '│' || c.is_whitespace()))
+    .filter(|l| *l == "Metadata" || *l == "Variables")
+    .collect();
+  assert_eq!(titles, ["Metadata", "Variables"]);
+  assert!(output.contains("top.counter"));
+}
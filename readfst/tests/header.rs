@@ -0,0 +1,112 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Writer, scope_type, var_dir, var_type, writer_pack_type};
+use serde_json::json;
+
+/// Writes a waveform with a clock to the given writer,
+/// the dump activity is changed at the given times.
+fn write_clock(mut writer: Writer, dump_activity: &[(u64, bool)]) {
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let clk = writer
+    .create_var(var_type::VCD_WIRE, var_dir::INPUT, 1, "clk", None)
+    .unwrap();
+  writer.set_upscope();
+  for time in (0..=100).step_by(10) {
+    writer.emit_time_change(time).unwrap();
+    for (_, active) in dump_activity.iter().filter(|(t, _)| *t == time) {
+      writer.emit_dump_active(*active);
+    }
+    let value = if time % 20 == 0 { b"0" } else { b"1" };
+    writer.emit_value_change(clk, value).unwrap();
+  }
+  writer.emit_time_change(120).unwrap();
+}
+
+#[test]
+fn header_of_waveform() {
+  let file = TempFile::new("header-of-waveform");
+  write_blocks(&file);
+  let json = readfst_json(&file, &["-H"]);
+  let keys: Vec<_> = json.as_object().unwrap().keys().collect();
+  assert_eq!(keys, ["header", "dump_activity"]);
+  let header = &json["header"];
+  // Metadata is included in the header.
+  assert_eq!(header["start_time"], 0);
+  assert_eq!(header["end_time"], END_TIME);
+  assert_eq!(header["num_vars"], 3);
+  assert_eq!(header["max_handle"], 3);
+  assert_eq!(header["num_vc_blocks"], BLOCK_ENDS.len());
+  assert_eq!(header["double_endian_match"], true);
+  assert_eq!(header["pack_type"], "Zlib");
+  assert_eq!(header["hier_compression"], "Gzip");
+  assert_eq!(header["gzip_wrapped"], false);
+  // Dumping is active all the time.
+  assert_eq!(
+    json["dump_activity"],
+    json!([{ "start_time": 0, "end_time": END_TIME, "active": true }])
+  );
+}
+
+#[test]
+fn compression() {
+  let file = TempFile::new("header-compression-fastlz");
+  let writer = Writer::create(file.path(), true).unwrap();
+  write_clock(writer.pack_type(writer_pack_type::FASTLZ), &[]);
+  let header = &readfst_json(&file, &["-H"])["header"];
+  assert_eq!(header["pack_type"], "FastLZ");
+  assert_eq!(header["hier_compression"], "Gzip");
+
+  // The hierarchy is compressed by LZ4 if value changes are.
+  let file = TempFile::new("header-compression-lz4");
+  let writer = Writer::create(file.path(), true).unwrap();
+  write_clock(writer.pack_type(writer_pack_type::LZ4), &[]);
+  let header = &readfst_json(&file, &["-H"])["header"];
+  assert_eq!(header["pack_type"], "LZ4");
+  let hier = header["hier_compression"].as_str().unwrap();
+  assert!(matches!(hier, "LZ4" | "LZ4Duo"), "{hier}");
+}
+
+#[test]
+fn gzip_wrapped() {
+  let file = TempFile::new("header-gzip-wrapped");
+  let writer = Writer::create(file.path(), true).unwrap();
+  write_clock(writer.repack_on_close(true), &[]);
+  let header = &readfst_json(&file, &["-H"])["header"];
+  assert_eq!(header["gzip_wrapped"], true);
+  // Blocks inside the wrapper are not visible.
+  assert_eq!(header["pack_type"], "Unknown");
+  assert_eq!(header["hier_compression"], "Unknown");
+  assert_eq!(header["end_time"], 120);
+}
+
+#[test]
+fn dump_activity_periods() {
+  let file = TempFile::new("header-dump-activity-periods");
+  let writer = Writer::create(file.path(), true).unwrap();
+  // Repeated changes and changes at the start time do not create periods.
+  let changes = [(0, true), (20, false), (30, false), (50, true), (80, false)];
+  write_clock(writer, &changes);
+  let json = readfst_json(&file, &["-H"]);
+  assert_eq!(
+    json["dump_activity"],
+    json!([
+      { "start_time": 0, "end_time": 20, "active": true },
+      { "start_time": 20, "end_time": 50, "active": false },
+      { "start_time": 50, "end_time": 80, "active": true },
+      { "start_time": 80, "end_time": 120, "active": false },
+    ])
+  );
+
+  let output = readfst(&file, &["-H", "-f", "csv"]);
+  let sections: Vec<_> = output.trim_end().split("\n\n").collect();
+  assert_eq!(sections.len(), 2);
+  assert!(sections[0].starts_with("Date,Version,File type,"));
+  assert!(sections[0].contains(",Max handle,Number of value change blocks,"));
+  assert_eq!(
+    sections[1],
+    "Start time,End time,Active\n0,20,true\n20,50,false\n50,80,true\n80,120,false"
+  );
+}
@@ -0,0 +1,128 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use fstapi::{Writer, scope_type, var_dir, var_type};
+use serde_json::{Value, json};
+
+/// Writes a waveform with variables of different types,
+/// some of them have unknown bits.
+fn write_stats(file: &TempFile) {
+  let mut writer = Writer::create(file.path(), true).unwrap();
+  writer.set_scope(scope_type::VCD_MODULE, "top", "").unwrap();
+  let bus = writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 4, "bus", None)
+    .unwrap();
+  let int = writer
+    .create_var(var_type::SV_INT, var_dir::OUTPUT, 8, "int", None)
+    .unwrap();
+  let real = writer
+    .create_var(var_type::VCD_REAL, var_dir::OUTPUT, 64, "real", None)
+    .unwrap();
+  let string = writer
+    .create_var(var_type::GEN_STRING, var_dir::OUTPUT, 0, "string", None)
+    .unwrap();
+  let flag = writer
+    .create_var(var_type::VCD_WIRE, var_dir::OUTPUT, 1, "flag", None)
+    .unwrap();
+  // Aliases are not counted.
+  writer
+    .create_var(var_type::VCD_REG, var_dir::OUTPUT, 4, "alias", Some(bus))
+    .unwrap();
+  writer.set_upscope();
+
+  writer.emit_time_change(0).unwrap();
+  writer.emit_value_change(bus, b"0000").unwrap();
+  writer.emit_value_change(int, b"11111110").unwrap();
+  writer
+    .emit_value_change(real, &1.5f64.to_ne_bytes())
+    .unwrap();
+  writer.emit_var_len_value_change(string, b"a").unwrap();
+  writer.emit_value_change(flag, b"0").unwrap();
+  for (time, value) in [(10, b"x000"), (30, b"0011"), (50, b"zzzz"), (60, b"1111")] {
+    writer.emit_time_change(time).unwrap();
+    writer.emit_value_change(bus, value).unwrap();
+    match time {
+      30 => writer.emit_value_change(int, b"00000101").unwrap(),
+      50 => writer
+        .emit_value_change(real, &(-2.25f64).to_ne_bytes())
+        .unwrap(),
+      _ => {}
+    }
+  }
+  writer.emit_time_change(90).unwrap();
+  writer.emit_value_change(flag, b"x").unwrap();
+  writer.emit_time_change(100).unwrap();
+}
+
+/// Returns names of variables in the statistics.
+fn names(json: &Value) -> Vec<&str> {
+  json["statistics"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|s| s["name"].as_str().unwrap())
+    .collect()
+}
+
+#[test]
+fn stats_of_vars() {
+  let file = TempFile::new("stats-of-vars");
+  write_stats(&file);
+  let json = readfst_json(&file, &["--stats", "--sort", "handle"]);
+  let expected = json!([
+    {
+      "handle": 1, "name": "top.bus", "changes": 5, "toggles": 12,
+      "first_change": 0, "last_change": 60, "xz_time": 30, "min": 0, "max": 15,
+    },
+    {
+      "handle": 2, "name": "top.int", "changes": 2, "toggles": 7,
+      "first_change": 0, "last_change": 30, "xz_time": 0, "min": -2, "max": 5,
+    },
+    {
+      "handle": 3, "name": "top.real", "changes": 2, "toggles": null,
+      "first_change": 0, "last_change": 50, "xz_time": null, "min": -2.25, "max": 1.5,
+    },
+    {
+      "handle": 4, "name": "top.string", "changes": 1, "toggles": null,
+      "first_change": 0, "last_change": 0, "xz_time": null, "min": null, "max": null,
+    },
+    {
+      "handle": 5, "name": "top.flag", "changes": 2, "toggles": 1,
+      "first_change": 0, "last_change": 90, "xz_time": 10, "min": 0, "max": 0,
+    },
+  ]);
+  assert_eq!(json["statistics"], expected);
+}
+
+#[test]
+fn stats_in_parallel() {
+  let file = TempFile::new("stats-in-parallel");
+  write_blocks(&file);
+  let expected = readfst_json(&file, &["-S"]);
+  for jobs in ["2", "4"] {
+    assert_eq!(readfst_json(&file, &["-S", "-j", jobs]), expected);
+  }
+  let counter = &expected["statistics"][0];
+  assert_eq!(counter["name"], "top.counter");
+  assert_eq!(counter["changes"], BLOCK_ENDS[2] / 15 + 1);
+  assert_eq!(counter["last_change"], BLOCK_ENDS[2]);
+}
+
+#[test]
+fn sort_and_top() {
+  let file = TempFile::new("stats-sort-and-top");
+  write_stats(&file);
+  let json = readfst_json(&file, &["-S"]);
+  assert_eq!(
+    names(&json),
+    ["top.bus", "top.int", "top.real", "top.flag", "top.string"]
+  );
+  let json = readfst_json(&file, &["-S", "--sort", "toggles"]);
+  assert_eq!(names(&json)[..3], ["top.bus", "top.int", "top.flag"]);
+  let json = readfst_json(&file, &["-S", "--sort", "xz-time", "--top", "2"]);
+  assert_eq!(names(&json), ["top.bus", "top.flag"]);
+  let json = readfst_json(&file, &["-S", "--sort", "name", "--top", "3"]);
+  assert_eq!(names(&json), ["top.bus", "top.flag", "top.int"]);
+}
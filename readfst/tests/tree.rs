@@ -0,0 +1,118 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+
+#[test]
+fn whole_tree() {
+  let file = TempFile::new("tree-whole-tree");
+  write_hierarchy(&file);
+  let expected = "\
+top (VcdModule, Top, 3 vars)
+├── core (VcdModule, Core, 3 vars)
+│   ├── clk: VcdWire [1] -> top.clk
+│   ├── state: VcdReg [2]
+│   └── temp: VcdReal [8]
+├── empty (VcdModule, 0 vars)
+├── clk: VcdWire [1]
+├── data: VcdReg [16]
+└── count: VcdInteger [32]
+rst: VcdWire [1]
+";
+  assert_eq!(readfst(&file, &["--tree"]), expected);
+}
+
+#[test]
+fn limited_depth() {
+  let file = TempFile::new("tree-limited-depth");
+  write_hierarchy(&file);
+  let expected = "\
+top (VcdModule, Top, 3 vars)
+├── core (VcdModule, Core, 3 vars)
+├── empty (VcdModule, 0 vars)
+├── clk: VcdWire [1]
+├── data: VcdReg [16]
+└── count: VcdInteger [32]
+rst: VcdWire [1]
+";
+  assert_eq!(readfst(&file, &["-t", "--depth", "1"]), expected);
+  let expected = "top (VcdModule, Top, 3 vars)\nrst: VcdWire [1]\n";
+  assert_eq!(readfst(&file, &["-t", "--depth", "0"]), expected);
+}
+
+#[test]
+fn root_scope() {
+  let file = TempFile::new("tree-root-scope");
+  write_hierarchy(&file);
+  let expected = "\
+top.core (VcdModule, Core, 3 vars)
+├── clk: VcdWire [1] -> top.clk
+├── state: VcdReg [2]
+└── temp: VcdReal [8]
+";
+  assert_eq!(readfst(&file, &["-t", "--root", "top.core"]), expected);
+  assert_eq!(
+    readfst(&file, &["-t", "--root", "top.empty"]),
+    "top.empty (VcdModule, 0 vars)\n"
+  );
+
+  let output = run_readfst(&file, &["-t", "--root", "core"]);
+  assert!(!output.status.success());
+  let stderr = String::from_utf8(output.stderr).unwrap();
+  assert_eq!(stderr.trim(), "Scope `core` not found.");
+}
+
+#[test]
+fn tree_csv() {
+  let file = TempFile::new("tree-csv");
+  write_hierarchy(&file);
+  let output = readfst(&file, &["-t", "-f", "csv"]);
+  let lines: Vec<_> = output.lines().collect();
+  assert_eq!(
+    lines,
+    [
+      "Depth,Kind,Name,Type,Direction,Component,Number of variables,Length in Bits,Alias Of",
+      "0,Scope,top,VcdModule,,Top,3,,",
+      "1,Scope,top.core,VcdModule,,Core,3,,",
+      "2,Var,top.core.clk,VcdWire,Input,,,1,top.clk",
+      "2,Var,top.core.state,VcdReg,Output,,,2,",
+      "2,Var,top.core.temp,VcdReal,Output,,,8,",
+      "1,Scope,top.empty,VcdModule,,,0,,",
+      "1,Var,top.clk,VcdWire,Input,,,1,",
+      "1,Var,top.data,VcdReg,Inout,,,16,",
+      "1,Var,top.count,VcdInteger,Output,,,32,",
+      "0,Var,rst,VcdWire,Input,,,1,",
+    ]
+  );
+}
+
+#[test]
+fn tree_json() {
+  let file = TempFile::new("tree-json");
+  write_hierarchy(&file);
+  let json = readfst_json(&file, &["-t", "--depth", "1"]);
+  let tree = &json["tree"];
+  let top = &tree["scopes"][0];
+  assert_eq!(top["name"], "top");
+  assert_eq!(top["component"], "Top");
+  assert_eq!(top["num_vars"], 3);
+  let scopes: Vec<_> = top["scopes"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|s| (s["name"].as_str().unwrap(), s["num_vars"].as_u64().unwrap()))
+    .collect();
+  assert_eq!(scopes, [("core", 3), ("empty", 0)]);
+  // Children of `core` are beyond the depth limit.
+  assert_eq!(top["scopes"][0]["vars"].as_array().unwrap().len(), 0);
+  let vars: Vec<_> = top["vars"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|v| v["name"].as_str().unwrap())
+    .collect();
+  assert_eq!(vars, ["clk", "data", "count"]);
+  assert_eq!(tree["vars"][0]["name"], "rst");
+  assert_eq!(tree["vars"][0]["direction"], "Input");
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "capi")]
+
+mod common;
+
+use common::*;
+use serde_json::json;
+
+#[test]
+fn values_at_block_boundaries() {
+  let file = TempFile::new("values-at-block-boundaries");
+  write_blocks(&file);
+  let json = readfst_json(
+    &file,
+    &["--values", "top", "--at", "165,330,495,550,600,601"],
+  );
+  let mut expected = Vec::new();
+  for time in [165, 330, 495, 550, 600, 601] {
+    // Values after the end time are the ones at the end time.
+    let count = time.min(495) / 15;
+    let (counter, toggle, string) = (
+      format!("{count:08b}"),
+      (count % 2).to_string(),
+      format!("s{count}"),
+    );
+    expected.push(json!({"time": time, "name": "top.counter", "value": counter}));
+    expected.push(json!({"time": time, "name": "top.toggle", "value": toggle}));
+    expected.push(json!({"time": time, "name": "top.string", "value": string}));
+  }
+  assert_eq!(json["values"], json!(expected));
+}
+
+#[test]
+fn values_at_unordered_times() {
+  let file = TempFile::new("values-at-unordered-times");
+  write_blocks(&file);
+  let json = readfst_json(
+    &file,
+    &[
+      "--values",
+      "string",
+      "--at",
+      "100,0,14,15",
+      "--radix",
+      "hex",
+    ],
+  );
+  let values: Vec<_> = json["values"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|v| v["value"].as_str().unwrap())
+    .collect();
+  assert_eq!(values, ["s6", "s0", "s0", "s1"]);
+}
+
+#[test]
+fn values_between() {
+  let file = TempFile::new("values-between");
+  write_blocks(&file);
+  let json = readfst_json(
+    &file,
+    &[
+      "--values", "counter", "--from", "160", "--to", "180", "--radix", "dec",
+    ],
+  );
+  assert_eq!(
+    json["values"],
+    json!([
+      {"time": 165, "name": "top.counter", "value": "11"},
+      {"time": 180, "name": "top.counter", "value": "12"},
+    ])
+  );
+}